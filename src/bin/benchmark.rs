@@ -2,12 +2,12 @@ use std::path::PathBuf;
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{
         read_edges_from_fmi_file, reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph,
     },
     reading_pathfinder,
     utility::{benchmark_distances, gen_tests_cases},
-    FileType,
 };
 
 /// Does a single threaded benchmark.
@@ -22,10 +22,6 @@ struct Args {
     #[arg(short, long)]
     file: PathBuf,
 
-    /// Type of the input file
-    #[arg(short = 't', long, value_enum, default_value = "fmi")]
-    file_type: FileType,
-
     /// Number of benchmarks to be run.
     #[arg(short, long)]
     number_of_benchmarks: u32,
@@ -38,7 +34,11 @@ fn main() {
     let edges = read_edges_from_fmi_file(&args.graph);
     let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
 
-    let pathfinder = reading_pathfinder(&args.file.as_path(), &args.file_type);
+    let pathfinder = reading_pathfinder(
+        &args.file.as_path(),
+        Some(&GraphFingerprint::of(graph.out_graph())),
+    )
+    .unwrap_or_else(|error| panic!("{}", error));
 
     let sources_and_targets = gen_tests_cases(graph.out_graph(), args.number_of_benchmarks);
 
@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use faster_paths::{
+    container::GraphFingerprint,
+    graphs::{
+        permutation::{Permutation, Renumber},
+        reordering::{breadth_first, by_level, depth_first, hilbert_curve},
+        vec_vec_graph::VecVecGraph,
+        vertex_attributes::AttributedGraph,
+        Graph, Vertex,
+    },
+    search::{
+        ch::contracted_graph::ContractedGraph, hl::hub_graph::HubGraph, reordered::Reordered,
+        PathFinding,
+    },
+    utility::{benchmark_distances, gen_tests_cases, read_container_with_spinnner},
+};
+
+/// Compares the query times of a contracted graph and a hub graph in the input
+/// vertex order with the same structures in cache friendlier orders.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    /// Contracted graph container
+    #[arg(short, long)]
+    contracted_graph: Option<PathBuf>,
+
+    /// Hub graph container
+    #[arg(short = 'u', long)]
+    hub_graph: Option<PathBuf>,
+
+    /// Number of benchmarks to be run.
+    #[arg(short, long)]
+    number_of_benchmarks: u32,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let AttributedGraph { graph, attributes } =
+        AttributedGraph::<VecVecGraph>::try_from_fmi_file(&args.graph)
+            .unwrap_or_else(|error| panic!("{}", error));
+    let fingerprint = GraphFingerprint::of(graph.out_graph());
+    let sources_and_targets = gen_tests_cases(graph.out_graph(), args.number_of_benchmarks);
+
+    let number_of_vertices = Graph::number_of_vertices(graph.out_graph());
+    let orders = [
+        ("breadth first", breadth_first(&graph)),
+        ("depth first", depth_first(&graph)),
+        (
+            "hilbert curve",
+            hilbert_curve(&attributes, number_of_vertices),
+        ),
+    ];
+
+    if let Some(path) = &args.contracted_graph {
+        let contracted_graph: ContractedGraph =
+            read_container_with_spinnner("contracted graph", path, Some(&fingerprint));
+        let level = by_level(contracted_graph.level_to_vertex()).unwrap();
+        compare_orders(
+            "contracted graph",
+            &contracted_graph,
+            orders.iter().chain([&("level", level)]),
+            &sources_and_targets,
+        );
+    }
+
+    if let Some(path) = &args.hub_graph {
+        let hub_graph: HubGraph =
+            read_container_with_spinnner("hub graph", path, Some(&fingerprint));
+        let level = by_level(&hub_graph.level_to_vertex).unwrap();
+        compare_orders(
+            "hub graph",
+            &hub_graph,
+            orders.iter().chain([&("level", level)]),
+            &sources_and_targets,
+        );
+    }
+}
+
+fn compare_orders<'a, P: PathFinding + Renumber>(
+    name: &str,
+    pathfinder: &P,
+    orders: impl Iterator<Item = &'a (&'a str, Permutation)>,
+    sources_and_targets: &[(Vertex, Vertex)],
+) {
+    let input_duration = benchmark_distances(pathfinder, sources_and_targets);
+    println!("{} in input order: {:?}", name, input_duration);
+
+    for (order, permutation) in orders {
+        let reordered = Reordered::new(pathfinder, permutation.clone());
+        for &(source, target) in sources_and_targets {
+            assert_eq!(
+                reordered.shortest_path_distance(source, target),
+                pathfinder.shortest_path_distance(source, target),
+                "{} order changed a distance",
+                order
+            );
+        }
+
+        let duration = benchmark_distances(&reordered, sources_and_targets);
+        println!(
+            "{} in {} order: {:?} ({:.2}x)",
+            name,
+            order,
+            duration,
+            input_duration.as_secs_f64() / duration.as_secs_f64()
+        );
+    }
+}
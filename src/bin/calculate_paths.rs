@@ -6,7 +6,6 @@ use faster_paths::{
     reading_pathfinder,
     search::PathFinding,
     utility::{get_progressbar, write_json_with_spinnner},
-    FileType,
 };
 use indicatif::ParallelProgressIterator;
 use itertools::Itertools;
@@ -20,10 +19,6 @@ struct Args {
     #[arg(short, long)]
     file: PathBuf,
 
-    /// Type of the input file
-    #[arg(short = 't', long, value_enum, default_value = "fmi")]
-    file_type: FileType,
-
     /// Path of test cases
     #[arg(short, long)]
     num_paths: u32,
@@ -36,7 +31,8 @@ struct Args {
 fn main() {
     let args = Args::parse();
 
-    let pathfinder = reading_pathfinder(&args.file.as_path(), &args.file_type);
+    let pathfinder =
+        reading_pathfinder(&args.file.as_path(), None).unwrap_or_else(|error| panic!("{}", error));
 
     let pb = get_progressbar("Getting paths", args.num_paths as u64);
 
@@ -8,7 +8,7 @@ use faster_paths::{
     search::{
         alt::landmark::Landmarks, collections::dijkstra_data::Path, DistanceHeuristic, PathFinding,
     },
-    utility::{level_to_vertex, read_bincode_with_spinnner, read_json_with_spinnner},
+    utility::{level_to_vertex, read_container_with_spinnner, read_json_with_spinnner},
 };
 use indicatif::ParallelProgressIterator;
 use itertools::Itertools;
@@ -37,11 +37,11 @@ fn main() {
 
     // Build graph
     let graph: ReversibleGraph<VecVecGraph> =
-        read_bincode_with_spinnner("graph", &args.graph.as_path());
+        read_container_with_spinnner("graph", &args.graph.as_path(), None);
 
     // Build graph
     let simple_graph: ReversibleGraph<VecVecGraph> =
-        read_bincode_with_spinnner("simple graph", &args.simple_graph.as_path());
+        read_container_with_spinnner("simple graph", &args.simple_graph.as_path(), None);
 
     println!(
         "true {}, simple {}",
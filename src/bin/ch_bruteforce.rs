@@ -1,17 +1,14 @@
-use std::{
-    fs::File,
-    io::{BufReader, BufWriter},
-    path::PathBuf,
-};
+use std::{fs::File, io::BufReader, path::PathBuf};
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{
         read_edges_from_fmi_file, reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph,
-        Vertex,
+        Graph, Vertex,
     },
     search::ch::contracted_graph::ContractedGraph,
-    utility::{benchmark_and_test_path, generate_test_cases},
+    utility::{benchmark_and_test_path, generate_test_cases, write_container_with_spinnner},
 };
 
 #[derive(Parser, Debug)]
@@ -54,8 +51,12 @@ fn main() {
     );
 
     // Write contracted_graph to file
-    let writer = BufWriter::new(File::create(&args.contracted_graph).unwrap());
-    bincode::serialize_into(writer, &contracted_graph).unwrap();
+    write_container_with_spinnner(
+        "contracted graph",
+        &args.contracted_graph,
+        &contracted_graph,
+        &GraphFingerprint::of(graph.out_graph()),
+    );
 
     // Benchmark and test correctness
     let tests = generate_test_cases(graph.out_graph(), 1_000);
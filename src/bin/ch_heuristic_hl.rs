@@ -1,10 +1,10 @@
-use std::{fs::File, io::BufReader, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
 use faster_paths::{
     graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph},
     search::{ch::contracted_graph::ContractedGraph, hl::hub_graph::HubGraph},
-    utility::{benchmark_and_test_path, generate_test_cases},
+    utility::{benchmark_and_test_path, generate_test_cases, read_container_with_spinnner},
 };
 
 /// Starts a routing service on localhost:3030/route
@@ -28,14 +28,13 @@ fn main() {
     let args = Args::parse();
 
     // Build graph
-    let reader = BufReader::new(File::open(&args.graph).unwrap());
-    let graph: ReversibleGraph<VecVecGraph> = bincode::deserialize_from(reader).unwrap();
+    let graph: ReversibleGraph<VecVecGraph> =
+        read_container_with_spinnner("graph", &args.graph, None);
 
-    let reader = BufReader::new(File::open(&args.contracted_graph).unwrap());
-    let contracted_graph: ContractedGraph = bincode::deserialize_from(reader).unwrap();
+    let contracted_graph: ContractedGraph =
+        read_container_with_spinnner("contracted graph", &args.contracted_graph, None);
 
-    let reader = BufReader::new(File::open(&args.hub_graph).unwrap());
-    let hub_graph: HubGraph = bincode::deserialize_from(reader).unwrap();
+    let hub_graph: HubGraph = read_container_with_spinnner("hub graph", &args.hub_graph, None);
 
     // Create contracted_graph
     let contracted_graph = ContractedGraph::by_contraction_top_down_with_heuristic(
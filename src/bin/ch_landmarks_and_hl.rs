@@ -1,7 +1,8 @@
-use std::{fs::File, io::BufWriter, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{
         read_edges_from_fmi_file, reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph,
         Distance, Vertex,
@@ -10,7 +11,10 @@ use faster_paths::{
         alt::landmark::Landmarks, ch::contracted_graph::ContractedGraph, hl::hub_graph::HubGraph,
         DistanceHeuristic, PathFinding,
     },
-    utility::{benchmark_and_test_path, generate_test_cases, read_bincode_with_spinnner},
+    utility::{
+        benchmark_and_test_path, generate_test_cases, read_container_with_spinnner,
+        write_container_with_spinnner,
+    },
 };
 
 /// Starts a routing service on localhost:3030/route
@@ -77,7 +81,11 @@ fn main() {
     let edges = read_edges_from_fmi_file(&args.graph);
     let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
 
-    let hub_graph: HubGraph = read_bincode_with_spinnner("hub graph", &args.hub_graph.as_path());
+    let hub_graph: HubGraph = read_container_with_spinnner(
+        "hub graph",
+        &args.hub_graph.as_path(),
+        Some(&GraphFingerprint::of(graph.out_graph())),
+    );
 
     // Create landmakrs
     let landmarks = Landmarks::random(&graph, 1);
@@ -91,8 +99,12 @@ fn main() {
     let contracted_graph = ContractedGraph::by_contraction_with_heuristic(&graph, &h);
 
     // Write contracted_graph to file
-    let writer = BufWriter::new(File::create(&args.contracted_graph).unwrap());
-    bincode::serialize_into(writer, &contracted_graph).unwrap();
+    write_container_with_spinnner(
+        "contracted graph",
+        &args.contracted_graph,
+        &contracted_graph,
+        &GraphFingerprint::of(graph.out_graph()),
+    );
 
     // Benchmark and test correctness
     let tests = generate_test_cases(graph.out_graph(), 1_000);
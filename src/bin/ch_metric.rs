@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use faster_paths::{
+    container::GraphFingerprint,
+    graphs::{
+        fmi::{try_read_fmi_file_in_parallel, FmiMetric, FmiOptions, TravelTimeOptions},
+        multi_metric_graph::MultiMetricGraph,
+    },
+    search::ch::contracted_graph::ContractedGraph,
+    utility::{benchmark_and_test_path, generate_test_cases, write_container_with_spinnner},
+};
+
+/// Reads the distance and the travel time of a .fmi file in one pass and
+/// builds a contracted graph for the metric picked by name.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    /// Metric to contract, either distance or travel_time
+    #[arg(short, long, default_value = "distance")]
+    metric: String,
+
+    /// Outfile for the contracted graph container
+    #[arg(short, long)]
+    contracted_graph: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let options = FmiOptions {
+        additional_metrics: vec![FmiMetric::TravelTime(TravelTimeOptions::default())],
+        with_attributes: false,
+        ..FmiOptions::default()
+    };
+    let fmi = try_read_fmi_file_in_parallel(&args.graph, &options)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let graph = MultiMetricGraph::new(
+        vec!["distance".to_string(), "travel_time".to_string()],
+        &fmi.edges,
+        &fmi.additional_weights,
+    );
+
+    let metric = graph.metric_index(&args.metric).unwrap_or_else(|| {
+        panic!(
+            "Unknown metric {}, expected one of {:?}",
+            args.metric,
+            graph.metric_names()
+        )
+    });
+    let reversible_metric = graph.reversible_metric(metric);
+
+    let contracted_graph =
+        ContractedGraph::with_dijkstra_witness_search_read_only(&reversible_metric, 16);
+
+    write_container_with_spinnner(
+        "contracted graph",
+        &args.contracted_graph,
+        &contracted_graph,
+        &GraphFingerprint::of(reversible_metric.out_graph()),
+    );
+
+    let tests = generate_test_cases(reversible_metric.out_graph(), 1_000);
+    let average_duration =
+        benchmark_and_test_path(reversible_metric.out_graph(), &tests, &contracted_graph).unwrap();
+    println!("Average duration was {:?}", average_duration);
+}
@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use faster_paths::{
+    container::GraphFingerprint,
+    graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph},
+    search::ch::{
+        contracted_graph::ContractedGraph,
+        mapped_contracted_graph::{write_mapped_contracted_graph_file, MappedContractedGraph},
+    },
+    utility::{benchmark_and_test_path, generate_test_cases, read_container_with_spinnner},
+};
+
+/// Converts a contracted graph into the layout that can be memory mapped.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    /// Contracted graph container
+    #[arg(short, long)]
+    contracted_graph: PathBuf,
+
+    /// Outfile for the memory mapped contracted graph
+    #[arg(short, long)]
+    mapped_contracted_graph: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    // Build graph
+    let graph = ReversibleGraph::<VecVecGraph>::from_fmi_file(&args.graph);
+    let fingerprint = GraphFingerprint::of(graph.out_graph());
+
+    let contracted_graph: ContractedGraph = read_container_with_spinnner(
+        "contracted graph",
+        &args.contracted_graph,
+        Some(&fingerprint),
+    );
+
+    write_mapped_contracted_graph_file(
+        &args.mapped_contracted_graph,
+        &contracted_graph,
+        &fingerprint,
+    )
+    .unwrap_or_else(|error| panic!("{}", error));
+    drop(contracted_graph);
+
+    // Benchmark and test correctness
+    let mapped_contracted_graph = MappedContractedGraph::open(&args.mapped_contracted_graph)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let tests = generate_test_cases(graph.out_graph(), 1_000);
+    let average_duration =
+        benchmark_and_test_path(graph.out_graph(), &tests, &mapped_contracted_graph).unwrap();
+    println!("All correct. Average duration was {:?}", average_duration);
+}
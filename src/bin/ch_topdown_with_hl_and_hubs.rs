@@ -1,23 +1,19 @@
-use std::{
-    path::PathBuf,
-    sync::atomic::AtomicI32,
-};
+use std::{path::PathBuf, sync::atomic::AtomicI32};
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{
         reversible_graph::ReversibleGraph, vec_hash_graph::VecHashGraph,
         vec_vec_graph::VecVecGraph, Distance, Graph, Vertex, WeightedEdge,
     },
     search::{
-        alt::landmark::Landmarks,
-        ch::contracted_graph::ContractedGraph,
-        hl::hub_graph::HubGraph,
+        alt::landmark::Landmarks, ch::contracted_graph::ContractedGraph, hl::hub_graph::HubGraph,
         DistanceHeuristic,
     },
     utility::{
-        benchmark_and_test_path, generate_test_cases, read_bincode_with_spinnner,
-        read_json_with_spinnner, write_bincode_with_spinnner,
+        benchmark_and_test_path, generate_test_cases, read_container_with_spinnner,
+        read_json_with_spinnner, write_container_with_spinnner,
     },
 };
 use indicatif::ParallelProgressIterator;
@@ -51,11 +47,15 @@ fn main() {
 
     // Build graph
     let graph: ReversibleGraph<VecVecGraph> =
-        read_bincode_with_spinnner("graph", &args.graph.as_path());
+        read_container_with_spinnner("graph", &args.graph.as_path(), None);
     let graph: ReversibleGraph<VecHashGraph> =
         ReversibleGraph::from_edges(&graph.out_graph().all_edges());
 
-    let hub_graph: HubGraph = read_bincode_with_spinnner("hub graph", &args.hub_graph.as_path());
+    let hub_graph: HubGraph = read_container_with_spinnner(
+        "hub graph",
+        &args.hub_graph.as_path(),
+        Some(&GraphFingerprint::of(graph.out_graph())),
+    );
 
     let level_to_vertex: Vec<Vertex> =
         read_json_with_spinnner("level to vertex", &args.level_to_vertex.as_path());
@@ -107,10 +107,11 @@ fn main() {
     println!("Average duration was {:?}", average_duration);
 
     // Write contracted_graph to file
-    write_bincode_with_spinnner(
-        "contracted_graph",
+    write_container_with_spinnner(
+        "contracted graph",
         &args.contracted_graph.as_path(),
         &contracted_graph,
+        &GraphFingerprint::of(graph.out_graph()),
     );
 }
 
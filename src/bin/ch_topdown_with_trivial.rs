@@ -2,14 +2,12 @@ use std::path::PathBuf;
 
 use clap::Parser;
 use faster_paths::{
-    graphs::{
-        reversible_graph::ReversibleGraph,
-        vec_vec_graph::VecVecGraph, Vertex,
-    },
+    container::GraphFingerprint,
+    graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, Vertex},
     search::{ch::contracted_graph::ContractedGraph, TrivialHeuristic},
     utility::{
-        benchmark_and_test_path, generate_test_cases, read_bincode_with_spinnner,
-        read_json_with_spinnner, write_bincode_with_spinnner,
+        benchmark_and_test_path, generate_test_cases, read_container_with_spinnner,
+        read_json_with_spinnner, write_container_with_spinnner,
     },
 };
 
@@ -36,7 +34,7 @@ fn main() {
 
     // Build graph
     let graph: ReversibleGraph<VecVecGraph> =
-        read_bincode_with_spinnner("graph", &args.graph.as_path());
+        read_container_with_spinnner("graph", &args.graph.as_path(), None);
     // let graph: ReversibleGraph<VecHashGraph> =
     //     ReversibleGraph::from_edges(&graph.out_graph().all_edges());
 
@@ -57,9 +55,10 @@ fn main() {
     println!("Average duration was {:?}", average_duration);
 
     // Write contracted_graph to file
-    write_bincode_with_spinnner(
-        "contracted_graph",
+    write_container_with_spinnner(
+        "contracted graph",
         &args.contracted_graph.as_path(),
         &contracted_graph,
+        &GraphFingerprint::of(graph.out_graph()),
     );
 }
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use faster_paths::{
+    container::GraphFingerprint,
+    graphs::{
+        edge_based_graph::edge_based_graph, reversible_graph::ReversibleGraph,
+        turn_restrictions::try_read_turn_restrictions_file, vec_vec_graph::VecVecGraph, Distance,
+        Graph,
+    },
+    search::{ch::contracted_graph::ContractedGraph, edge_based::EdgeBased, PathFinding},
+    utility::{gen_tests_cases, write_container_with_spinnner},
+};
+
+/// Builds a contracted graph that respects turn restrictions, by contracting
+/// the edge based graph. Writes the contracted graph and the mapping between
+/// the vertices of the input and the edge based graph.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    /// Infile with turn restrictions
+    #[arg(short, long)]
+    turn_restrictions: PathBuf,
+
+    /// Cost of U-turns, which are forbidden outside of dead ends if not given
+    #[arg(short, long)]
+    u_turn_cost: Option<Distance>,
+
+    /// Outfile for the contracted graph container
+    #[arg(short, long)]
+    contracted_graph: PathBuf,
+
+    /// Outfile for the edge based mapping container
+    #[arg(short, long)]
+    mapping: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let graph = ReversibleGraph::<VecVecGraph>::try_from_fmi_file_in_parallel(&args.graph)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let number_of_vertices = Graph::number_of_vertices(graph.out_graph()) as usize;
+    let mut restrictions =
+        try_read_turn_restrictions_file(&args.turn_restrictions, number_of_vertices)
+            .unwrap_or_else(|error| panic!("{}", error));
+    restrictions.u_turn_cost = args.u_turn_cost;
+
+    let (expanded_graph, mapping) = edge_based_graph(&graph, &restrictions);
+    println!(
+        "The edge based graph has {} vertices and {} edges",
+        Graph::number_of_vertices(expanded_graph.out_graph()),
+        expanded_graph.out_graph().number_of_edges()
+    );
+
+    let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&expanded_graph, 16);
+
+    let fingerprint = GraphFingerprint::of(expanded_graph.out_graph());
+    write_container_with_spinnner(
+        "contracted graph",
+        &args.contracted_graph,
+        &contracted_graph,
+        &fingerprint,
+    );
+    write_container_with_spinnner("edge based mapping", &args.mapping, &mapping, &fingerprint);
+
+    // Check the contracted graph against Dijkstra on the edge based graph.
+    let dijkstra = EdgeBased::new(expanded_graph.out_graph().clone(), mapping.clone());
+    let contracted_graph = EdgeBased::new(contracted_graph, mapping);
+    for (source, target) in gen_tests_cases(graph.out_graph(), 1_000) {
+        assert_eq!(
+            contracted_graph.shortest_path_distance(source, target),
+            dijkstra.shortest_path_distance(source, target),
+            "wrong distance from {} to {}",
+            source,
+            target
+        );
+    }
+}
@@ -1,10 +1,11 @@
-use std::{fs::File, io::BufWriter, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph},
     search::ch::contracted_graph::ContractedGraph,
-    utility::{benchmark_and_test_path, generate_test_cases},
+    utility::{benchmark_and_test_path, generate_test_cases, write_container_with_spinnner},
 };
 
 /// Starts a routing service on localhost:3030/route
@@ -30,8 +31,12 @@ fn main() {
     let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&graph, 16);
 
     // Write contracted_graph to file
-    let writer = BufWriter::new(File::create(&args.contracted_graph).unwrap());
-    bincode::serialize_into(writer, &contracted_graph).unwrap();
+    write_container_with_spinnner(
+        "contracted graph",
+        &args.contracted_graph,
+        &contracted_graph,
+        &GraphFingerprint::of(graph.out_graph()),
+    );
 
     // Benchmark and test correctness
     let tests = generate_test_cases(graph.out_graph(), 1_000);
@@ -1,9 +1,9 @@
-use std::{fs::File, io::BufReader, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
 use faster_paths::{
     graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph},
-    utility::{benchmark_path, gen_tests_cases},
+    utility::{benchmark_path, gen_tests_cases, read_container_with_spinnner},
 };
 
 /// Reading a .bincode file is way faster than a .fmi file
@@ -18,8 +18,8 @@ struct Args {
 fn main() {
     let args = Args::parse();
 
-    let writer = BufReader::new(File::open(&args.graph_bincode).unwrap());
-    let graph: ReversibleGraph<VecVecGraph> = bincode::deserialize_from(writer).unwrap();
+    let graph: ReversibleGraph<VecVecGraph> =
+        read_container_with_spinnner("graph", &args.graph_bincode, None);
 
     let m = 500;
     println!("Value over {} sequential searches", m);
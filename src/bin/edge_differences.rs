@@ -1,13 +1,11 @@
-use std::{
-    fs::File,
-    io::{BufReader, BufWriter},
-    path::PathBuf,
-};
+use std::{fs::File, io::BufWriter, path::PathBuf};
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, Graph, WeightedEdge},
     search::{hl::hub_graph::HubGraph, PathFinding},
+    utility::read_container_with_spinnner,
 };
 use itertools::Itertools;
 use rand::prelude::*;
@@ -34,10 +32,11 @@ fn main() {
     let graph: ReversibleGraph<VecVecGraph> =
         ReversibleGraph::<VecVecGraph>::from_fmi_file(&args.graph);
 
-    let hub_graph: HubGraph = {
-        let reader = BufReader::new(File::open(&args.hub_graph).unwrap());
-        bincode::deserialize_from(reader).unwrap()
-    };
+    let hub_graph: HubGraph = read_container_with_spinnner(
+        "hub graph",
+        &args.hub_graph,
+        Some(&GraphFingerprint::of(graph.out_graph())),
+    );
 
     let mut edge_differences = vec![false; graph.number_of_vertices() as usize];
 
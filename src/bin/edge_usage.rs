@@ -7,10 +7,10 @@ use std::{
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, Graph},
     reading_pathfinder,
     utility::{get_paths, get_progressbar},
-    FileType,
 };
 use indicatif::ProgressIterator;
 use itertools::Itertools;
@@ -27,10 +27,6 @@ struct Args {
     #[arg(short, long)]
     file: PathBuf,
 
-    /// Type of the input file
-    #[arg(short = 't', long, value_enum, default_value = "fmi")]
-    file_type: FileType,
-
     /// Number of seartes.
     #[arg(short, long, default_value = "100000")]
     number_of_searches: u32,
@@ -47,7 +43,11 @@ fn main() {
     // Build graph
     let graph = ReversibleGraph::<VecVecGraph>::from_fmi_file(&args.graph);
 
-    let pathfinder = reading_pathfinder(&args.file.as_path(), &args.file_type);
+    let pathfinder = reading_pathfinder(
+        &args.file.as_path(),
+        Some(&GraphFingerprint::of(graph.out_graph())),
+    )
+    .unwrap_or_else(|error| panic!("{}", error));
 
     // Get paths and level_to_vertex
     let paths = get_paths(
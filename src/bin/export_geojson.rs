@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use faster_paths::{
+    container::GraphFingerprint,
+    geojson::{
+        feature_collection, hub_label_feature, path_feature, search_space_feature,
+        write_geojson_file,
+    },
+    graphs::{vec_vec_graph::VecVecGraph, vertex_attributes::AttributedGraph, Vertex},
+    search::{
+        ch::{contracted_graph::ContractedGraph, pathfinding::one_to_one_search_space},
+        dijkstra::{dijkstra_one_to_one_path_wrapped, dijkstra_one_to_one_search_space},
+        hl::hub_graph::HubGraph,
+    },
+    utility::read_container_with_spinnner,
+};
+
+/// Exports the shortest path between two vertices and the search spaces that
+/// found it as GeoJSON.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format, the vertices need coordinates
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    #[arg(short, long)]
+    source: Vertex,
+
+    #[arg(short, long)]
+    target: Vertex,
+
+    /// Contracted graph container, adds the CH search spaces
+    #[arg(short, long)]
+    contracted_graph: Option<PathBuf>,
+
+    /// Hub graph container, adds the forward label of the source and the
+    /// backward label of the target
+    #[arg(short = 'u', long)]
+    hub_graph: Option<PathBuf>,
+
+    /// Outfile in .geojson format
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let AttributedGraph { graph, attributes } =
+        AttributedGraph::<VecVecGraph>::try_from_fmi_file(&args.graph)
+            .unwrap_or_else(|error| panic!("{}", error));
+    let fingerprint = GraphFingerprint::of(graph.out_graph());
+
+    let mut features = Vec::new();
+
+    if let Some(path) =
+        dijkstra_one_to_one_path_wrapped(graph.out_graph(), args.source, args.target)
+    {
+        match path_feature(&path, &attributes) {
+            Some(feature) => features.push(feature),
+            None => eprintln!("the path has vertices without coordinates, skipping it"),
+        }
+    }
+    let search_space =
+        dijkstra_one_to_one_search_space(graph.out_graph(), args.source, args.target);
+    features.push(search_space_feature(&search_space, &attributes, "dijkstra"));
+
+    if let Some(path) = &args.contracted_graph {
+        let contracted_graph: ContractedGraph =
+            read_container_with_spinnner("contracted graph", path, Some(&fingerprint));
+        let (forward, backward) = one_to_one_search_space(
+            contracted_graph.upward_graph(),
+            contracted_graph.downward_graph(),
+            args.source,
+            args.target,
+        );
+        features.push(search_space_feature(&forward, &attributes, "ch forward"));
+        features.push(search_space_feature(&backward, &attributes, "ch backward"));
+    }
+
+    if let Some(path) = &args.hub_graph {
+        let hub_graph: HubGraph =
+            read_container_with_spinnner("hub graph", path, Some(&fingerprint));
+        features.push(hub_label_feature(
+            args.source,
+            hub_graph.forward.get_label(args.source),
+            &attributes,
+            "forward",
+        ));
+        features.push(hub_label_feature(
+            args.target,
+            hub_graph.backward.get_label(args.target),
+            &attributes,
+            "backward",
+        ));
+    }
+
+    write_geojson_file(&args.output, &feature_collection(features))
+        .unwrap_or_else(|error| panic!("{}", error));
+}
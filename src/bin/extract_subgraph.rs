@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use faster_paths::{
+    container::GraphFingerprint,
+    graphs::{
+        binary_graph::write_binary_graph_file,
+        permutation::Renumber,
+        subgraph::{within_bounding_box, within_distance, within_hops, BoundingBox},
+        vec_vec_graph::VecVecGraph,
+        vertex_attributes::AttributedGraph,
+        Distance, Graph, Vertex,
+    },
+    utility::write_container_with_spinnner,
+};
+
+/// Cuts a subgraph out of a graph, either around a seed vertex or inside of a
+/// bounding box. Writes the subgraph in the binary graph format, with
+/// coordinates, and the permutation that maps its ids back to the ids of the
+/// input.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    /// Keep the vertices around this vertex, requires --hops or --distance
+    #[arg(short, long)]
+    seed: Option<Vertex>,
+
+    /// Keep the vertices at most this many edges away from the seed
+    #[arg(long, requires = "seed", conflicts_with = "distance")]
+    hops: Option<u32>,
+
+    /// Keep the vertices at most this far away from or to the seed
+    #[arg(long, requires = "seed")]
+    distance: Option<Distance>,
+
+    /// Keep the vertices inside of
+    /// min_latitude,min_longitude,max_latitude,max_longitude
+    #[arg(
+        long,
+        value_delimiter = ',',
+        allow_hyphen_values = true,
+        conflicts_with = "seed"
+    )]
+    bounding_box: Option<Vec<f64>>,
+
+    /// Outfile for the subgraph in the binary graph format
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Outfile for the permutation container
+    #[arg(short, long)]
+    permutation: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let AttributedGraph { graph, attributes } =
+        AttributedGraph::<VecVecGraph>::try_from_fmi_file(&args.graph)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+    let (subgraph, permutation) = match (args.seed, args.hops, args.distance, &args.bounding_box) {
+        (Some(seed), Some(hops), None, None) => within_hops(&graph, seed, hops),
+        (Some(seed), None, Some(distance), None) => within_distance(&graph, seed, distance),
+        (None, None, None, Some(bounding_box)) if bounding_box.len() == 4 => {
+            let bounding_box = BoundingBox {
+                min_latitude: bounding_box[0],
+                min_longitude: bounding_box[1],
+                max_latitude: bounding_box[2],
+                max_longitude: bounding_box[3],
+            };
+            within_bounding_box(&graph, &attributes, &bounding_box)
+        }
+        _ => panic!(
+            "Either --seed with --hops or --distance, or --bounding-box with four values is needed"
+        ),
+    };
+
+    let number_of_vertices = subgraph.out_graph().number_of_vertices();
+    println!(
+        "The subgraph has {} vertices and {} edges",
+        number_of_vertices,
+        subgraph.out_graph().number_of_edges()
+    );
+
+    let attributes = attributes
+        .renumbered(&permutation)
+        .restricted_to(number_of_vertices);
+    write_binary_graph_file(&args.output, subgraph.out_graph(), Some(&attributes))
+        .unwrap_or_else(|error| panic!("{}", error));
+
+    let fingerprint = GraphFingerprint::of(subgraph.out_graph());
+    write_container_with_spinnner("permutation", &args.permutation, &permutation, &fingerprint);
+}
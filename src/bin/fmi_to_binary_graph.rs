@@ -0,0 +1,50 @@
+use std::{path::PathBuf, time::Instant};
+
+use clap::Parser;
+use faster_paths::graphs::{
+    binary_graph::write_binary_graph_file,
+    fmi::{try_read_fmi_file_in_parallel, FmiOptions},
+    number_of_vertices_of,
+    vec_vec_graph::VecVecGraph,
+    GraphFromEdges,
+};
+
+/// Converts a .fmi file into the compact binary graph format, see
+/// `faster_paths::graphs::binary_graph`.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short = 'f', long)]
+    graph_fmi: PathBuf,
+    /// Outfile in the binary graph format
+    #[arg(short = 'b', long)]
+    graph_binary: PathBuf,
+    /// Do not store the coordinates of the vertices
+    #[arg(long)]
+    without_coordinates: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let start = Instant::now();
+    let options = FmiOptions {
+        with_attributes: !args.without_coordinates,
+        ..FmiOptions::default()
+    };
+    let fmi = try_read_fmi_file_in_parallel(&args.graph_fmi, &options)
+        .unwrap_or_else(|error| panic!("{}", error));
+    // Sized like the graphs of the other fmi loaders, so its fingerprint matches
+    // theirs.
+    let number_of_vertices = number_of_vertices_of(&fmi.edges);
+    let graph = VecVecGraph::with_edges(number_of_vertices, &fmi.edges);
+    println!("Reading fmi graph took {:?}", start.elapsed());
+
+    let start = Instant::now();
+    let attributes =
+        (!args.without_coordinates).then(|| fmi.attributes.restricted_to(number_of_vertices));
+    write_binary_graph_file(&args.graph_binary, &graph, attributes.as_ref())
+        .unwrap_or_else(|error| panic!("{}", error));
+    println!("Writing binary graph took {:?}", start.elapsed());
+}
@@ -1,8 +1,10 @@
-use std::{fs::File, io::BufWriter, path::PathBuf, time::Instant};
+use std::{path::PathBuf, time::Instant};
 
 use clap::Parser;
-use faster_paths::graphs::{
-    read_edges_from_fmi_file, reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, Graph,
+use faster_paths::{
+    container::GraphFingerprint,
+    graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, Graph},
+    utility::write_container_with_spinnner,
 };
 
 /// Reading a .bincode file is way faster than a .fmi file
@@ -21,14 +23,18 @@ fn main() {
     let args = Args::parse();
 
     let start = Instant::now();
-    let edges = read_edges_from_fmi_file(&args.graph_fmi);
-    let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+    let graph = ReversibleGraph::<VecVecGraph>::try_from_fmi_file_in_parallel(&args.graph_fmi)
+        .unwrap_or_else(|error| panic!("{}", error));
     println!("Reading fmi graph took {:?}", start.elapsed());
 
     println!("{}", graph.out_graph().is_bidirectional());
 
     let start = Instant::now();
-    let writer = BufWriter::new(File::create(&args.graph_bincode).unwrap());
-    bincode::serialize_into(writer, &graph).unwrap();
+    write_container_with_spinnner(
+        "graph",
+        &args.graph_bincode,
+        &graph,
+        &GraphFingerprint::of(graph.out_graph()),
+    );
     println!("Writing bincode took {:?}", start.elapsed());
 }
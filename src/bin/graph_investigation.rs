@@ -1,6 +1,4 @@
 use std::{
-    fs::File,
-    io::BufWriter,
     path::PathBuf,
     time::{Duration, Instant},
 };
@@ -19,6 +17,7 @@ use faster_paths::{
         },
         dijkstra::{dijkstra_one_to_all_wraped, dijkstra_one_to_one},
     },
+    stats::{GraphStats, StatsOptions, StatsReport},
     utility::{benchmark_distances, benchmark_path, gen_tests_cases, get_progressbar},
 };
 use indicatif::ParallelProgressIterator;
@@ -33,9 +32,10 @@ struct Args {
     #[arg(short, long)]
     graph: PathBuf,
 
-    /// Infile in .fmi format
+    /// Outfile for the json report of the graph statistics, see
+    /// `faster_paths::stats`
     #[arg(short, long)]
-    degrees_out: Option<PathBuf>,
+    report: Option<PathBuf>,
 }
 
 fn main() {
@@ -44,55 +44,24 @@ fn main() {
     let edges = read_edges_from_fmi_file(&args.graph);
     let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
 
+    let graph_stats = GraphStats::of(graph.out_graph(), &StatsOptions::default());
     println!(
-        "Is graph bidirectional? {}",
-        graph.out_graph().is_bidirectional()
+        "{} vertices, {} non trivial, average degree {:.2}, {:.1}% bidirectional edges",
+        graph_stats.number_of_vertices,
+        graph_stats.number_of_non_trivial_vertices,
+        graph_stats.average_degree,
+        graph_stats.bidirectional_ratio * 100.0
     );
-
-    // let mut bi_edge = 0;
-    // let mut nonbi_edge = 0;
-    // let mut writer = BufWriter::new(File::create("missing_edges.txt").unwrap());
-    // graph.out_graph().all_edges().into_iter().for_each(|edge| {
-    //     let other = graph.get_weight(&edge.remove_weight().reversed());
-    //     if other != None {
-    //         bi_edge += 1;
-    //     } else {
-    //         nonbi_edge += 1;
-    //     }
-
-    //     if other != Some(edge.weight) {
-    //         writeln!(writer, "{} {} {} 0 0", edge.head, edge.tail,
-    // edge.weight).unwrap();     }
-    // });
-    // writer.flush().unwrap();
-    // println!("{} {}", bi_edge, nonbi_edge);
-
-    if let Some(degrees_out) = args.degrees_out.as_ref() {
-        let out_degrees = graph
-            .out_graph()
-            .vertices()
-            .map(|vertex| graph.out_graph().edges(vertex).len())
-            .collect_vec();
-        let writer = BufWriter::new(File::create(&degrees_out).unwrap());
-        serde_json::to_writer(writer, &out_degrees).unwrap();
+    if let Some(report) = args.report.as_ref() {
+        StatsReport {
+            graph: graph_stats,
+            contracted_graph: None,
+        }
+        .write_json_file(report)
+        .unwrap_or_else(|error| panic!("{}", error));
     }
 
     let vertices = graph.out_graph().vertices().collect_vec();
-    println!(
-        "non trivial vertices: {}",
-        graph.out_graph().non_trivial_vertices().len()
-    );
-    println!("trivial: {}", graph.out_graph().number_of_vertices());
-    println!("average degree is {}", graph.out_graph().average_degree());
-    println!(
-        "sum of squared degree {}",
-        graph
-            .out_graph()
-            .vertices()
-            .map(|vertex| graph.out_graph().edges(vertex).len().pow(2) as u128)
-            .sum::<u128>()
-    );
-
     let n = 10_000;
     let (avg_path_len, avg_dijkstra_rank, avg_queue_pops) = get_dijkstra_info(&graph, n);
 
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use faster_paths::{
+    container::GraphFingerprint,
+    graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph},
+    search::ch::contracted_graph::ContractedGraph,
+    stats::{ContractedGraphStats, GraphStats, StatsOptions, StatsReport},
+    utility::read_container_with_spinnner,
+};
+
+/// Computes degree distributions, weight histograms, the bidirectional edge
+/// ratio and a diameter estimate of a graph and, if given, statistics of its
+/// contracted graph. Writes them as a json report.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    /// Contracted graph container
+    #[arg(short, long)]
+    contracted_graph: Option<PathBuf>,
+
+    /// Number of Dijkstras for the diameter estimate
+    #[arg(short, long, default_value_t = 16)]
+    diameter_samples: u32,
+
+    /// Outfile for the json report
+    #[arg(short, long)]
+    report: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let graph = ReversibleGraph::<VecVecGraph>::try_from_fmi_file_in_parallel(&args.graph)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let options = StatsOptions {
+        diameter_samples: args.diameter_samples,
+        ..StatsOptions::default()
+    };
+    let graph_stats = GraphStats::of(graph.out_graph(), &options);
+    println!(
+        "{} vertices, {} edges, average degree {:.2}, {:.1}% bidirectional edges",
+        graph_stats.number_of_vertices,
+        graph_stats.number_of_edges,
+        graph_stats.average_degree,
+        graph_stats.bidirectional_ratio * 100.0
+    );
+    if let Some(diameter) = &graph_stats.diameter {
+        println!(
+            "diameter is at least {} ({} hops)",
+            diameter.distance, diameter.hops
+        );
+    }
+
+    let contracted_graph_stats = args.contracted_graph.as_ref().map(|path| {
+        let contracted_graph: ContractedGraph = read_container_with_spinnner(
+            "contracted graph",
+            path,
+            Some(&GraphFingerprint::of(graph.out_graph())),
+        );
+        let stats = ContractedGraphStats::of(&contracted_graph);
+        println!(
+            "{} shortcuts, average upward degree {:.2}",
+            stats.number_of_shortcuts, stats.average_upward_degree
+        );
+        stats
+    });
+
+    StatsReport {
+        graph: graph_stats,
+        contracted_graph: contracted_graph_stats,
+    }
+    .write_json_file(&args.report)
+    .unwrap_or_else(|error| panic!("{}", error));
+}
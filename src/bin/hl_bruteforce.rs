@@ -1,17 +1,14 @@
-use std::{
-    fs::File,
-    io::{BufReader, BufWriter},
-    path::PathBuf,
-};
+use std::{fs::File, io::BufReader, path::PathBuf};
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{
         read_edges_from_fmi_file, reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph,
         Graph, Vertex,
     },
     search::hl::hub_graph::HubGraph,
-    utility::{benchmark_and_test_path, generate_test_cases},
+    utility::{benchmark_and_test_path, generate_test_cases, write_container_with_spinnner},
 };
 
 #[derive(Parser, Debug)]
@@ -47,8 +44,12 @@ fn main() {
             / graph.out_graph().non_trivial_vertices().len() as f64
     );
 
-    let writer = BufWriter::new(File::create(&args.hub_graph).unwrap());
-    bincode::serialize_into(writer, &hub_graph).unwrap();
+    write_container_with_spinnner(
+        "hub graph",
+        &args.hub_graph,
+        &hub_graph,
+        &GraphFingerprint::of(graph.out_graph()),
+    );
 
     // Benchmark and test correctness
     let tests = generate_test_cases(graph.out_graph(), 1_000);
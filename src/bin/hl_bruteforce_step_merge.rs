@@ -1,12 +1,13 @@
 use std::{
     collections::HashMap,
     fs::{self, File},
-    io::{BufReader, BufWriter},
+    io::BufReader,
     path::PathBuf,
 };
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{
         read_edges_from_fmi_file, reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph,
         Graph, Vertex,
@@ -19,7 +20,10 @@ use faster_paths::{
         },
         PathFinding,
     },
-    utility::{benchmark_and_test_path, generate_test_cases, read_bincode_with_spinnner},
+    utility::{
+        benchmark_and_test_path, generate_test_cases, read_bincode_with_spinnner,
+        write_container_with_spinnner,
+    },
 };
 use rayon::iter::ParallelIterator;
 
@@ -87,8 +91,12 @@ fn main() {
         vertex_to_level,
     };
 
-    let writer = BufWriter::new(File::create(&args.hub_graph).unwrap());
-    bincode::serialize_into(writer, &hub_graph).unwrap();
+    write_container_with_spinnner(
+        "hub graph",
+        &args.hub_graph,
+        &hub_graph,
+        &GraphFingerprint::of(graph.out_graph()),
+    );
 
     // Benchmark and test correctness
     let tests = generate_test_cases(graph.out_graph(), 1_000);
@@ -7,7 +7,7 @@ use clap::Parser;
 use faster_paths::{
     graphs::Vertex,
     search::{hl::hub_graph::HubGraph, PathFinding},
-    utility::read_bincode_with_spinnner,
+    utility::read_container_with_spinnner,
 };
 use indicatif::ProgressIterator;
 
@@ -26,8 +26,8 @@ struct Args {
 fn main() {
     let args = Args::parse();
 
-    let h1: HubGraph = read_bincode_with_spinnner("h1", args.h1.as_path());
-    let h2: HubGraph = read_bincode_with_spinnner("h2", args.h2.as_path());
+    let h1: HubGraph = read_container_with_spinnner("h1", args.h1.as_path(), None);
+    let h2: HubGraph = read_container_with_spinnner("h2", args.h2.as_path(), None);
 
     assert_eq!(h1.number_of_vertices(), h2.number_of_vertices());
 
@@ -1,17 +1,17 @@
-use std::{
-    fs::File,
-    io::{BufReader, BufWriter},
-    path::PathBuf,
-};
+use std::path::PathBuf;
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{
         read_edges_from_fmi_file, reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph,
         Graph,
     },
     search::hl::hub_graph::HubGraph,
-    utility::{benchmark_and_test_path, generate_test_cases},
+    utility::{
+        benchmark_and_test_path, generate_test_cases, read_container_with_spinnner,
+        write_container_with_spinnner,
+    },
 };
 
 #[derive(Parser, Debug)]
@@ -36,8 +36,11 @@ fn main() {
     let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
 
     // Read contracted_graph
-    let reader = BufReader::new(File::open(&args.contracted_graph).unwrap());
-    let contracted_graph = bincode::deserialize_from(reader).unwrap();
+    let contracted_graph = read_container_with_spinnner(
+        "contracted graph",
+        &args.contracted_graph,
+        Some(&GraphFingerprint::of(graph.out_graph())),
+    );
 
     // Create hub_graph
     let hub_graph = HubGraph::by_merging(&contracted_graph);
@@ -48,8 +51,12 @@ fn main() {
     );
 
     // Write hub_graph to file
-    let writer = BufWriter::new(File::create(&args.hub_graph).unwrap());
-    bincode::serialize_into(writer, &hub_graph).unwrap();
+    write_container_with_spinnner(
+        "hub graph",
+        &args.hub_graph,
+        &hub_graph,
+        &GraphFingerprint::of(graph.out_graph()),
+    );
 
     // Benchmark and test correctness
     let tests = generate_test_cases(graph.out_graph(), 1_000);
@@ -6,7 +6,7 @@ use faster_paths::{
     search::{ch::contracted_graph::vertex_to_level, hl::hub_graph::HubGraph, PathFinding},
     utility::{
         average_ch_vertex_degree, average_hl_label_size, get_paths, level_to_vertex,
-        level_to_vertex_with_ord, read_bincode_with_spinnner,
+        level_to_vertex_with_ord, read_container_with_spinnner,
     },
 };
 use rand::prelude::*;
@@ -42,7 +42,8 @@ fn main() {
     // Build graph
     let graph = ReversibleGraph::<VecVecGraph>::from_fmi_file(&args.graph);
 
-    let hub_graph: HubGraph = read_bincode_with_spinnner("hub graph", &args.hub_graph.as_path());
+    let hub_graph: HubGraph =
+        read_container_with_spinnner("hub graph", &args.hub_graph.as_path(), None);
 
     // Get paths and level_to_vertex
     let paths = get_paths(
@@ -57,7 +58,7 @@ fn main() {
 
     if let Some(simple_hub_graph) = args.simple_hub_graph {
         let simple_hub_graph: HubGraph =
-            read_bincode_with_spinnner("hub graph", &simple_hub_graph.as_path());
+            read_container_with_spinnner("hub graph", &simple_hub_graph.as_path(), None);
         let level_to_vertex: Vec<Vertex> =
             level_to_vertex(&paths, simple_hub_graph.number_of_vertices());
         orderings.push(("simple ordering", level_to_vertex.clone()));
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use faster_paths::{
+    container::GraphFingerprint,
+    graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph},
+    search::hl::{
+        hub_graph::HubGraph,
+        mapped_hub_graph::{write_mapped_hub_graph_file, MappedHubGraph},
+    },
+    utility::{benchmark_and_test_path, generate_test_cases, read_container_with_spinnner},
+};
+
+/// Converts a hub graph into the layout that can be memory mapped.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    /// Hub graph container
+    #[arg(short = 'u', long)]
+    hub_graph: PathBuf,
+
+    /// Outfile for the memory mapped hub graph
+    #[arg(short, long)]
+    mapped_hub_graph: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    // Build graph
+    let graph = ReversibleGraph::<VecVecGraph>::from_fmi_file(&args.graph);
+    let fingerprint = GraphFingerprint::of(graph.out_graph());
+
+    let hub_graph: HubGraph =
+        read_container_with_spinnner("hub graph", &args.hub_graph, Some(&fingerprint));
+
+    write_mapped_hub_graph_file(&args.mapped_hub_graph, &hub_graph, &fingerprint)
+        .unwrap_or_else(|error| panic!("{}", error));
+    drop(hub_graph);
+
+    // Benchmark and test correctness
+    let mapped_hub_graph =
+        MappedHubGraph::open(&args.mapped_hub_graph).unwrap_or_else(|error| panic!("{}", error));
+    let tests = generate_test_cases(graph.out_graph(), 1_000);
+    let average_duration =
+        benchmark_and_test_path(graph.out_graph(), &tests, &mapped_hub_graph).unwrap();
+    println!("All correct. Average duration was {:?}", average_duration);
+}
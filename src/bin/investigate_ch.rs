@@ -4,7 +4,7 @@ use clap::Parser;
 use faster_paths::{
     graphs::Vertex,
     search::{ch::contracted_graph::ContractedGraph, PathFinding},
-    utility::{benchmark_distances, benchmark_path, read_bincode_with_spinnner},
+    utility::{benchmark_distances, benchmark_path, read_container_with_spinnner},
 };
 use itertools::Itertools;
 use rand::prelude::*;
@@ -26,7 +26,7 @@ fn main() {
 
     // Read contracted_graph
     let contracted_graph: ContractedGraph =
-        read_bincode_with_spinnner("contrated graph", &args.contracted_graph.as_path());
+        read_container_with_spinnner("contrated graph", &args.contracted_graph.as_path(), None);
 
     let vertices = (0..contracted_graph.number_of_vertices()).collect_vec();
     let mut rng = thread_rng();
@@ -13,7 +13,7 @@ use faster_paths::{
         alt::landmark::Landmarks, collections::dijkstra_data::Path, hl::hub_graph::HubGraph,
         DistanceHeuristic, PathFinding,
     },
-    utility::{get_paths, level_to_vertex, read_bincode_with_spinnner, read_json_with_spinnner},
+    utility::{get_paths, level_to_vertex, read_container_with_spinnner, read_json_with_spinnner},
 };
 use indicatif::ParallelProgressIterator;
 use itertools::Itertools;
@@ -50,14 +50,15 @@ fn main() {
 
     // Build graph
     let graph: ReversibleGraph<VecVecGraph> =
-        read_bincode_with_spinnner("graph", &args.graph.as_path());
+        read_container_with_spinnner("graph", &args.graph.as_path(), None);
 
     // Build graph
-    let graph_hl: HubGraph = read_bincode_with_spinnner("graph hl", &&args.hl_graph.as_path());
+    let graph_hl: HubGraph =
+        read_container_with_spinnner("graph hl", &&args.hl_graph.as_path(), None);
 
     // Build graph
     let simple_graph_hl: HubGraph =
-        read_bincode_with_spinnner("simple graph hl", &args.simple_graph_hl.as_path());
+        read_container_with_spinnner("simple graph hl", &args.simple_graph_hl.as_path(), None);
 
     // check_if_upper_bound(&graph, &simple_graph);
 
@@ -4,7 +4,7 @@ use clap::Parser;
 use faster_paths::{
     graphs::Vertex,
     search::{hl::hub_graph::HubGraph, PathFinding},
-    utility::{benchmark_distances, benchmark_path, read_bincode_with_spinnner},
+    utility::{benchmark_distances, benchmark_path, read_container_with_spinnner},
 };
 use itertools::Itertools;
 use rand::prelude::*;
@@ -25,7 +25,8 @@ fn main() {
     let args = Args::parse();
 
     // Read contracted_graph
-    let hub_graph: HubGraph = read_bincode_with_spinnner("hubgraph", &args.hub_graph.as_path());
+    let hub_graph: HubGraph =
+        read_container_with_spinnner("hubgraph", &args.hub_graph.as_path(), None);
 
     println!(
         "average label size {}",
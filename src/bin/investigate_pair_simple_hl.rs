@@ -12,7 +12,7 @@ use faster_paths::{
     },
     utility::{
         benchmark_and_test_distance, generate_test_cases, get_progressbar,
-        read_bincode_with_spinnner,
+        read_container_with_spinnner,
     },
 };
 use itertools::Itertools;
@@ -39,12 +39,12 @@ fn main() {
     let args = Args::parse();
 
     let graph_org: ReversibleGraph<VecVecGraph> =
-        read_bincode_with_spinnner("graph", &args.graph.as_path());
+        read_container_with_spinnner("graph", &args.graph.as_path(), None);
     let mut graph: ReversibleGraph<VecHashGraph> =
         ReversibleGraph::from_edges(&graph_org.out_graph().all_edges());
 
     let simple_graph_hl: HubGraph =
-        read_bincode_with_spinnner("simple hub graph", &args.simple_graph_hl);
+        read_container_with_spinnner("simple hub graph", &args.simple_graph_hl, None);
 
     let mut edges = Vec::new();
 
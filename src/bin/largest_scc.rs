@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use faster_paths::{
+    container::GraphFingerprint,
+    graphs::{
+        reversible_graph::ReversibleGraph,
+        strongly_connected_components::StronglyConnectedComponents, vec_vec_graph::VecVecGraph,
+        Graph,
+    },
+    utility::write_container_with_spinnner,
+};
+
+/// Cuts a graph down to its largest strongly connected component. Writes the
+/// component as a graph and the permutation that maps its ids back to the ids
+/// of the input.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    /// Outfile for the graph container of the largest component
+    #[arg(short, long)]
+    component_graph: PathBuf,
+
+    /// Outfile for the permutation container
+    #[arg(short, long)]
+    permutation: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let graph = ReversibleGraph::<VecVecGraph>::try_from_fmi_file_in_parallel(&args.graph)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let components = StronglyConnectedComponents::new(&graph);
+    let (component_graph, permutation) = components.extract_largest_component(&graph);
+
+    println!(
+        "{} components, the largest has {} of {} vertices",
+        components.number_of_components(),
+        component_graph.out_graph().number_of_vertices(),
+        components.number_of_vertices()
+    );
+
+    let fingerprint = GraphFingerprint::of(component_graph.out_graph());
+    write_container_with_spinnner(
+        "component graph",
+        &args.component_graph,
+        &component_graph,
+        &fingerprint,
+    );
+    write_container_with_spinnner("permutation", &args.permutation, &permutation, &fingerprint);
+}
@@ -2,13 +2,13 @@ use std::{fs::File, io::BufWriter, path::PathBuf};
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, Graph, Vertex},
     reading_pathfinder,
     search::ch::contracted_graph::vertex_to_level,
     utility::{
         average_ch_vertex_degree, average_hl_label_size, get_paths, hit_percentage, level_to_vertex,
     },
-    FileType,
 };
 use libc::{mlockall, MCL_CURRENT, MCL_FUTURE};
 
@@ -23,10 +23,6 @@ struct Args {
     #[arg(short, long)]
     file: PathBuf,
 
-    /// Type of the input file
-    #[arg(short = 't', long, value_enum, default_value = "fmi")]
-    file_type: FileType,
-
     /// Number of seartes.
     #[arg(short, long, default_value = "100000")]
     number_of_searches: u32,
@@ -56,7 +52,11 @@ fn main() {
     // Build graph
     let graph = ReversibleGraph::<VecVecGraph>::from_fmi_file(&args.graph);
 
-    let pathfinder = reading_pathfinder(&args.file.as_path(), &args.file_type);
+    let pathfinder = reading_pathfinder(
+        &args.file.as_path(),
+        Some(&GraphFingerprint::of(graph.out_graph())),
+    )
+    .unwrap_or_else(|error| panic!("{}", error));
 
     // Get paths and level_to_vertex
     let paths = get_paths(
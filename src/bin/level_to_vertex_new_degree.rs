@@ -8,6 +8,7 @@ use std::{
 
 use clap::Parser;
 use faster_paths::{
+    container::GraphFingerprint,
     graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, Graph, Vertex},
     reading_pathfinder,
     search::{ch::contracted_graph::vertex_to_level, PathFinding},
@@ -15,7 +16,6 @@ use faster_paths::{
         average_hl_label_size, average_hl_label_size_vertices, get_progressbar,
         write_json_with_spinnner,
     },
-    FileType,
 };
 use itertools::Itertools;
 use rand::prelude::*;
@@ -32,10 +32,6 @@ struct Args {
     #[arg(short, long)]
     file: PathBuf,
 
-    /// Type of the input file
-    #[arg(short = 't', long, value_enum, default_value = "fmi")]
-    file_type: FileType,
-
     /// Number of seartes.
     #[arg(short, long, default_value = "100000")]
     number_of_searches: u32,
@@ -60,7 +56,11 @@ fn main() {
     // Build graph
     let graph = ReversibleGraph::<VecVecGraph>::from_fmi_file(&args.graph);
 
-    let pathfinder = reading_pathfinder(&args.file.as_path(), &args.file_type);
+    let pathfinder = reading_pathfinder(
+        &args.file.as_path(),
+        Some(&GraphFingerprint::of(graph.out_graph())),
+    )
+    .unwrap_or_else(|error| panic!("{}", error));
 
     let number_of_vertices = graph.number_of_vertices();
 
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use faster_paths::{
+    container::GraphFingerprint,
+    graphs::{
+        permutation::{Permutation, Renumber},
+        reversible_graph::ReversibleGraph,
+        vec_vec_graph::VecVecGraph,
+        Graph,
+    },
+    utility::write_container_with_spinnner,
+};
+
+/// Numbers the vertices with edges continuously from zero. Writes the
+/// renumbered graph and the permutation that maps its ids back to the ids of
+/// the input.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    /// Outfile for the renumbered graph container
+    #[arg(short, long)]
+    renumbered_graph: PathBuf,
+
+    /// Outfile for the permutation container
+    #[arg(short, long)]
+    permutation: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let graph = ReversibleGraph::<VecVecGraph>::try_from_fmi_file_in_parallel(&args.graph)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let (permutation, number_of_vertices_with_edges) = Permutation::compacting(&graph);
+    let renumbered_graph = graph
+        .renumbered(&permutation)
+        .restricted_to(number_of_vertices_with_edges);
+
+    println!(
+        "{} of {} vertices have edges",
+        renumbered_graph.out_graph().number_of_vertices(),
+        permutation.number_of_vertices()
+    );
+
+    let fingerprint = GraphFingerprint::of(renumbered_graph.out_graph());
+    write_container_with_spinnner(
+        "renumbered graph",
+        &args.renumbered_graph,
+        &renumbered_graph,
+        &fingerprint,
+    );
+    write_container_with_spinnner("permutation", &args.permutation, &permutation, &fingerprint);
+}
@@ -1,4 +1,4 @@
-use std::{fs::File, io::BufReader, path::PathBuf, sync::atomic::AtomicU32};
+use std::{path::PathBuf, sync::atomic::AtomicU32};
 
 use clap::Parser;
 use faster_paths::{
@@ -10,6 +10,7 @@ use faster_paths::{
         ch::contracted_graph::ContractedGraph, dijkstra::dijkstra_one_to_one_path_wrapped,
         PathFinding,
     },
+    utility::read_container_with_spinnner,
 };
 use indicatif::ParallelProgressIterator;
 use rand::prelude::*;
@@ -34,8 +35,8 @@ fn main() {
     let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
 
     // Read contracted_graph
-    let reader = BufReader::new(File::open(&args.ch_graph).unwrap());
-    let contracted_graph: ContractedGraph = bincode::deserialize_from(reader).unwrap();
+    let contracted_graph: ContractedGraph =
+        read_container_with_spinnner("contracted graph", &args.ch_graph, None);
 
     let total_failed = AtomicU32::new(0);
     let all = AtomicU32::new(0);
@@ -0,0 +1,47 @@
+use std::{fs::File, io::BufWriter, path::PathBuf, process::ExitCode};
+
+use clap::Parser;
+use faster_paths::graphs::{
+    fmi::{try_read_fmi_file_in_parallel, FmiOptions},
+    validation::ValidationReport,
+};
+
+/// Checks a graph against the graph requirements and reports isolated
+/// vertices, self edges, parallel edges, zero weight edges, unreachable
+/// vertices, asymmetric edges and possible distance overflows. Exits with an
+/// error if a requirement is violated.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Infile in .fmi format
+    #[arg(short, long)]
+    graph: PathBuf,
+
+    /// Outfile for the full report as json
+    #[arg(short, long)]
+    report: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let options = FmiOptions {
+        with_attributes: false,
+        ..FmiOptions::default()
+    };
+    let fmi = try_read_fmi_file_in_parallel(&args.graph, &options)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let report = ValidationReport::of_fmi_file(&fmi);
+    println!("{}", report);
+
+    if let Some(path) = &args.report {
+        let writer = BufWriter::new(File::create(path).unwrap());
+        serde_json::to_writer_pretty(writer, &report).unwrap();
+    }
+
+    if report.is_valid() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
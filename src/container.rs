@@ -0,0 +1,403 @@
+//! A self-describing file format for the preprocessed data structures.
+//!
+//! A container starts with a fixed size header, followed by the bincode
+//! encoded payload. All integers are little endian.
+//!
+//! | bytes | content                                      |
+//! |-------|----------------------------------------------|
+//! | 8     | magic bytes `FASTPATH`                       |
+//! | 4     | format version                               |
+//! | 4     | payload kind, see [`PayloadKind`]            |
+//! | 8     | number of vertices of the source graph       |
+//! | 8     | number of edges of the source graph          |
+//! | 8     | hash of the edges of the source graph        |
+//! | 8     | payload length in bytes                      |
+//! | 8     | xxh3 checksum of the payload                 |
+//!
+//! Everything is checked before the payload is deserialized, so loading the
+//! wrong file gives an error instead of a panic deep inside serde or wrong
+//! answers.
+
+use std::{
+    fmt,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use xxhash_rust::xxh3::{xxh3_64, Xxh3};
+
+use crate::{
+    error::{ContainerErrorKind, Error, Result},
+    graphs::{
+        edge_based_graph::EdgeBasedMapping, permutation::Permutation,
+        reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, Graph,
+    },
+    search::{ch::contracted_graph::ContractedGraph, hl::hub_graph::HubGraph},
+};
+
+pub const MAGIC: [u8; 8] = *b"FASTPATH";
+
+/// Bump this whenever the header or the serialization of a payload changes.
+pub const VERSION: u32 = 1;
+
+const HEADER_LENGTH: usize = 56;
+
+/// What a container holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadKind {
+    ContractedGraph = 1,
+    HubGraph = 2,
+    /// A `ReversibleGraph<VecVecGraph>`.
+    ReversibleGraph = 3,
+    Permutation = 4,
+    EdgeBasedMapping = 5,
+}
+
+impl PayloadKind {
+    fn from_u32(kind: u32) -> Option<Self> {
+        match kind {
+            1 => Some(PayloadKind::ContractedGraph),
+            2 => Some(PayloadKind::HubGraph),
+            3 => Some(PayloadKind::ReversibleGraph),
+            4 => Some(PayloadKind::Permutation),
+            5 => Some(PayloadKind::EdgeBasedMapping),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PayloadKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PayloadKind::ContractedGraph => "contracted graph",
+            PayloadKind::HubGraph => "hub graph",
+            PayloadKind::ReversibleGraph => "graph",
+            PayloadKind::Permutation => "permutation",
+            PayloadKind::EdgeBasedMapping => "edge based mapping",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Identifies the graph a data structure was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphFingerprint {
+    pub number_of_vertices: u64,
+    pub number_of_edges: u64,
+    /// Hash over all edges, including their weights.
+    pub hash: u64,
+}
+
+impl GraphFingerprint {
+    pub fn of(graph: &dyn Graph) -> Self {
+        let mut hasher = Xxh3::new();
+        let mut number_of_edges = 0;
+
+        for vertex in graph.vertices() {
+            // Sorted, so that the fingerprint does not depend on the graph type.
+            let mut edges: Vec<_> = graph.edges(vertex).collect();
+            edges.sort_by_key(|edge| (edge.head, edge.weight));
+
+            for edge in edges {
+                hasher.update(&edge.tail.to_le_bytes());
+                hasher.update(&edge.head.to_le_bytes());
+                hasher.update(&edge.weight.to_le_bytes());
+                number_of_edges += 1;
+            }
+        }
+
+        GraphFingerprint {
+            number_of_vertices: graph.number_of_vertices() as u64,
+            number_of_edges,
+            hash: hasher.digest(),
+        }
+    }
+}
+
+impl fmt::Display for GraphFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} vertices, {} edges, hash {:016x}",
+            self.number_of_vertices, self.number_of_edges, self.hash
+        )
+    }
+}
+
+/// A data structure that can be stored in a container.
+pub trait Payload: Serialize + DeserializeOwned {
+    const KIND: PayloadKind;
+}
+
+impl Payload for ContractedGraph {
+    const KIND: PayloadKind = PayloadKind::ContractedGraph;
+}
+
+impl Payload for HubGraph {
+    const KIND: PayloadKind = PayloadKind::HubGraph;
+}
+
+/// Only implemented for one graph type, because the kind does not tell the
+/// graph types apart and their serializations are incompatible.
+impl Payload for ReversibleGraph<VecVecGraph> {
+    const KIND: PayloadKind = PayloadKind::ReversibleGraph;
+}
+
+/// The fingerprint of a permutation container is the one of the renumbered
+/// graph.
+impl Payload for Permutation {
+    const KIND: PayloadKind = PayloadKind::Permutation;
+}
+
+/// The fingerprint of an edge based mapping container is the one of the edge
+/// based graph.
+impl Payload for EdgeBasedMapping {
+    const KIND: PayloadKind = PayloadKind::EdgeBasedMapping;
+}
+
+/// The checked header of a container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub version: u32,
+    pub kind: PayloadKind,
+    pub fingerprint: GraphFingerprint,
+    pub payload_length: u64,
+    pub checksum: u64,
+}
+
+/// Returns true if `bytes`, e.g. the start of a file, begin with [`MAGIC`].
+pub fn has_magic(bytes: &[u8]) -> bool {
+    bytes.starts_with(&MAGIC)
+}
+
+/// Writes `payload` as container into the file at `path`. `fingerprint`
+/// identifies the graph the payload was built from.
+pub fn write_container_file<T: Payload>(
+    path: &Path,
+    payload: &T,
+    fingerprint: &GraphFingerprint,
+) -> Result<()> {
+    let file = File::create(path).map_err(|error| Error::io(path, error))?;
+    write_container(BufWriter::new(file), path, payload, fingerprint)
+}
+
+/// Writes `payload` as container into `writer`. `path` is only used to give
+/// errors some context.
+pub fn write_container<T: Payload, W: Write>(
+    mut writer: W,
+    path: &Path,
+    payload: &T,
+    fingerprint: &GraphFingerprint,
+) -> Result<()> {
+    let payload = bincode::serialize(payload).map_err(|error| Error::bincode(path, error))?;
+
+    let mut header = Vec::with_capacity(HEADER_LENGTH);
+    header.extend_from_slice(&MAGIC);
+    header.extend_from_slice(&VERSION.to_le_bytes());
+    header.extend_from_slice(&(T::KIND as u32).to_le_bytes());
+    header.extend_from_slice(&fingerprint.number_of_vertices.to_le_bytes());
+    header.extend_from_slice(&fingerprint.number_of_edges.to_le_bytes());
+    header.extend_from_slice(&fingerprint.hash.to_le_bytes());
+    header.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    header.extend_from_slice(&xxh3_64(&payload).to_le_bytes());
+
+    writer
+        .write_all(&header)
+        .and_then(|_| writer.write_all(&payload))
+        .and_then(|_| writer.flush())
+        .map_err(|error| Error::io(path, error))
+}
+
+/// Reads and checks the header of a container. Only the magic bytes and the
+/// version are checked, the remaining fields are returned as they are.
+pub fn read_header<R: Read>(reader: &mut R, path: &Path) -> Result<Header> {
+    let mut bytes = Vec::with_capacity(HEADER_LENGTH);
+    reader
+        .take(HEADER_LENGTH as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|error| Error::io(path, error))?;
+
+    if !has_magic(&bytes) {
+        return Err(Error::container(path, ContainerErrorKind::MissingMagic));
+    }
+    // A container that ends within its header is not a legacy file.
+    if bytes.len() < HEADER_LENGTH {
+        return Err(Error::container(path, ContainerErrorKind::Truncated));
+    }
+
+    let u32_at = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+    let u64_at = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+
+    let version = u32_at(8);
+    if version != VERSION {
+        let kind = ContainerErrorKind::UnsupportedVersion {
+            found: version,
+            supported: VERSION,
+        };
+        return Err(Error::container(path, kind));
+    }
+
+    let kind = PayloadKind::from_u32(u32_at(12)).ok_or_else(|| {
+        Error::container(path, ContainerErrorKind::UnknownPayloadKind(u32_at(12)))
+    })?;
+
+    Ok(Header {
+        version,
+        kind,
+        fingerprint: GraphFingerprint {
+            number_of_vertices: u64_at(16),
+            number_of_edges: u64_at(24),
+            hash: u64_at(32),
+        },
+        payload_length: u64_at(40),
+        checksum: u64_at(48),
+    })
+}
+
+/// Reads a container from the file at `path`, see [`read_container`].
+pub fn read_container_file<T: Payload>(
+    path: &Path,
+    expected_fingerprint: Option<&GraphFingerprint>,
+) -> Result<T> {
+    let file = File::open(path).map_err(|error| Error::io(path, error))?;
+    read_container(BufReader::new(file), path, expected_fingerprint)
+}
+
+/// Reads a container holding a `T` from `reader`. If `expected_fingerprint`
+/// is given, the payload must have been built from that graph. `path` is only
+/// used to give errors some context.
+pub fn read_container<T: Payload, R: Read>(
+    mut reader: R,
+    path: &Path,
+    expected_fingerprint: Option<&GraphFingerprint>,
+) -> Result<T> {
+    let header = read_header(&mut reader, path)?;
+
+    if header.kind != T::KIND {
+        let kind = ContainerErrorKind::WrongPayloadKind {
+            expected: T::KIND,
+            found: header.kind,
+        };
+        return Err(Error::container(path, kind));
+    }
+
+    if let Some(expected) = expected_fingerprint {
+        if *expected != header.fingerprint {
+            let kind = ContainerErrorKind::FingerprintMismatch {
+                expected: *expected,
+                found: header.fingerprint,
+            };
+            return Err(Error::container(path, kind));
+        }
+    }
+
+    let mut payload = Vec::new();
+    (&mut reader)
+        .take(header.payload_length)
+        .read_to_end(&mut payload)
+        .map_err(|error| Error::io(path, error))?;
+    if payload.len() as u64 != header.payload_length {
+        return Err(Error::container(path, ContainerErrorKind::Truncated));
+    }
+
+    if xxh3_64(&payload) != header.checksum {
+        return Err(Error::container(path, ContainerErrorKind::ChecksumMismatch));
+    }
+
+    bincode::deserialize(&payload).map_err(|error| Error::bincode(path, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{read_container, write_container, GraphFingerprint};
+    use crate::{
+        error::{ContainerErrorKind, Error},
+        graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, WeightedEdge},
+        search::hl::hub_graph::HubGraph,
+    };
+
+    fn graph(weight: u64) -> ReversibleGraph<VecVecGraph> {
+        ReversibleGraph::from_edges(&vec![
+            WeightedEdge::new(0, 1, weight),
+            WeightedEdge::new(1, 2, 2),
+        ])
+    }
+
+    fn container_error<T>(result: crate::error::Result<T>) -> ContainerErrorKind {
+        match result {
+            Err(Error::Container { kind, .. }) => kind,
+            Err(error) => panic!("expected container error, got {}", error),
+            Ok(_) => panic!("expected container error"),
+        }
+    }
+
+    #[test]
+    fn round_trip_checks_kind_and_fingerprint() {
+        let graph = graph(1);
+        let fingerprint = GraphFingerprint::of(graph.out_graph());
+        let path = Path::new("test.bincode");
+
+        let mut buffer = Vec::new();
+        write_container(&mut buffer, path, &graph, &fingerprint).unwrap();
+
+        let read: ReversibleGraph<VecVecGraph> =
+            read_container(buffer.as_slice(), path, Some(&fingerprint)).unwrap();
+        assert_eq!(GraphFingerprint::of(read.out_graph()), fingerprint);
+
+        let other_fingerprint = GraphFingerprint::of(self::graph(2).out_graph());
+        assert_ne!(other_fingerprint, fingerprint);
+        assert!(matches!(
+            container_error(read_container::<ReversibleGraph<VecVecGraph>, _>(
+                buffer.as_slice(),
+                path,
+                Some(&other_fingerprint)
+            )),
+            ContainerErrorKind::FingerprintMismatch { .. }
+        ));
+
+        assert!(matches!(
+            container_error(read_container::<HubGraph, _>(buffer.as_slice(), path, None)),
+            ContainerErrorKind::WrongPayloadKind { .. }
+        ));
+    }
+
+    #[test]
+    fn detects_corruption() {
+        let graph = graph(1);
+        let fingerprint = GraphFingerprint::of(graph.out_graph());
+        let path = Path::new("test.bincode");
+
+        let mut buffer = Vec::new();
+        write_container(&mut buffer, path, &graph, &fingerprint).unwrap();
+        let read = |buffer: &[u8]| {
+            container_error(read_container::<ReversibleGraph<VecVecGraph>, _>(
+                buffer, path, None,
+            ))
+        };
+
+        let mut corrupted = buffer.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert_eq!(read(&corrupted), ContainerErrorKind::ChecksumMismatch);
+
+        assert_eq!(
+            read(&buffer[..buffer.len() - 1]),
+            ContainerErrorKind::Truncated
+        );
+        assert_eq!(read(&buffer[..20]), ContainerErrorKind::Truncated);
+        assert_eq!(read(&buffer[..4]), ContainerErrorKind::MissingMagic);
+
+        let mut newer = buffer.clone();
+        newer[8] += 1;
+        assert!(matches!(
+            read(&newer),
+            ContainerErrorKind::UnsupportedVersion { .. }
+        ));
+
+        let legacy = bincode::serialize(&graph).unwrap();
+        assert_eq!(read(&legacy), ContainerErrorKind::MissingMagic);
+    }
+}
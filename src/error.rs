@@ -0,0 +1,234 @@
+use std::{fmt, path::PathBuf};
+
+use thiserror::Error;
+
+use crate::{
+    container::{GraphFingerprint, PayloadKind},
+    graphs::Vertex,
+};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors that can occur while reading or writing files of this crate.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("unable to access {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    #[error("{}: {kind}", path.display())]
+    Container {
+        path: PathBuf,
+        kind: ContainerErrorKind,
+    },
+
+    #[error("unable to (de)serialize {}: {source}", path.display())]
+    Bincode {
+        path: PathBuf,
+        #[source]
+        source: bincode::Error,
+    },
+
+    #[error("unable to detect the file type of {}", path.display())]
+    UnknownFileType { path: PathBuf },
+
+    #[error("invalid {option}: {reason}")]
+    InvalidOption {
+        option: &'static str,
+        reason: String,
+    },
+}
+
+impl Error {
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Error::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    pub fn container(path: impl Into<PathBuf>, kind: ContainerErrorKind) -> Self {
+        Error::Container {
+            path: path.into(),
+            kind,
+        }
+    }
+
+    pub fn bincode(path: impl Into<PathBuf>, source: bincode::Error) -> Self {
+        Error::Bincode {
+            path: path.into(),
+            source,
+        }
+    }
+
+    pub fn invalid_option(option: &'static str, reason: impl Into<String>) -> Self {
+        Error::InvalidOption {
+            option,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// A problem in a text file, located by its 1-based line and column.
+#[derive(Debug, Error)]
+#[error("{}:{line}:{column}: {kind}", path.display())]
+pub struct ParseError {
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    /// A line ended before the named field.
+    MissingField(&'static str),
+    /// The named field is not a valid integer.
+    InvalidInteger { field: &'static str, value: String },
+    /// The named field is not a valid number.
+    InvalidNumber { field: &'static str, value: String },
+    /// The named weight is negative, not finite or does not fit below
+    /// `Distance::MAX`, which searches use as infinity.
+    InvalidWeight { field: &'static str, value: String },
+    /// An edge references a vertex that is not declared in the header.
+    VertexOutOfRange {
+        vertex: Vertex,
+        number_of_vertices: usize,
+    },
+    /// A record of a delimited file is malformed, e.g. not valid UTF-8.
+    InvalidRecord(String),
+    /// A DIMACS vertex id is 0, but DIMACS ids start at 1.
+    VertexIdZero,
+    /// The file ended before the number of edges declared in its header.
+    EdgeCountMismatch { expected: usize, found: usize },
+    /// The file ended before the number of vertices declared in its header.
+    VertexCountMismatch { expected: usize, found: usize },
+    /// A line starts with a type the format does not know.
+    UnknownLineType(String),
+    /// A header field does not have the only value the reader supports, e.g.
+    /// a DIMACS problem type other than `sp`.
+    UnexpectedValue {
+        field: &'static str,
+        expected: &'static str,
+        value: String,
+    },
+    /// A file has more than one header line.
+    DuplicateHeader,
+    /// The header comes after lines it should describe.
+    HeaderAfterData,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::MissingField(field) => write!(f, "missing field {}", field),
+            ParseErrorKind::InvalidInteger { field, value } => {
+                write!(f, "{} '{}' is not a valid integer", field, value)
+            }
+            ParseErrorKind::InvalidNumber { field, value } => {
+                write!(f, "{} '{}' is not a valid number", field, value)
+            }
+            ParseErrorKind::InvalidWeight { field, value } => write!(
+                f,
+                "{} '{}' is negative, not finite or too large",
+                field, value
+            ),
+            ParseErrorKind::VertexOutOfRange {
+                vertex,
+                number_of_vertices,
+            } => write!(
+                f,
+                "vertex {} is out of range, the graph has {} vertices",
+                vertex, number_of_vertices
+            ),
+            ParseErrorKind::InvalidRecord(reason) => write!(f, "invalid record: {}", reason),
+            ParseErrorKind::VertexIdZero => write!(f, "vertex ids start at 1"),
+            ParseErrorKind::EdgeCountMismatch { expected, found } => {
+                write!(f, "expected {} edges but found {}", expected, found)
+            }
+            ParseErrorKind::VertexCountMismatch { expected, found } => {
+                write!(f, "expected {} vertices but found {}", expected, found)
+            }
+            ParseErrorKind::UnknownLineType(line_type) => {
+                write!(f, "unknown line type '{}'", line_type)
+            }
+            ParseErrorKind::UnexpectedValue {
+                field,
+                expected,
+                value,
+            } => write!(f, "expected {} '{}' but found '{}'", field, expected, value),
+            ParseErrorKind::DuplicateHeader => write!(f, "there is already a header"),
+            ParseErrorKind::HeaderAfterData => {
+                write!(f, "the header must come before all other lines")
+            }
+        }
+    }
+}
+
+/// Reasons why a container file, see [`crate::container`], is rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContainerErrorKind {
+    /// The file does not start with the container magic bytes.
+    MissingMagic,
+    /// The file was written by a newer or older, incompatible version.
+    UnsupportedVersion {
+        found: u32,
+        supported: u32,
+    },
+    /// The header names a payload kind this version does not know.
+    UnknownPayloadKind(u32),
+    /// The file holds something else than requested, e.g. a hub graph
+    /// instead of a contracted graph.
+    WrongPayloadKind {
+        expected: PayloadKind,
+        found: PayloadKind,
+    },
+    /// The file was built from a different graph.
+    FingerprintMismatch {
+        expected: GraphFingerprint,
+        found: GraphFingerprint,
+    },
+    /// The file ended within its header or before the payload length given
+    /// in its header.
+    Truncated,
+    /// The payload does not match its checksum, the file is corrupted.
+    ChecksumMismatch,
+    /// The payload passed its checksum but is inconsistent, e.g. an edge
+    /// points to a vertex that does not exist.
+    Malformed(&'static str),
+}
+
+impl fmt::Display for ContainerErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerErrorKind::MissingMagic => write!(f, "not a faster_paths container"),
+            ContainerErrorKind::UnsupportedVersion { found, supported } => write!(
+                f,
+                "container version {} is not supported, expected version {}",
+                found, supported
+            ),
+            ContainerErrorKind::UnknownPayloadKind(kind) => {
+                write!(f, "unknown payload kind {}", kind)
+            }
+            ContainerErrorKind::WrongPayloadKind { expected, found } => {
+                write!(f, "expected a {} but found a {}", expected, found)
+            }
+            ContainerErrorKind::FingerprintMismatch { expected, found } => write!(
+                f,
+                "built from a different graph, expected {} but found {}",
+                expected, found
+            ),
+            ContainerErrorKind::Truncated => write!(f, "the container is truncated"),
+            ContainerErrorKind::ChecksumMismatch => {
+                write!(f, "the payload checksum does not match")
+            }
+            ContainerErrorKind::Malformed(reason) => write!(f, "malformed payload: {}", reason),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! GeoJSON export of paths, search spaces and hub labels, e.g. to look at a
+//! bad route in QGIS or on geojson.io.
+//!
+//! Coordinates are written as `[longitude, latitude]`, as GeoJSON requires.
+//! Search spaces and labels skip vertices without coordinates, paths are not
+//! exported at all if one of their vertices has none. Distances are attached as
+//! properties, for multi points as an array in the order of the points.
+
+use std::{fs::File, io::BufWriter, path::Path};
+
+use serde_json::{json, Value};
+
+use crate::{
+    error::{Error, Result},
+    graphs::{vertex_attributes::VertexAttributes, Distance, Vertex},
+    search::{collections::dijkstra_data::Path as ShortestPath, hl::hub_graph::LabelEntry},
+};
+
+/// A LineString along the vertices of `path`, or a Point if the path has only
+/// one vertex. Returns `None` if the path is empty or a vertex has no
+/// coordinate, since skipping it would draw a different path.
+pub fn path_feature(path: &ShortestPath, attributes: &VertexAttributes) -> Option<Value> {
+    let coordinates = path
+        .vertices
+        .iter()
+        .map(|&vertex| coordinate(attributes, vertex))
+        .collect::<Option<Vec<_>>>()?;
+
+    let geometry = match coordinates.as_slice() {
+        [] => return None,
+        [coordinate] => json!({ "type": "Point", "coordinates": coordinate }),
+        _ => json!({ "type": "LineString", "coordinates": coordinates }),
+    };
+
+    Some(feature(
+        geometry,
+        json!({
+            "source": path.vertices.first(),
+            "target": path.vertices.last(),
+            "distance": path.distance,
+            "number_of_vertices": path.vertices.len(),
+        }),
+    ))
+}
+
+/// A MultiPoint of the settled vertices of a search, e.g. from
+/// [`crate::search::dijkstra::dijkstra_one_to_one_search_space`] or
+/// [`crate::search::ch::pathfinding::one_to_one_search_space`]. `name` tells
+/// searches apart, e.g. `"forward"` and `"backward"`.
+pub fn search_space_feature(
+    search_space: &[(Vertex, Distance)],
+    attributes: &VertexAttributes,
+    name: &str,
+) -> Value {
+    multi_point_feature(
+        search_space.iter().copied(),
+        attributes,
+        json!({
+            "name": name,
+            "max_distance": search_space.iter().map(|&(_, distance)| distance).max(),
+        }),
+    )
+}
+
+/// A MultiPoint of the hubs of the label of `vertex`, e.g. from
+/// [`crate::search::hl::half_hub_graph::HalfHubGraph::get_label`].
+/// `direction` tells forward and backward labels apart.
+pub fn hub_label_feature<E: LabelEntry>(
+    vertex: Vertex,
+    label: &[E],
+    attributes: &VertexAttributes,
+    direction: &str,
+) -> Value {
+    multi_point_feature(
+        label.iter().map(|entry| (entry.vertex(), entry.distance())),
+        attributes,
+        json!({
+            "vertex": vertex,
+            "direction": direction,
+            "label_size": label.len(),
+        }),
+    )
+}
+
+pub fn feature_collection(features: Vec<Value>) -> Value {
+    json!({ "type": "FeatureCollection", "features": features })
+}
+
+/// Writes a feature or a feature collection into the file at `path`.
+pub fn write_geojson_file(path: &Path, geojson: &Value) -> Result<()> {
+    let file = File::create(path).map_err(|error| Error::io(path, error))?;
+    serde_json::to_writer(BufWriter::new(file), geojson)
+        .map_err(|error| Error::io(path, error.into()))
+}
+
+/// A MultiPoint of `vertices` with their ids and distances as properties in
+/// addition to `properties`.
+fn multi_point_feature(
+    vertices: impl Iterator<Item = (Vertex, Distance)>,
+    attributes: &VertexAttributes,
+    mut properties: Value,
+) -> Value {
+    let mut coordinates = Vec::new();
+    let mut ids = Vec::new();
+    let mut distances = Vec::new();
+    for (vertex, distance) in vertices {
+        if let Some(coordinate) = coordinate(attributes, vertex) {
+            coordinates.push(coordinate);
+            ids.push(vertex);
+            distances.push(distance);
+        }
+    }
+
+    properties["number_of_vertices"] = json!(ids.len());
+    properties["vertices"] = json!(ids);
+    properties["distances"] = json!(distances);
+    feature(
+        json!({ "type": "MultiPoint", "coordinates": coordinates }),
+        properties,
+    )
+}
+
+fn feature(geometry: Value, properties: Value) -> Value {
+    json!({ "type": "Feature", "geometry": geometry, "properties": properties })
+}
+
+fn coordinate(attributes: &VertexAttributes, vertex: Vertex) -> Option<[f64; 2]> {
+    attributes
+        .coordinate(vertex)
+        .map(|(latitude, longitude)| [longitude, latitude])
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{path_feature, search_space_feature};
+    use crate::{
+        graphs::vertex_attributes::{VertexAttribute, VertexAttributes},
+        search::collections::dijkstra_data::Path,
+    };
+
+    #[test]
+    fn writes_longitude_first_and_skips_unknown_vertices() {
+        let attributes = VertexAttributes::new(vec![
+            VertexAttribute::new(48.0, 9.0),
+            VertexAttribute::new(49.0, 10.0),
+        ]);
+
+        let path = Path {
+            vertices: vec![0, 1],
+            distance: 5,
+        };
+        let feature = path_feature(&path, &attributes).unwrap();
+        assert_eq!(
+            feature["geometry"],
+            json!({ "type": "LineString", "coordinates": [[9.0, 48.0], [10.0, 49.0]] })
+        );
+        assert_eq!(feature["properties"]["distance"], json!(5));
+
+        let single_vertex = Path {
+            vertices: vec![1],
+            distance: 0,
+        };
+        assert_eq!(
+            path_feature(&single_vertex, &attributes).unwrap()["geometry"],
+            json!({ "type": "Point", "coordinates": [10.0, 49.0] })
+        );
+        let unknown_vertex = Path {
+            vertices: vec![0, 7, 1],
+            distance: 5,
+        };
+        assert!(path_feature(&unknown_vertex, &attributes).is_none());
+
+        let feature = search_space_feature(&[(1, 0), (7, 2), (0, 3)], &attributes, "forward");
+        assert_eq!(
+            feature["geometry"]["coordinates"],
+            json!([[10.0, 49.0], [9.0, 48.0]])
+        );
+        assert_eq!(feature["properties"]["vertices"], json!([1, 0]));
+        assert_eq!(feature["properties"]["distances"], json!([0, 3]));
+        assert_eq!(feature["properties"]["max_distance"], json!(3));
+    }
+}
@@ -0,0 +1,339 @@
+//! A compact binary format for input graphs.
+//!
+//! Only the out edges are stored, in compressed sparse row layout. The in
+//! edges are derived while loading, so the format does not depend on a graph
+//! type. All integers and floats are little endian.
+//!
+//! | bytes     | content                                         |
+//! |-----------|-------------------------------------------------|
+//! | 8         | magic bytes `FPGRAPH\0`                         |
+//! | 4         | format version                                  |
+//! | 4         | flags, bit 0 is set if coordinates follow       |
+//! | 8         | number of vertices `n`                          |
+//! | 8         | number of edges `m`                             |
+//! | 8         | xxh3 checksum of everything after the header    |
+//! | 8 (n + 1) | u64 offsets, the edges of `v` are at `offsets[v]..offsets[v + 1]` |
+//! | 4 m       | u32 heads, sorted within the edges of a vertex  |
+//! | 8 m       | u64 weights                                     |
+//! | 16 n      | f64 latitude and longitude per vertex, optional |
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use xxhash_rust::xxh3::xxh3_64;
+
+use super::{
+    reversible_graph::ReversibleGraph,
+    vertex_attributes::{AttributedGraph, VertexAttribute, VertexAttributes},
+    Distance, Graph, GraphFromEdges, Vertex, WeightedEdge,
+};
+use crate::error::{ContainerErrorKind, Error, Result};
+
+pub const MAGIC: [u8; 8] = *b"FPGRAPH\0";
+
+/// Bump this whenever the layout changes.
+pub const VERSION: u32 = 1;
+
+const HEADER_LENGTH: usize = 40;
+
+const HAS_COORDINATES: u32 = 1;
+
+/// Writes `graph` into the file at `path`. If `attributes` are given, the
+/// latitude and longitude of every vertex are stored as well.
+pub fn write_binary_graph_file(
+    path: &Path,
+    graph: &dyn Graph,
+    attributes: Option<&VertexAttributes>,
+) -> Result<()> {
+    let file = File::create(path).map_err(|error| Error::io(path, error))?;
+    write_binary_graph(BufWriter::new(file), path, graph, attributes)
+}
+
+/// Writes `graph` into `writer`, see [`write_binary_graph_file`]. `path` is
+/// only used to give errors some context.
+pub fn write_binary_graph<W: Write>(
+    mut writer: W,
+    path: &Path,
+    graph: &dyn Graph,
+    attributes: Option<&VertexAttributes>,
+) -> Result<()> {
+    let number_of_vertices = graph.number_of_vertices();
+    if let Some(attributes) = attributes {
+        if attributes.number_of_vertices() != number_of_vertices {
+            let reason = "the number of coordinates differs from the number of vertices";
+            return Err(Error::container(
+                path,
+                ContainerErrorKind::Malformed(reason),
+            ));
+        }
+    }
+
+    let mut offsets = Vec::with_capacity(number_of_vertices as usize + 1);
+    let mut heads = Vec::new();
+    let mut weights = Vec::new();
+    offsets.push(0u64);
+    for vertex in graph.vertices() {
+        let mut edges: Vec<_> = graph.edges(vertex).collect();
+        edges.sort_unstable_by_key(|edge| edge.head);
+        for edge in edges {
+            heads.push(edge.head);
+            weights.push(edge.weight);
+        }
+        offsets.push(heads.len() as u64);
+    }
+
+    let mut payload =
+        Vec::with_capacity(8 * offsets.len() + 12 * heads.len() + 16 * number_of_vertices as usize);
+    offsets
+        .iter()
+        .for_each(|offset| payload.extend_from_slice(&offset.to_le_bytes()));
+    heads
+        .iter()
+        .for_each(|head| payload.extend_from_slice(&head.to_le_bytes()));
+    weights
+        .iter()
+        .for_each(|weight| payload.extend_from_slice(&weight.to_le_bytes()));
+    if let Some(attributes) = attributes {
+        for attribute in attributes.iter() {
+            payload.extend_from_slice(&attribute.latitude.to_le_bytes());
+            payload.extend_from_slice(&attribute.longitude.to_le_bytes());
+        }
+    }
+
+    let flags = if attributes.is_some() {
+        HAS_COORDINATES
+    } else {
+        0
+    };
+
+    let mut header = Vec::with_capacity(HEADER_LENGTH);
+    header.extend_from_slice(&MAGIC);
+    header.extend_from_slice(&VERSION.to_le_bytes());
+    header.extend_from_slice(&flags.to_le_bytes());
+    header.extend_from_slice(&(number_of_vertices as u64).to_le_bytes());
+    header.extend_from_slice(&(heads.len() as u64).to_le_bytes());
+    header.extend_from_slice(&xxh3_64(&payload).to_le_bytes());
+
+    writer
+        .write_all(&header)
+        .and_then(|_| writer.write_all(&payload))
+        .and_then(|_| writer.flush())
+        .map_err(|error| Error::io(path, error))
+}
+
+/// Reads a graph from the file at `path`, see [`read_binary_graph`].
+pub fn read_binary_graph_file<G: GraphFromEdges>(path: &Path) -> Result<AttributedGraph<G>> {
+    let bytes = std::fs::read(path).map_err(|error| Error::io(path, error))?;
+    read_binary_graph(&bytes, path)
+}
+
+/// Reads a graph from `bytes` into any graph type that can be built from its
+/// edges, see [`GraphFromEdges`], and derives its in edges. The attributes are
+/// empty if the file has no coordinates. `path` is only used to give errors
+/// some context.
+pub fn read_binary_graph<G: GraphFromEdges>(
+    bytes: &[u8],
+    path: &Path,
+) -> Result<AttributedGraph<G>> {
+    let error = |kind: ContainerErrorKind| Error::container(path, kind);
+
+    if !bytes.starts_with(&MAGIC) {
+        return Err(error(ContainerErrorKind::MissingMagic));
+    }
+    let header = bytes
+        .get(..HEADER_LENGTH)
+        .ok_or_else(|| error(ContainerErrorKind::Truncated))?;
+    let u32_at = |offset: usize| u32::from_le_bytes(header[offset..offset + 4].try_into().unwrap());
+    let u64_at = |offset: usize| u64::from_le_bytes(header[offset..offset + 8].try_into().unwrap());
+
+    let version = u32_at(8);
+    if version != VERSION {
+        return Err(error(ContainerErrorKind::UnsupportedVersion {
+            found: version,
+            supported: VERSION,
+        }));
+    }
+    let has_coordinates = u32_at(12) & HAS_COORDINATES != 0;
+    let number_of_vertices = u64_at(16);
+    let number_of_edges = u64_at(24);
+    let checksum = u64_at(32);
+
+    if number_of_vertices > Vertex::MAX as u64 {
+        return Err(error(ContainerErrorKind::Malformed(
+            "too many vertices for a u32 vertex id",
+        )));
+    }
+    let payload_length = (number_of_vertices + 1)
+        .checked_mul(8)
+        .zip(number_of_edges.checked_mul(12))
+        .and_then(|(offsets, edges)| offsets.checked_add(edges))
+        .and_then(|length| {
+            length.checked_add(if has_coordinates {
+                16 * number_of_vertices
+            } else {
+                0
+            })
+        })
+        .ok_or_else(|| error(ContainerErrorKind::Truncated))?;
+
+    let payload = &bytes[HEADER_LENGTH..];
+    if (payload.len() as u64) < payload_length {
+        return Err(error(ContainerErrorKind::Truncated));
+    }
+    let payload = &payload[..payload_length as usize];
+    if xxh3_64(payload) != checksum {
+        return Err(error(ContainerErrorKind::ChecksumMismatch));
+    }
+
+    let (offsets, rest) = payload.split_at(8 * (number_of_vertices as usize + 1));
+    let (heads, rest) = rest.split_at(4 * number_of_edges as usize);
+    let (weights, coordinates) = rest.split_at(8 * number_of_edges as usize);
+
+    let offsets: Vec<u64> = offsets
+        .chunks_exact(8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .collect();
+    let heads: Vec<Vertex> = heads
+        .chunks_exact(4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .collect();
+    let weights: Vec<Distance> = weights
+        .chunks_exact(8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .collect();
+
+    if offsets.first() != Some(&0)
+        || offsets.last() != Some(&number_of_edges)
+        || offsets.windows(2).any(|window| window[0] > window[1])
+    {
+        return Err(error(ContainerErrorKind::Malformed(
+            "offsets are not sorted",
+        )));
+    }
+    if heads.iter().any(|&head| head as u64 >= number_of_vertices) {
+        return Err(error(ContainerErrorKind::Malformed(
+            "an edge points to a vertex that does not exist",
+        )));
+    }
+
+    let mut edges = Vec::with_capacity(number_of_edges as usize);
+    for (tail, window) in offsets.windows(2).enumerate() {
+        for index in window[0] as usize..window[1] as usize {
+            edges.push(WeightedEdge::new(
+                tail as Vertex,
+                heads[index],
+                weights[index],
+            ));
+        }
+    }
+    let graph = ReversibleGraph::with_edges(number_of_vertices as Vertex, &edges);
+
+    let attributes = if has_coordinates {
+        coordinates
+            .chunks_exact(16)
+            .map(|bytes| {
+                let latitude = f64::from_le_bytes(bytes[..8].try_into().unwrap());
+                let longitude = f64::from_le_bytes(bytes[8..].try_into().unwrap());
+                VertexAttribute::new(latitude, longitude)
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(AttributedGraph {
+        graph,
+        attributes: VertexAttributes::new(attributes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{read_binary_graph, write_binary_graph};
+    use crate::{
+        error::{ContainerErrorKind, Error},
+        graphs::{
+            csr_graph::CsrGraph,
+            vec_graph::VecGraph,
+            vec_hash_graph::VecHashGraph,
+            vec_vec_graph::VecVecGraph,
+            vertex_attributes::{VertexAttribute, VertexAttributes},
+            Graph, WeightedEdge,
+        },
+    };
+
+    #[test]
+    fn round_trip_derives_in_edges() {
+        let edges = vec![
+            WeightedEdge::new(2, 0, 7),
+            WeightedEdge::new(0, 2, 3),
+            WeightedEdge::new(0, 1, 5),
+            WeightedEdge::new(1, 2, 1),
+        ];
+        let graph = VecVecGraph::from_edges(&edges);
+        let attributes = VertexAttributes::new(vec![
+            VertexAttribute::new(48.1, 9.1),
+            VertexAttribute::new(48.2, 9.2),
+            VertexAttribute::new(48.3, 9.3),
+        ]);
+
+        let mut bytes = Vec::new();
+        write_binary_graph(&mut bytes, Path::new("test"), &graph, Some(&attributes)).unwrap();
+        let read = read_binary_graph::<VecHashGraph>(&bytes, Path::new("test")).unwrap();
+
+        assert_eq!(read.attributes, attributes);
+        assert_eq!(read.graph.out_graph().number_of_vertices(), 3);
+        for edge in edges {
+            assert_eq!(
+                read.graph.out_graph().get_weight(&edge.remove_weight()),
+                Some(edge.weight)
+            );
+            assert_eq!(
+                read.graph
+                    .in_graph()
+                    .get_weight(&edge.remove_weight().reversed()),
+                Some(edge.weight)
+            );
+        }
+
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        match read_binary_graph::<VecVecGraph>(&bytes, Path::new("test")) {
+            Err(Error::Container { kind, .. }) => {
+                assert_eq!(kind, ContainerErrorKind::ChecksumMismatch)
+            }
+            _ => panic!("expected a checksum mismatch"),
+        }
+    }
+
+    #[test]
+    fn loads_into_read_only_graphs() {
+        // Vertex 3 has no edges and is kept anyway.
+        let edges = vec![WeightedEdge::new(0, 1, 5), WeightedEdge::new(2, 0, 7)];
+        let mut graph = VecVecGraph::from_edges(&edges);
+        graph.set_weight(&WeightedEdge::new(3, 3, 0).remove_weight(), None);
+        assert_eq!(graph.number_of_vertices(), 4);
+
+        let mut bytes = Vec::new();
+        write_binary_graph(&mut bytes, Path::new("test"), &graph, None).unwrap();
+
+        let csr = read_binary_graph::<CsrGraph>(&bytes, Path::new("test")).unwrap();
+        let vec = read_binary_graph::<VecGraph>(&bytes, Path::new("test")).unwrap();
+        for read in [csr.graph.out_graph() as &dyn Graph, vec.graph.out_graph()] {
+            assert_eq!(read.number_of_vertices(), 4);
+            assert_eq!(read.get_weight(&edges[1].remove_weight()), Some(7));
+            assert_eq!(read.edges(3).len(), 0);
+        }
+        assert_eq!(
+            csr.graph
+                .in_graph()
+                .get_weight(&edges[0].remove_weight().reversed()),
+            Some(5)
+        );
+    }
+}
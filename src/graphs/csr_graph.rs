@@ -0,0 +1,182 @@
+use rayon::slice::ParallelSliceMut;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Distance, Edge, EdgeSlices, Graph, GraphFromEdges, TaillessEdge, Vertex, WeightedEdge,
+};
+
+/// A read only graph in compressed sparse row layout. The out edges of `v` are
+/// `edges[offsets[v]..offsets[v + 1]]`, sorted by head, so searches can take
+/// them as a slice, see [`EdgeSlices`].
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct CsrGraph {
+    offsets: Vec<usize>,
+    edges: Vec<TaillessEdge>,
+}
+
+impl CsrGraph {
+    /// Builds a graph with `number_of_vertices` vertices, see
+    /// [`GraphFromEdges::with_edges`].
+    pub fn from_edges(number_of_vertices: u32, edges: &[WeightedEdge]) -> CsrGraph {
+        // `from_edges` never inserts an edge of weight `Distance::MAX`.
+        let mut edges: Vec<WeightedEdge> = edges
+            .iter()
+            .filter(|edge| edge.weight < Distance::MAX)
+            .cloned()
+            .collect();
+        edges.par_sort_unstable_by_key(|edge| (edge.tail, edge.head, edge.weight));
+        edges.dedup_by_key(|edge| (edge.tail, edge.head));
+
+        let mut offsets = vec![0; number_of_vertices as usize + 1];
+        for edge in edges.iter() {
+            assert!(
+                edge.tail < number_of_vertices,
+                "tail {} is out of range",
+                edge.tail
+            );
+            assert!(
+                edge.head < number_of_vertices,
+                "head {} is out of range",
+                edge.head
+            );
+            offsets[edge.tail as usize + 1] += 1;
+        }
+        for vertex in 0..number_of_vertices as usize {
+            offsets[vertex + 1] += offsets[vertex];
+        }
+
+        CsrGraph {
+            offsets,
+            edges: edges.iter().map(WeightedEdge::remove_tail).collect(),
+        }
+    }
+
+    /// Copies any graph into the compressed sparse row layout.
+    pub fn from_graph(graph: &dyn Graph) -> CsrGraph {
+        let mut offsets = Vec::with_capacity(graph.number_of_vertices() as usize + 1);
+        let mut edges = Vec::new();
+        offsets.push(0);
+        for vertex in graph.vertices() {
+            let start = edges.len();
+            edges.extend(graph.edges(vertex).map(|edge| edge.remove_tail()));
+            edges[start..].sort_unstable_by_key(|edge| edge.head);
+            offsets.push(edges.len());
+        }
+
+        CsrGraph { offsets, edges }
+    }
+}
+
+impl GraphFromEdges for CsrGraph {
+    fn with_edges(number_of_vertices: u32, edges: &[WeightedEdge]) -> Self {
+        CsrGraph::from_edges(number_of_vertices, edges)
+    }
+}
+
+impl EdgeSlices for CsrGraph {
+    fn edge_slice(&self, tail: Vertex) -> &[TaillessEdge] {
+        match (
+            self.offsets.get(tail as usize),
+            self.offsets.get(tail as usize + 1),
+        ) {
+            (Some(&start), Some(&stop)) => &self.edges[start..stop],
+            _ => &[],
+        }
+    }
+}
+
+impl Graph for CsrGraph {
+    fn number_of_vertices(&self) -> u32 {
+        self.offsets.len().saturating_sub(1) as u32
+    }
+
+    fn edges(&self, tail: Vertex) -> Box<dyn ExactSizeIterator<Item = WeightedEdge> + Send + '_> {
+        Box::new(
+            self.edge_slice(tail)
+                .iter()
+                .map(move |edge| edge.set_tail(tail)),
+        )
+    }
+
+    fn get_weight(&self, edge: &Edge) -> Option<Distance> {
+        let edges = self.edge_slice(edge.tail);
+        let index = edges
+            .binary_search_by_key(&edge.head, |edge| edge.head)
+            .ok()?;
+        Some(edges[index].weight)
+    }
+
+    fn set_weight(&mut self, _edge: &Edge, _weight: Option<Distance>) {
+        unimplemented!("This is a read only graph");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CsrGraph;
+    use crate::{
+        graphs::{
+            vec_graph::VecGraph, vec_hash_graph::VecHashGraph, vec_vec_graph::VecVecGraph,
+            Distance, EdgeSlices, Graph, GraphFromEdges, WeightedEdge,
+        },
+        search::{
+            collections::{
+                dijkstra_data::{DijkstraData, DijkstraDataVec},
+                vertex_distance_queue::VertexDistanceQueueBinaryHeap,
+                vertex_expanded_data::VertexExpandedDataBitSet,
+            },
+            dijkstra::{dijkstra_one_to_all_wraped, dijktra_one_to_all},
+        },
+    };
+
+    #[test]
+    fn static_search_matches_dyn_search() {
+        let edges = vec![
+            WeightedEdge::new(0, 2, 4),
+            WeightedEdge::new(0, 1, 1),
+            WeightedEdge::new(0, 1, 3),
+            WeightedEdge::new(1, 2, 1),
+            WeightedEdge::new(2, 3, 5),
+            WeightedEdge::new(3, 0, 2),
+        ];
+        let graph = VecVecGraph::from_edges(&edges);
+        let csr_graph = CsrGraph::from_edges(5, &edges);
+
+        assert_eq!(csr_graph.number_of_vertices(), 5);
+        assert_eq!(csr_graph.all_edges(), graph.all_edges());
+        assert_eq!(
+            CsrGraph::from_graph(&graph).all_edges(),
+            csr_graph.all_edges()
+        );
+        assert!(csr_graph.edge_slice(4).is_empty());
+        assert!(csr_graph.edge_slice(9).is_empty());
+
+        for source in 0..4 {
+            let expected = dijkstra_one_to_all_wraped(&graph as &dyn Graph, source);
+
+            let mut data = DijkstraDataVec::new(&csr_graph);
+            let mut expanded = VertexExpandedDataBitSet::new(&csr_graph);
+            let mut queue = VertexDistanceQueueBinaryHeap::new();
+            dijktra_one_to_all(&csr_graph, &mut data, &mut expanded, &mut queue, source);
+
+            for target in 0..4 {
+                assert_eq!(data.get_distance(target), expected.get_distance(target));
+            }
+        }
+    }
+
+    #[test]
+    fn graphs_from_edges_drop_edges_of_weight_max() {
+        let edges = vec![
+            WeightedEdge::new(0, 1, 2),
+            WeightedEdge::new(1, 2, Distance::MAX),
+            WeightedEdge::new(2, 0, 3),
+        ];
+        let expected = vec![WeightedEdge::new(0, 1, 2), WeightedEdge::new(2, 0, 3)];
+
+        assert_eq!(CsrGraph::with_edges(3, &edges).all_edges(), expected);
+        assert_eq!(VecGraph::with_edges(3, &edges).all_edges(), expected);
+        assert_eq!(VecHashGraph::with_edges(3, &edges).all_edges(), expected);
+        assert_eq!(VecVecGraph::with_edges(3, &edges).all_edges(), expected);
+    }
+}
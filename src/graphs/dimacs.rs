@@ -0,0 +1,439 @@
+//! Files of the 9th DIMACS implementation challenge. DIMACS numbers vertices
+//! from 1, while this crate numbers them from 0. The readers subtract 1 from
+//! every vertex id and the writers add it again.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use super::{
+    parsing::{numbered_lines, LineFields},
+    vertex_attributes::{VertexAttribute, VertexAttributes},
+    weight_scaling::{WeightReport, WeightScaling},
+    Graph, Vertex, WeightedEdge,
+};
+use crate::error::{Error, ParseError, ParseErrorKind, Result};
+
+/// DIMACS stores coordinates as integers in millionths of a degree.
+const COORDINATE_FACTOR: f64 = 1_000_000.0;
+
+/// Content of a DIMACS `.gr` file.
+#[derive(Debug)]
+pub struct GrFile {
+    /// Number of vertices of the `p sp n m` header, if there is one.
+    pub number_of_vertices: Option<usize>,
+    pub edges: Vec<WeightedEdge>,
+    pub weight_report: WeightReport,
+}
+
+/// Reads the arcs (`a tail head weight`) of a DIMACS `.gr` file.
+pub fn try_read_edges_from_gr_file(path: &Path) -> Result<Vec<WeightedEdge>> {
+    Ok(try_read_gr_file(path, &WeightScaling::default())?.edges)
+}
+
+/// Reads a DIMACS `.gr` file and converts its weights with `scaling`.
+pub fn try_read_gr_file(path: &Path, scaling: &WeightScaling) -> Result<GrFile> {
+    let file = File::open(path).map_err(|error| Error::io(path, error))?;
+    read_gr(BufReader::new(file), path, scaling)
+}
+
+/// Reads a DIMACS `.gr` file from `reader`. `path` is only used to give errors
+/// some context.
+///
+/// If the file has a `p sp n m` header, it must come before the first arc, all
+/// arcs must be within the `n` vertices and there must be exactly `m` arcs.
+/// Comment lines (`c`) and lines of unknown type are ignored.
+pub fn read_gr<R: BufRead>(reader: R, path: &Path, scaling: &WeightScaling) -> Result<GrFile> {
+    let mut header = None;
+    let mut edges = Vec::new();
+    let mut weight_report = WeightReport::default();
+    let mut line_number = 0;
+
+    for line in numbered_lines(reader, path) {
+        let (current_line_number, line) = line?;
+        line_number = current_line_number;
+        let mut fields = LineFields::new(path, line_number, &line);
+
+        match line.split_whitespace().next() {
+            Some("p") => {
+                check_header_position(&fields, header.is_some(), !edges.is_empty())?;
+                fields.next_field("line type")?;
+                expect_field(&mut fields, "problem type", "sp")?;
+                let number_of_vertices: usize = fields.next_integer("number of vertices")?;
+                let number_of_edges: usize = fields.next_integer("number of edges")?;
+                header = Some((number_of_vertices, number_of_edges));
+            }
+            Some("a") => {
+                fields.next_field("line type")?;
+                let number_of_vertices = header.map(|(number_of_vertices, _)| number_of_vertices);
+                let tail = next_vertex(&mut fields, "tail", number_of_vertices)?;
+                let head = next_vertex(&mut fields, "head", number_of_vertices)?;
+                let weight = fields.next_weight("weight", scaling, &mut weight_report)?;
+                edges.push(WeightedEdge::new(tail, head, weight));
+            }
+            _ => (),
+        }
+    }
+
+    if let Some((_, number_of_edges)) = header {
+        if edges.len() != number_of_edges {
+            return Err(ParseError {
+                path: path.to_path_buf(),
+                line: line_number + 1,
+                column: 1,
+                kind: ParseErrorKind::EdgeCountMismatch {
+                    expected: number_of_edges,
+                    found: edges.len(),
+                },
+            }
+            .into());
+        }
+    }
+
+    Ok(GrFile {
+        number_of_vertices: header.map(|(number_of_vertices, _)| number_of_vertices),
+        edges,
+        weight_report,
+    })
+}
+
+/// Reads the coordinates (`v id x y`) of a DIMACS `.co` file, where `x` is the
+/// longitude and `y` the latitude in millionths of a degree.
+pub fn try_read_co_file(path: &Path) -> Result<VertexAttributes> {
+    let file = File::open(path).map_err(|error| Error::io(path, error))?;
+    read_co(BufReader::new(file), path)
+}
+
+/// Reads a DIMACS `.co` file from `reader`. `path` is only used to give errors
+/// some context. Every vertex up to the number of vertices of the `p aux sp co
+/// n` header, or the largest id if there is no header, needs a coordinate. The
+/// header must come before the first coordinate.
+pub fn read_co<R: BufRead>(reader: R, path: &Path) -> Result<VertexAttributes> {
+    let mut number_of_vertices = None;
+    let mut attributes: Vec<Option<VertexAttribute>> = Vec::new();
+    let mut line_number = 0;
+
+    for line in numbered_lines(reader, path) {
+        let (current_line_number, line) = line?;
+        line_number = current_line_number;
+        let mut fields = LineFields::new(path, line_number, &line);
+
+        match line.split_whitespace().next() {
+            Some("p") => {
+                // p aux sp co n
+                check_header_position(
+                    &fields,
+                    number_of_vertices.is_some(),
+                    !attributes.is_empty(),
+                )?;
+                fields.next_field("line type")?;
+                expect_field(&mut fields, "aux", "aux")?;
+                expect_field(&mut fields, "problem type", "sp")?;
+                expect_field(&mut fields, "file type", "co")?;
+                number_of_vertices = Some(fields.next_integer("number of vertices")?);
+            }
+            Some("v") => {
+                fields.next_field("line type")?;
+                let vertex = next_vertex(&mut fields, "vertex", number_of_vertices)? as usize;
+                let longitude = fields.next_number("x")? / COORDINATE_FACTOR;
+                let latitude = fields.next_number("y")? / COORDINATE_FACTOR;
+
+                if vertex >= attributes.len() {
+                    attributes.resize(vertex + 1, None);
+                }
+                attributes[vertex] = Some(VertexAttribute::new(latitude, longitude));
+            }
+            _ => (),
+        }
+    }
+
+    let expected = number_of_vertices.unwrap_or(attributes.len());
+    attributes.resize(expected, None);
+    let found = attributes.iter().flatten().count();
+    if found != expected {
+        return Err(ParseError {
+            path: path.to_path_buf(),
+            line: line_number + 1,
+            column: 1,
+            kind: ParseErrorKind::VertexCountMismatch { expected, found },
+        }
+        .into());
+    }
+
+    Ok(VertexAttributes::new(
+        attributes.into_iter().flatten().collect(),
+    ))
+}
+
+/// Writes all edges of `graph` as DIMACS `.gr` file.
+pub fn write_gr_file(graph: &dyn Graph, path: &Path) -> Result<()> {
+    let file = File::create(path).map_err(|error| Error::io(path, error))?;
+    write_gr(graph, BufWriter::new(file)).map_err(|error| Error::io(path, error))
+}
+
+pub fn write_gr<W: Write>(graph: &dyn Graph, mut writer: W) -> std::io::Result<()> {
+    writeln!(writer, "c written by faster_paths")?;
+    writeln!(
+        writer,
+        "p sp {} {}",
+        graph.number_of_vertices(),
+        graph.number_of_edges()
+    )?;
+    for vertex in graph.vertices() {
+        for edge in graph.edges(vertex) {
+            writeln!(
+                writer,
+                "a {} {} {}",
+                edge.tail + 1,
+                edge.head + 1,
+                edge.weight
+            )?;
+        }
+    }
+    writer.flush()
+}
+
+/// Writes the coordinates of `attributes` as DIMACS `.co` file.
+pub fn write_co_file(attributes: &VertexAttributes, path: &Path) -> Result<()> {
+    let file = File::create(path).map_err(|error| Error::io(path, error))?;
+    write_co(attributes, BufWriter::new(file)).map_err(|error| Error::io(path, error))
+}
+
+pub fn write_co<W: Write>(attributes: &VertexAttributes, mut writer: W) -> std::io::Result<()> {
+    writeln!(writer, "c written by faster_paths")?;
+    writeln!(writer, "p aux sp co {}", attributes.number_of_vertices())?;
+    for (vertex, attribute) in attributes.iter().enumerate() {
+        writeln!(
+            writer,
+            "v {} {} {}",
+            vertex + 1,
+            (attribute.longitude * COORDINATE_FACTOR).round() as i64,
+            (attribute.latitude * COORDINATE_FACTOR).round() as i64
+        )?;
+    }
+    writer.flush()
+}
+
+/// Rejects a second header and a header after the first arc or coordinate.
+fn check_header_position(fields: &LineFields, has_header: bool, has_data: bool) -> Result<()> {
+    if has_header {
+        return Err(fields.error(1, ParseErrorKind::DuplicateHeader).into());
+    }
+    if has_data {
+        return Err(fields.error(1, ParseErrorKind::HeaderAfterData).into());
+    }
+    Ok(())
+}
+
+/// Reads a header field that must be `expected`, e.g. the problem type `sp`.
+fn expect_field(
+    fields: &mut LineFields,
+    field: &'static str,
+    expected: &'static str,
+) -> Result<()> {
+    let (column, value) = fields.next_field(field)?;
+    if value != expected {
+        let kind = ParseErrorKind::UnexpectedValue {
+            field,
+            expected,
+            value: value.to_string(),
+        };
+        return Err(fields.error(column, kind).into());
+    }
+    Ok(())
+}
+
+/// Parses a 1-based DIMACS vertex id into a [`Vertex`].
+fn next_vertex(
+    fields: &mut LineFields,
+    field: &'static str,
+    number_of_vertices: Option<usize>,
+) -> Result<Vertex> {
+    let (column, value) = fields.next_field(field)?;
+    let id: Vertex = value.parse().map_err(|_| {
+        fields.error(
+            column,
+            ParseErrorKind::InvalidInteger {
+                field,
+                value: value.to_string(),
+            },
+        )
+    })?;
+
+    if id == 0 {
+        return Err(fields.error(column, ParseErrorKind::VertexIdZero).into());
+    }
+    if let Some(number_of_vertices) = number_of_vertices {
+        if id as usize > number_of_vertices {
+            let kind = ParseErrorKind::VertexOutOfRange {
+                vertex: id,
+                number_of_vertices,
+            };
+            return Err(fields.error(column, kind).into());
+        }
+    }
+
+    Ok(id - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{read_co, read_gr, write_co, write_gr};
+    use crate::{
+        error::{Error, ParseErrorKind},
+        graphs::{
+            vec_vec_graph::VecVecGraph,
+            vertex_attributes::{VertexAttribute, VertexAttributes},
+            weight_scaling::WeightScaling,
+            Graph, WeightedEdge,
+        },
+    };
+
+    #[test]
+    fn gr_round_trip() {
+        let edges = vec![
+            WeightedEdge::new(0, 1, 3),
+            WeightedEdge::new(1, 2, 4),
+            WeightedEdge::new(2, 0, 5),
+        ];
+        let graph = VecVecGraph::from_edges(&edges);
+
+        let mut buffer = Vec::new();
+        write_gr(&graph, &mut buffer).unwrap();
+        assert!(String::from_utf8_lossy(&buffer).contains("p sp 3 3\na 1 2 3\n"));
+
+        let gr = read_gr(
+            buffer.as_slice(),
+            Path::new("test.gr"),
+            &WeightScaling::default(),
+        )
+        .unwrap();
+        assert_eq!(gr.number_of_vertices, Some(3));
+        assert_eq!(gr.edges, graph.all_edges());
+    }
+
+    #[test]
+    fn gr_file_round_trip() {
+        let content = "c written by faster_paths\np sp 3 3\na 1 2 3\na 2 3 4\na 3 1 5\n";
+        let gr = read_gr(
+            content.as_bytes(),
+            Path::new("test.gr"),
+            &WeightScaling::default(),
+        )
+        .unwrap();
+        assert_eq!(gr.edges[0], WeightedEdge::new(0, 1, 3));
+
+        let mut buffer = Vec::new();
+        write_gr(&VecVecGraph::from_edges(&gr.edges), &mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), content);
+    }
+
+    #[test]
+    fn gr_header_is_validated() {
+        let read = |content: &str| match read_gr(
+            content.as_bytes(),
+            Path::new("test.gr"),
+            &WeightScaling::default(),
+        ) {
+            Err(Error::Parse(error)) => (error.line, error.kind),
+            other => panic!("expected parse error, got {:?}", other),
+        };
+
+        assert_eq!(
+            read("p sp 2 1\na 1 3 5\n"),
+            (
+                2,
+                ParseErrorKind::VertexOutOfRange {
+                    vertex: 3,
+                    number_of_vertices: 2
+                }
+            )
+        );
+        assert_eq!(
+            read("c comment\np sp 2 2\na 1 2 5\n"),
+            (
+                4,
+                ParseErrorKind::EdgeCountMismatch {
+                    expected: 2,
+                    found: 1
+                }
+            )
+        );
+        assert_eq!(read("a 0 1 5\n"), (1, ParseErrorKind::VertexIdZero));
+        assert_eq!(
+            read("a 1 3 5\np sp 2 1\n"),
+            (2, ParseErrorKind::HeaderAfterData)
+        );
+        assert_eq!(
+            read("p sp 3 1\np sp 2 1\na 1 3 5\n"),
+            (2, ParseErrorKind::DuplicateHeader)
+        );
+        assert_eq!(
+            read("p max 2 1\na 1 2 5\n"),
+            (
+                1,
+                ParseErrorKind::UnexpectedValue {
+                    field: "problem type",
+                    expected: "sp",
+                    value: "max".to_string()
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn co_header_is_validated() {
+        let read = |content: &str| match read_co(content.as_bytes(), Path::new("test.co")) {
+            Err(Error::Parse(error)) => (error.line, error.kind),
+            other => panic!("expected parse error, got {:?}", other),
+        };
+
+        assert_eq!(
+            read("p aux sp co 1\nv 1 0 0\nv 2 0 0\n"),
+            (
+                3,
+                ParseErrorKind::VertexOutOfRange {
+                    vertex: 2,
+                    number_of_vertices: 1
+                }
+            )
+        );
+        assert_eq!(
+            read("v 2 0 0\np aux sp co 1\nv 1 0 0\n"),
+            (2, ParseErrorKind::HeaderAfterData)
+        );
+        assert_eq!(
+            read("p aux sp co 1\np aux sp co 2\n"),
+            (2, ParseErrorKind::DuplicateHeader)
+        );
+        assert_eq!(
+            read("p aux sp gr 1\nv 1 0 0\n"),
+            (
+                1,
+                ParseErrorKind::UnexpectedValue {
+                    field: "file type",
+                    expected: "co",
+                    value: "gr".to_string()
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn co_round_trip() {
+        let attributes = VertexAttributes::new(vec![
+            VertexAttribute::new(48.775556, 9.182778),
+            VertexAttribute::new(-33.856784, 151.215297),
+        ]);
+
+        let mut buffer = Vec::new();
+        write_co(&attributes, &mut buffer).unwrap();
+        let read = read_co(buffer.as_slice(), Path::new("test.co")).unwrap();
+
+        assert_eq!(read, attributes);
+    }
+}
@@ -0,0 +1,249 @@
+//! The edge based graph, which models turns as edges so that restricted and
+//! costly turns, see [`super::turn_restrictions`], are respected by every
+//! search that runs on a plain graph.
+//!
+//! Every edge `u -> v` of the input becomes a vertex, which stands for having
+//! arrived at `v` over this edge. An allowed turn from `u -> v` onto `v -> w`
+//! becomes an edge between the two edge vertices, weighted with the weight of
+//! `v -> w` plus the turn cost. Additionally, every input vertex `v` gets a
+//! source vertex with edges to the edge vertices of its out edges, and a
+//! target vertex with free edges from the edge vertices of its in edges.
+//!
+//! A query from `s` to `t` on the input becomes a query from the source vertex
+//! of `s` to the target vertex of `t`. [`EdgeBasedMapping`] translates the
+//! vertices and restores paths, and [`crate::search::edge_based::EdgeBased`]
+//! wraps a path finder built on the edge based graph, e.g. a contracted graph
+//! or a hub graph, so that it answers queries on the input.
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    reversible_graph::ReversibleGraph,
+    turn_restrictions::{Turn, TurnRestrictions},
+    Edge, Graph, Vertex, WeightedEdge,
+};
+use crate::search::collections::dijkstra_data::Path;
+
+/// Translates between the vertices of an input graph and its edge based
+/// graph.
+///
+/// The edge vertices come first, numbered in the order of
+/// [`Graph::all_edges`] of the input, followed by the source and then the
+/// target vertices of the input vertices.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EdgeBasedMapping {
+    number_of_vertices: u32,
+    tails: Vec<Vertex>,
+    heads: Vec<Vertex>,
+}
+
+impl EdgeBasedMapping {
+    /// The number of vertices of the input graph.
+    pub fn number_of_vertices(&self) -> u32 {
+        self.number_of_vertices
+    }
+
+    /// The number of edges of the input graph, which is the number of edge
+    /// vertices.
+    pub fn number_of_edges(&self) -> u32 {
+        self.heads.len() as u32
+    }
+
+    /// Returns the source vertex of the input vertex `vertex`.
+    pub fn source(&self, vertex: Vertex) -> Option<Vertex> {
+        (vertex < self.number_of_vertices).then(|| self.number_of_edges() + vertex)
+    }
+
+    /// Returns the target vertex of the input vertex `vertex`.
+    pub fn target(&self, vertex: Vertex) -> Option<Vertex> {
+        (vertex < self.number_of_vertices)
+            .then(|| self.number_of_edges() + self.number_of_vertices + vertex)
+    }
+
+    /// Returns the input edge of an edge vertex.
+    pub fn original_edge(&self, vertex: Vertex) -> Option<Edge> {
+        Some(Edge {
+            tail: *self.tails.get(vertex as usize)?,
+            head: self.heads[vertex as usize],
+        })
+    }
+
+    /// Translates a path from a source vertex to a target vertex of the edge
+    /// based graph into the path of input vertices it drives along.
+    pub fn restore_path(&self, path: &Path) -> Path {
+        let number_of_edges = self.number_of_edges();
+        let vertices = path
+            .vertices
+            .iter()
+            .filter_map(|&vertex| {
+                if vertex < number_of_edges {
+                    Some(self.heads[vertex as usize])
+                } else if vertex < number_of_edges + self.number_of_vertices {
+                    Some(vertex - number_of_edges)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Path {
+            vertices,
+            distance: path.distance,
+        }
+    }
+}
+
+/// Builds the edge based graph of `graph` with the turns allowed by
+/// `restrictions`.
+pub fn edge_based_graph<G: Graph + Default>(
+    graph: &ReversibleGraph<G>,
+    restrictions: &TurnRestrictions,
+) -> (ReversibleGraph<G>, EdgeBasedMapping) {
+    let out_graph: &dyn Graph = graph.out_graph();
+    let number_of_vertices = out_graph
+        .number_of_vertices()
+        .max(graph.in_graph().number_of_vertices());
+    let edges = out_graph.all_edges();
+    let number_of_edges = edges.len() as Vertex;
+
+    // `all_edges` lists the edges grouped by tail, so the edge vertices of the
+    // out edges of `v` are `first_edge[v]..first_edge[v + 1]`.
+    let mut first_edge = vec![0; number_of_vertices as usize + 1];
+    for edge in edges.iter() {
+        first_edge[edge.tail as usize + 1] += 1;
+    }
+    for vertex in 0..number_of_vertices as usize {
+        first_edge[vertex + 1] += first_edge[vertex];
+    }
+
+    let mapping = EdgeBasedMapping {
+        number_of_vertices,
+        tails: edges.iter().map(|edge| edge.tail).collect(),
+        heads: edges.iter().map(|edge| edge.head).collect(),
+    };
+
+    let mut edge_based_edges = Vec::new();
+    for (vertex, edge) in (0..number_of_edges).zip(edges.iter()) {
+        edge_based_edges.push(WeightedEdge::new(
+            mapping.source(edge.tail).unwrap(),
+            vertex,
+            edge.weight,
+        ));
+        edge_based_edges.push(WeightedEdge::new(
+            vertex,
+            mapping.target(edge.head).unwrap(),
+            0,
+        ));
+
+        let next_edges = first_edge[edge.head as usize]..first_edge[edge.head as usize + 1];
+        let is_dead_end = edges[next_edges.clone()]
+            .iter()
+            .all(|next_edge| next_edge.head == edge.tail);
+        for next_vertex in next_edges {
+            let next_edge = &edges[next_vertex];
+            let turn = Turn::new(edge.tail, edge.head, next_edge.head);
+            if let Some(cost) = restrictions.turn_cost(&turn, is_dead_end) {
+                edge_based_edges.push(WeightedEdge::new(
+                    vertex,
+                    next_vertex as Vertex,
+                    next_edge.weight.saturating_add(cost),
+                ));
+            }
+        }
+    }
+
+    (ReversibleGraph::from_edges(&edge_based_edges), mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edge_based_graph;
+    use crate::{
+        graphs::{
+            reversible_graph::ReversibleGraph,
+            turn_restrictions::{read_turn_restrictions, Turn, TurnRestrictions},
+            vec_vec_graph::VecVecGraph,
+            WeightedEdge,
+        },
+        search::{
+            ch::contracted_graph::ContractedGraph, edge_based::EdgeBased, hl::hub_graph::HubGraph,
+            PathFinding,
+        },
+    };
+
+    #[test]
+    fn restricted_turns_are_avoided() {
+        // A square 0 - 1 - 2 - 3 - 0 with edges in both directions and the dead
+        // end 2 - 4.
+        let mut edges = Vec::new();
+        for (tail, head, weight) in [(0, 1, 1), (1, 2, 1), (2, 3, 5), (3, 0, 6), (2, 4, 1)] {
+            edges.push(WeightedEdge::new(tail, head, weight));
+            edges.push(WeightedEdge::new(head, tail, weight));
+        }
+        let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+
+        let content = "# no turn from 0 over 1 to 2\nn 0 1 2\n\nc 1 2 4 3\n";
+        let restrictions =
+            read_turn_restrictions(content.as_bytes(), std::path::Path::new("test.turns"), 5)
+                .unwrap();
+        assert_eq!(restrictions.number_of_entries(), 2);
+        assert_eq!(restrictions.turn_cost(&Turn::new(2, 1, 2), false), None);
+        assert!(read_turn_restrictions("x 0 1 2".as_bytes(), "test.turns".as_ref(), 5).is_err());
+
+        let (expanded_graph, mapping) = edge_based_graph(&graph, &restrictions);
+        let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&expanded_graph, 10);
+        let hub_graph = HubGraph::by_merging(&contracted_graph);
+        let pathfinders: [Box<dyn PathFinding>; 3] = [
+            Box::new(EdgeBased::new(
+                expanded_graph.out_graph().clone(),
+                mapping.clone(),
+            )),
+            Box::new(EdgeBased::new(contracted_graph, mapping.clone())),
+            Box::new(EdgeBased::new(hub_graph, mapping)),
+        ];
+
+        for pathfinder in pathfinders.iter() {
+            // Queries from a vertex to itself are answered like on the input
+            // graph.
+            for vertex in [0, 2] {
+                assert_eq!(
+                    pathfinder.shortest_path(vertex, vertex).unwrap().vertices,
+                    graph.shortest_path(vertex, vertex).unwrap().vertices
+                );
+                assert_eq!(
+                    pathfinder.shortest_path_distance(vertex, vertex),
+                    graph.shortest_path_distance(vertex, vertex)
+                );
+            }
+
+            // 0 -> 1 -> 2 is forbidden, and so is the U-turn at 1.
+            let path = pathfinder.shortest_path(0, 2).unwrap();
+            assert_eq!(path.vertices, vec![0, 3, 2]);
+            assert_eq!(path.distance, 11);
+            assert_eq!(pathfinder.shortest_path_distance(1, 2), Some(1));
+
+            // Turning into the dead end costs 3.
+            assert_eq!(
+                pathfinder.shortest_path(1, 3).unwrap().vertices,
+                vec![1, 2, 3]
+            );
+            assert_eq!(pathfinder.shortest_path_distance(1, 4), Some(5));
+            assert_eq!(
+                pathfinder.shortest_path(4, 1).unwrap().vertices,
+                vec![4, 2, 1]
+            );
+        }
+
+        let mut restrictions = TurnRestrictions::default();
+        restrictions.u_turn_cost = Some(2);
+        restrictions.require(Turn::new(1, 2, 4));
+        let (expanded_graph, mapping) = edge_based_graph(&graph, &restrictions);
+        let pathfinder = EdgeBased::new(expanded_graph.out_graph().clone(), mapping);
+        // The only way on from 1 -> 2 is into the dead end.
+        assert_eq!(pathfinder.shortest_path(0, 3).unwrap().vertices, vec![0, 3]);
+        assert_eq!(
+            pathfinder.shortest_path(1, 3).unwrap().vertices,
+            vec![1, 0, 3]
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use super::{
+    weight_scaling::{WeightReport, WeightScaling},
+    Distance, Edge, Graph, Vertex, WeightedEdge,
+};
+use crate::error::{Error, ParseError, ParseErrorKind, Result};
+
+/// Layout of a delimited edge list, e.g. a CSV export with one edge per row.
+#[derive(Clone, Debug)]
+pub struct EdgeListOptions {
+    /// 0-based column of the tail vertex.
+    pub tail_column: usize,
+    /// 0-based column of the head vertex.
+    pub head_column: usize,
+    /// 0-based column of the weight.
+    pub weight_column: usize,
+    /// 0-based columns of further metrics, see
+    /// [`EdgeListFile::additional_weights`].
+    pub additional_weight_columns: Vec<usize>,
+    /// If true, the first row holds column names and is skipped.
+    pub has_header: bool,
+    pub delimiter: u8,
+    /// If true, every row is an edge in both directions, unless the opposite
+    /// edge has a row of its own. This keeps opposite edges with different
+    /// weights apart.
+    pub undirected: bool,
+    /// How weights are converted to integers.
+    pub scaling: WeightScaling,
+}
+
+impl Default for EdgeListOptions {
+    /// Comma separated `tail,head,weight` with a header row.
+    fn default() -> Self {
+        EdgeListOptions {
+            tail_column: 0,
+            head_column: 1,
+            weight_column: 2,
+            additional_weight_columns: Vec::new(),
+            has_header: true,
+            delimiter: b',',
+            undirected: false,
+            scaling: WeightScaling::default(),
+        }
+    }
+}
+
+impl EdgeListOptions {
+    /// Tab separated `tail head weight` with a header row.
+    pub fn tsv() -> Self {
+        EdgeListOptions {
+            delimiter: b'\t',
+            ..EdgeListOptions::default()
+        }
+    }
+}
+
+/// Content of an edge list.
+#[derive(Debug)]
+pub struct EdgeListFile {
+    pub edges: Vec<WeightedEdge>,
+    /// One column per entry of [`EdgeListOptions::additional_weight_columns`],
+    /// the `i`th weight of a column belongs to `edges[i]`.
+    pub additional_weights: Vec<Vec<Distance>>,
+    pub weight_report: WeightReport,
+}
+
+/// Reads the edges of a delimited edge list. Self edges are dropped.
+pub fn try_read_edges_from_edge_list_file(
+    path: &Path,
+    options: &EdgeListOptions,
+) -> Result<Vec<WeightedEdge>> {
+    Ok(try_read_edge_list_file(path, options)?.edges)
+}
+
+/// Reads a delimited edge list, see [`try_read_edges_from_edge_list_file`].
+pub fn try_read_edge_list_file(path: &Path, options: &EdgeListOptions) -> Result<EdgeListFile> {
+    let file = File::open(path).map_err(|error| Error::io(path, error))?;
+    read_edge_list(BufReader::new(file), path, options)
+}
+
+/// Reads a delimited edge list from `reader`. `path` is only used to give
+/// errors some context. The column of an error is the 1-based number of the
+/// field.
+pub fn read_edge_list<R: Read>(
+    reader: R,
+    path: &Path,
+    options: &EdgeListOptions,
+) -> Result<EdgeListFile> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(options.has_header)
+        .flexible(true)
+        .from_reader(reader);
+
+    let mut edges = Vec::new();
+    let mut additional_weights = vec![Vec::new(); options.additional_weight_columns.len()];
+    let mut edge_weights = Vec::new();
+    let mut weight_report = WeightReport::default();
+
+    for record in reader.records() {
+        let record = record.map_err(|error| csv_error(path, error))?;
+        let line = record
+            .position()
+            .map_or(0, |position| position.line() as usize);
+
+        let field = |index: usize, name: &'static str| {
+            record.get(index).map(str::trim).ok_or_else(|| ParseError {
+                path: path.to_path_buf(),
+                line,
+                column: index + 1,
+                kind: ParseErrorKind::MissingField(name),
+            })
+        };
+        let invalid = |index: usize, kind: ParseErrorKind| ParseError {
+            path: path.to_path_buf(),
+            line,
+            column: index + 1,
+            kind,
+        };
+
+        let parse_vertex = |index: usize, name: &'static str| -> Result<Vertex> {
+            let value = field(index, name)?;
+            value.parse().map_err(|_| {
+                invalid(
+                    index,
+                    ParseErrorKind::InvalidInteger {
+                        field: name,
+                        value: value.to_string(),
+                    },
+                )
+                .into()
+            })
+        };
+
+        let mut parse_weight = |index: usize| -> Result<Distance> {
+            let value = field(index, "weight")?;
+            options
+                .scaling
+                .convert_str("weight", value, &mut weight_report)
+                .map_err(|kind| invalid(index, kind).into())
+        };
+
+        let tail = parse_vertex(options.tail_column, "tail")?;
+        let head = parse_vertex(options.head_column, "head")?;
+        let weight = parse_weight(options.weight_column)?;
+        edge_weights.clear();
+        for &index in options.additional_weight_columns.iter() {
+            edge_weights.push(parse_weight(index)?);
+        }
+
+        if tail == head {
+            continue;
+        }
+
+        edges.push(WeightedEdge::new(tail, head, weight));
+        for (column, &weight) in additional_weights.iter_mut().zip(edge_weights.iter()) {
+            column.push(weight);
+        }
+    }
+
+    if options.undirected {
+        (edges, additional_weights) = add_opposite_edges(edges, additional_weights);
+    }
+
+    Ok(EdgeListFile {
+        edges,
+        additional_weights,
+        weight_report,
+    })
+}
+
+/// Follows every edge by its opposite edge, unless the opposite edge is one of
+/// `edges` itself.
+fn add_opposite_edges(
+    edges: Vec<WeightedEdge>,
+    additional_weights: Vec<Vec<Distance>>,
+) -> (Vec<WeightedEdge>, Vec<Vec<Distance>>) {
+    let listed = edges
+        .iter()
+        .map(|edge| (edge.tail, edge.head))
+        .collect::<HashSet<_>>();
+
+    let mut all_edges = Vec::with_capacity(2 * edges.len());
+    let mut all_additional_weights =
+        vec![Vec::with_capacity(2 * edges.len()); additional_weights.len()];
+    for (index, edge) in edges.into_iter().enumerate() {
+        let has_opposite_edge = listed.contains(&(edge.head, edge.tail));
+        let number_of_directions = if has_opposite_edge { 1 } else { 2 };
+        for (all_weights, weights) in all_additional_weights
+            .iter_mut()
+            .zip(additional_weights.iter())
+        {
+            all_weights.extend(std::iter::repeat_n(weights[index], number_of_directions));
+        }
+        all_edges.push(edge.clone());
+        if !has_opposite_edge {
+            all_edges.push(edge.reversed());
+        }
+    }
+
+    (all_edges, all_additional_weights)
+}
+
+/// Writes all edges of `graph` as delimited edge list. If `options.undirected`
+/// is set, each pair of opposite edges with the same weight is written only
+/// once.
+pub fn write_edge_list_file(
+    graph: &dyn Graph,
+    path: &Path,
+    options: &EdgeListOptions,
+) -> Result<()> {
+    let file = File::create(path).map_err(|error| Error::io(path, error))?;
+    write_edge_list(graph, BufWriter::new(file), options).map_err(|error| csv_error(path, error))
+}
+
+pub fn write_edge_list<W: Write>(
+    graph: &dyn Graph,
+    writer: W,
+    options: &EdgeListOptions,
+) -> std::result::Result<(), csv::Error> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_writer(writer);
+
+    let number_of_columns = options
+        .tail_column
+        .max(options.head_column)
+        .max(options.weight_column)
+        + 1;
+    let mut record = vec![String::new(); number_of_columns];
+
+    if options.has_header {
+        record[options.tail_column] = "tail".to_string();
+        record[options.head_column] = "head".to_string();
+        record[options.weight_column] = "weight".to_string();
+        writer.write_record(&record)?;
+    }
+
+    for vertex in graph.vertices() {
+        for edge in graph.edges(vertex) {
+            if options.undirected
+                && edge.tail > edge.head
+                && graph.get_weight(&Edge {
+                    tail: edge.head,
+                    head: edge.tail,
+                }) == Some(edge.weight)
+            {
+                continue;
+            }
+
+            record[options.tail_column] = edge.tail.to_string();
+            record[options.head_column] = edge.head.to_string();
+            record[options.weight_column] = edge.weight.to_string();
+            writer.write_record(&record)?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn csv_error(path: &Path, error: csv::Error) -> Error {
+    let line = error
+        .position()
+        .map_or(0, |position| position.line() as usize);
+
+    match error.into_kind() {
+        csv::ErrorKind::Io(error) => Error::io(path, error),
+        kind => ParseError {
+            path: path.to_path_buf(),
+            line,
+            column: 1,
+            kind: ParseErrorKind::InvalidRecord(format!("{:?}", kind)),
+        }
+        .into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{read_edge_list, write_edge_list, EdgeListOptions};
+    use crate::{
+        error::{Error, ParseErrorKind},
+        graphs::{vec_vec_graph::VecVecGraph, Graph, WeightedEdge},
+    };
+
+    #[test]
+    fn reads_mapped_columns() {
+        let content = "id;weight;from;to\n7;1.5;0;1\n8;2;1;2\n9;3;2;2\n";
+        let options = EdgeListOptions {
+            tail_column: 2,
+            head_column: 3,
+            weight_column: 1,
+            delimiter: b';',
+            undirected: true,
+            ..EdgeListOptions::default()
+        };
+        let edge_list =
+            read_edge_list(content.as_bytes(), Path::new("test.csv"), &options).unwrap();
+
+        assert_eq!(
+            edge_list.edges,
+            vec![
+                WeightedEdge::new(0, 1, 2),
+                WeightedEdge::new(1, 0, 2),
+                WeightedEdge::new(1, 2, 2),
+                WeightedEdge::new(2, 1, 2),
+            ]
+        );
+        assert_eq!(edge_list.weight_report.lost_precision, 1);
+    }
+
+    #[test]
+    fn reports_location_of_bad_fields() {
+        let content = "tail,head,weight\n0,1,1\n0,x,1\n";
+        let error = read_edge_list(
+            content.as_bytes(),
+            Path::new("test.csv"),
+            &EdgeListOptions::default(),
+        );
+
+        match error {
+            Err(Error::Parse(error)) => {
+                assert_eq!((error.line, error.column), (3, 2));
+                assert!(matches!(
+                    error.kind,
+                    ParseErrorKind::InvalidInteger { field: "head", .. }
+                ));
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let edges = vec![
+            WeightedEdge::new(0, 1, 3),
+            WeightedEdge::new(1, 0, 3),
+            WeightedEdge::new(1, 2, 4),
+            WeightedEdge::new(2, 1, 4),
+        ];
+        let graph = VecVecGraph::from_edges(&edges);
+
+        for options in [
+            EdgeListOptions::tsv(),
+            EdgeListOptions {
+                undirected: true,
+                has_header: false,
+                ..EdgeListOptions::default()
+            },
+        ] {
+            let mut buffer = Vec::new();
+            write_edge_list(&graph, &mut buffer, &options).unwrap();
+            let edge_list = read_edge_list(buffer.as_slice(), Path::new("test"), &options).unwrap();
+
+            let read_graph = VecVecGraph::from_edges(&edge_list.edges);
+            assert_eq!(read_graph.all_edges().len(), graph.all_edges().len());
+            for edge in graph.all_edges() {
+                assert_eq!(
+                    read_graph.get_weight(&edge.remove_weight()),
+                    Some(edge.weight)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn undirected_round_trip_keeps_asymmetric_weights() {
+        let edges = vec![
+            WeightedEdge::new(0, 1, 3),
+            WeightedEdge::new(1, 0, 7),
+            WeightedEdge::new(1, 2, 4),
+            WeightedEdge::new(2, 1, 4),
+        ];
+        let graph = VecVecGraph::from_edges(&edges);
+        let options = EdgeListOptions {
+            undirected: true,
+            ..EdgeListOptions::default()
+        };
+
+        let mut buffer = Vec::new();
+        write_edge_list(&graph, &mut buffer, &options).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer.clone()).unwrap(),
+            "tail,head,weight\n0,1,3\n1,0,7\n1,2,4\n"
+        );
+
+        let edge_list = read_edge_list(buffer.as_slice(), Path::new("test"), &options).unwrap();
+        let read_graph = VecVecGraph::from_edges(&edge_list.edges);
+        assert_eq!(read_graph.all_edges(), graph.all_edges());
+    }
+}
@@ -0,0 +1,796 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+use super::{
+    parsing::{numbered_lines, LineFields},
+    vertex_attributes::{VertexAttribute, VertexAttributes},
+    weight_scaling::{invalid_weight, WeightReport, WeightScaling},
+    Distance, WeightedEdge,
+};
+use crate::{
+    error::{Error, ParseError, ParseErrorKind, Result},
+    utility::get_progressbar,
+};
+
+/// Content of a fmi file.
+#[derive(Debug)]
+pub struct FmiFile {
+    /// Number of vertices of the header.
+    pub number_of_vertices: usize,
+    pub attributes: VertexAttributes,
+    pub edges: Vec<WeightedEdge>,
+    /// Self edges are not allowed and therefore not part of `edges`, this is
+    /// how many of them the file had.
+    pub number_of_self_edges: usize,
+    /// One column per metric of [`FmiOptions::additional_metrics`], the `i`th
+    /// weight of a column belongs to `edges[i]`.
+    pub additional_weights: Vec<Vec<Distance>>,
+    pub weight_report: WeightReport,
+}
+
+/// Options for reading a fmi file.
+#[derive(Clone, Debug)]
+pub struct FmiOptions {
+    /// How edge weights are derived from the edge lines.
+    pub metric: FmiMetric,
+    /// Metrics that are derived from the same edge lines in one pass, see
+    /// [`FmiFile::additional_weights`] and
+    /// [`super::multi_metric_graph::MultiMetricGraph`].
+    pub additional_metrics: Vec<FmiMetric>,
+    /// How the weights of all metrics are converted to integers.
+    pub scaling: WeightScaling,
+    /// If false, the vertex lines are skipped without being parsed and the
+    /// returned attributes are empty.
+    pub with_attributes: bool,
+}
+
+impl Default for FmiOptions {
+    fn default() -> Self {
+        FmiOptions {
+            metric: FmiMetric::Cost,
+            additional_metrics: Vec::new(),
+            scaling: WeightScaling::default(),
+            with_attributes: true,
+        }
+    }
+}
+
+/// How the weight of an edge is derived from its `cost type maxspeed` columns.
+#[derive(Clone, Debug)]
+pub enum FmiMetric {
+    /// The `cost` column.
+    Cost,
+    /// The time needed to travel the `cost` column, interpreted as length in
+    /// meters, with `maxspeed` in km/h.
+    TravelTime(TravelTimeOptions),
+}
+
+#[derive(Clone, Debug)]
+pub struct TravelTimeOptions {
+    /// Speed in km/h per road `type`, used if an edge has no positive
+    /// `maxspeed`.
+    pub default_speeds: HashMap<u32, f64>,
+    /// Speed in km/h for road types missing in `default_speeds`.
+    pub fallback_speed: f64,
+    /// Resolution of the resulting weights, e.g. 1000 for milliseconds.
+    pub units_per_second: f64,
+}
+
+impl Default for TravelTimeOptions {
+    /// Weights in milliseconds. The speed table follows the road type codes of
+    /// the fmi files exported from OpenStreetMap, e.g. 1 for motorway and 12
+    /// for residential.
+    fn default() -> Self {
+        let default_speeds = [
+            (1, 130.0), // motorway
+            (2, 100.0), // trunk
+            (3, 80.0),  // primary
+            (4, 70.0),  // secondary
+            (5, 60.0),  // tertiary
+            (6, 60.0),  // motorway_link
+            (7, 50.0),  // trunk_link
+            (8, 50.0),  // primary_link
+            (9, 50.0),  // secondary_link
+            (10, 50.0), // road
+            (11, 40.0), // unclassified
+            (12, 30.0), // residential
+            (13, 20.0), // unsurfaced
+            (14, 7.0),  // living_street
+            (15, 10.0), // service
+        ]
+        .into_iter()
+        .collect();
+
+        TravelTimeOptions {
+            default_speeds,
+            fallback_speed: 30.0,
+            units_per_second: 1000.0,
+        }
+    }
+}
+
+impl TravelTimeOptions {
+    /// Returns the travel time in `units_per_second`.
+    pub fn travel_time(&self, length: f64, road_type: u32, maxspeed: f64) -> f64 {
+        let speed = if maxspeed > 0.0 {
+            maxspeed
+        } else {
+            *self
+                .default_speeds
+                .get(&road_type)
+                .unwrap_or(&self.fallback_speed)
+        };
+
+        // km/h -> m/s
+        let meters_per_second = speed / 3.6;
+        length / meters_per_second * self.units_per_second
+    }
+}
+
+/// Reads the vertex attributes and edges of a fmi file. Self edges are
+/// dropped.
+///
+/// A fmi file starts with optional `#` comment lines, followed by the number
+/// of vertices and the number of edges. Then there is one line per vertex
+/// (`nodeID osmID lat lon elevation`) and one line per edge (`srcIDX trgIDX
+/// cost type maxspeed`).
+pub fn try_read_fmi_file(path: &Path) -> Result<FmiFile> {
+    try_read_fmi_file_with_options(path, &FmiOptions::default())
+}
+
+/// Reads a fmi file, see [`try_read_fmi_file`].
+pub fn try_read_fmi_file_with_options(path: &Path, options: &FmiOptions) -> Result<FmiFile> {
+    let file = File::open(path).map_err(|error| Error::io(path, error))?;
+    read_fmi(BufReader::new(file), path, options)
+}
+
+/// Reads only the edges of a fmi file, see [`try_read_fmi_file`].
+pub fn try_read_edges_from_fmi_file(path: &Path) -> Result<Vec<WeightedEdge>> {
+    let options = FmiOptions {
+        with_attributes: false,
+        ..FmiOptions::default()
+    };
+    Ok(try_read_fmi_file_with_options(path, &options)?.edges)
+}
+
+/// Reads a fmi file from `reader`. `path` is only used to give errors some
+/// context.
+pub fn read_fmi<R: BufRead>(reader: R, path: &Path, options: &FmiOptions) -> Result<FmiFile> {
+    let mut lines = numbered_lines(reader, path);
+    // Number of the line that was read last, used for errors at the end of file.
+    let mut line_number = 0;
+
+    let number_of_vertices =
+        next_header_value(&mut lines, &mut line_number, path, "number of vertices")?;
+    let number_of_edges = next_header_value(&mut lines, &mut line_number, path, "number of edges")?;
+
+    let pb = get_progressbar(
+        "Reading fmi file",
+        (number_of_vertices + number_of_edges) as u64,
+    );
+
+    let mut attributes = Vec::with_capacity(if options.with_attributes {
+        number_of_vertices
+    } else {
+        0
+    });
+    for found in 0..number_of_vertices {
+        let Some((vertex_line_number, line)) = lines.next().transpose()? else {
+            let kind = ParseErrorKind::VertexCountMismatch {
+                expected: number_of_vertices,
+                found,
+            };
+            return Err(eof_error(path, line_number, kind).into());
+        };
+        line_number = vertex_line_number;
+
+        if options.with_attributes {
+            let mut fields = LineFields::new(path, line_number, &line);
+            attributes.push(parse_vertex_attribute(&mut fields)?);
+        }
+        pb.inc(1);
+    }
+
+    let mut edges = Vec::with_capacity(number_of_edges);
+    let mut additional_weights = vec![Vec::new(); options.additional_metrics.len()];
+    let mut edge_weights = Vec::new();
+    let mut number_of_self_edges = 0;
+    let mut weight_report = WeightReport::default();
+    for found in 0..number_of_edges {
+        let Some((edge_line_number, line)) = lines.next().transpose()? else {
+            let kind = ParseErrorKind::EdgeCountMismatch {
+                expected: number_of_edges,
+                found,
+            };
+            return Err(eof_error(path, line_number, kind).into());
+        };
+        line_number = edge_line_number;
+
+        let mut fields = LineFields::new(path, line_number, &line);
+        let edge = parse_edge(
+            &mut fields,
+            number_of_vertices,
+            options,
+            &mut edge_weights,
+            &mut weight_report,
+        )?;
+
+        if edge.tail != edge.head {
+            edges.push(edge);
+            push_weights(&mut additional_weights, &edge_weights);
+        } else {
+            number_of_self_edges += 1;
+        }
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+
+    Ok(FmiFile {
+        number_of_vertices,
+        attributes: VertexAttributes::new(attributes),
+        edges,
+        number_of_self_edges,
+        additional_weights,
+        weight_report,
+    })
+}
+
+/// Reads a fmi file like [`try_read_fmi_file_with_options`], but parses the
+/// vertex and edge lines in parallel. Unlike [`read_fmi`], this does not
+/// stream the file, the whole file is read into memory first, see
+/// [`read_fmi_bytes_in_parallel`].
+pub fn try_read_fmi_file_in_parallel(path: &Path, options: &FmiOptions) -> Result<FmiFile> {
+    let bytes = std::fs::read(path).map_err(|error| Error::io(path, error))?;
+    read_fmi_bytes_in_parallel(&bytes, path, options)
+}
+
+/// Reads only the edges of a fmi file in parallel, see
+/// [`try_read_fmi_file_in_parallel`].
+pub fn try_read_edges_from_fmi_file_in_parallel(path: &Path) -> Result<Vec<WeightedEdge>> {
+    let options = FmiOptions {
+        with_attributes: false,
+        ..FmiOptions::default()
+    };
+    Ok(try_read_fmi_file_in_parallel(path, &options)?.edges)
+}
+
+/// Parses a fmi file that is entirely in memory like [`read_fmi`], with the
+/// same result and the same errors, but parses chunks of lines in parallel.
+pub fn read_fmi_bytes_in_parallel(
+    bytes: &[u8],
+    path: &Path,
+    options: &FmiOptions,
+) -> Result<FmiFile> {
+    let chunk_length = (bytes.len() / (8 * rayon::current_num_threads())).max(MIN_CHUNK_LENGTH);
+    read_fmi_in_chunks(bytes, path, options, chunk_length)
+}
+
+/// Chunks are at least this many bytes long, so that small files are not
+/// split needlessly.
+const MIN_CHUNK_LENGTH: usize = 1 << 20;
+
+/// Vertex attributes and edges of a chunk of lines.
+struct ParsedChunk {
+    number_of_lines: usize,
+    attributes: Vec<VertexAttribute>,
+    edges: Vec<WeightedEdge>,
+    number_of_self_edges: usize,
+    additional_weights: Vec<Vec<Distance>>,
+    weight_report: WeightReport,
+}
+
+fn read_fmi_in_chunks(
+    bytes: &[u8],
+    path: &Path,
+    options: &FmiOptions,
+    chunk_length: usize,
+) -> Result<FmiFile> {
+    // The header is short, so it is read sequentially. Reading lines from a
+    // slice consumes exactly the lines read, so `body` starts after the header.
+    let mut body = bytes;
+    let mut lines = numbered_lines(&mut body, path);
+    let mut line_number = 0;
+    let number_of_vertices =
+        next_header_value(&mut lines, &mut line_number, path, "number of vertices")?;
+    let number_of_edges = next_header_value(&mut lines, &mut line_number, path, "number of edges")?;
+    drop(lines);
+
+    let pb = get_progressbar(
+        "Reading fmi file",
+        (number_of_vertices + number_of_edges) as u64,
+    );
+
+    // 0-based index of the first line of each chunk, counted from the first
+    // line after the header.
+    let chunks = line_aligned_chunks(body, chunk_length);
+    let mut first_line_indices = Vec::with_capacity(chunks.len());
+    let mut number_of_lines = 0;
+    for chunk in chunks.iter() {
+        first_line_indices.push(number_of_lines);
+        number_of_lines += count_lines(chunk);
+    }
+
+    let parsed_chunks: Vec<Result<ParsedChunk>> = chunks
+        .par_iter()
+        .zip(first_line_indices.par_iter())
+        .map(|(chunk, &first_line_index)| {
+            let parsed_chunk = parse_chunk(
+                chunk,
+                first_line_index,
+                line_number,
+                number_of_vertices,
+                number_of_edges,
+                path,
+                options,
+            )?;
+            pb.inc(parsed_chunk.number_of_lines as u64);
+            Ok(parsed_chunk)
+        })
+        .collect();
+
+    let mut attributes = Vec::with_capacity(if options.with_attributes {
+        number_of_vertices
+    } else {
+        0
+    });
+    let mut edges = Vec::with_capacity(number_of_edges);
+    let mut additional_weights = vec![Vec::new(); options.additional_metrics.len()];
+    let mut number_of_self_edges = 0;
+    let mut weight_report = WeightReport::default();
+    // Chunks are in file order, so the first error is the one `read_fmi` would
+    // have returned.
+    for parsed_chunk in parsed_chunks {
+        let parsed_chunk = parsed_chunk?;
+        attributes.extend(parsed_chunk.attributes);
+        edges.extend(parsed_chunk.edges);
+        number_of_self_edges += parsed_chunk.number_of_self_edges;
+        for (column, chunk_column) in additional_weights
+            .iter_mut()
+            .zip(parsed_chunk.additional_weights)
+        {
+            column.extend(chunk_column);
+        }
+        weight_report.merge(&parsed_chunk.weight_report);
+    }
+    pb.finish_and_clear();
+
+    let last_line_number = line_number + number_of_lines;
+    if number_of_lines < number_of_vertices {
+        let kind = ParseErrorKind::VertexCountMismatch {
+            expected: number_of_vertices,
+            found: number_of_lines,
+        };
+        return Err(eof_error(path, last_line_number, kind).into());
+    }
+    if number_of_lines < number_of_vertices + number_of_edges {
+        let kind = ParseErrorKind::EdgeCountMismatch {
+            expected: number_of_edges,
+            found: number_of_lines - number_of_vertices,
+        };
+        return Err(eof_error(path, last_line_number, kind).into());
+    }
+
+    Ok(FmiFile {
+        number_of_vertices,
+        attributes: VertexAttributes::new(attributes),
+        edges,
+        number_of_self_edges,
+        additional_weights,
+        weight_report,
+    })
+}
+
+/// Parses the lines of `chunk`. Its first line is the line with the 0-based
+/// index `first_line_index` after the header, which ends at line
+/// `header_line_number`. Lines after the edges are ignored.
+fn parse_chunk(
+    chunk: &[u8],
+    first_line_index: usize,
+    header_line_number: usize,
+    number_of_vertices: usize,
+    number_of_edges: usize,
+    path: &Path,
+    options: &FmiOptions,
+) -> Result<ParsedChunk> {
+    let mut parsed_chunk = ParsedChunk {
+        number_of_lines: 0,
+        attributes: Vec::new(),
+        edges: Vec::new(),
+        number_of_self_edges: 0,
+        additional_weights: vec![Vec::new(); options.additional_metrics.len()],
+        weight_report: WeightReport::default(),
+    };
+    let mut edge_weights = Vec::new();
+
+    for (offset, line) in chunk.split_inclusive(|&byte| byte == b'\n').enumerate() {
+        let line_index = first_line_index + offset;
+        if line_index >= number_of_vertices + number_of_edges {
+            break;
+        }
+        let line = decode_line(line, path)?;
+        let mut fields = LineFields::new(path, header_line_number + line_index + 1, line);
+
+        if line_index < number_of_vertices {
+            if options.with_attributes {
+                let attribute = parse_vertex_attribute(&mut fields)?;
+                parsed_chunk.attributes.push(attribute);
+            }
+        } else {
+            let edge = parse_edge(
+                &mut fields,
+                number_of_vertices,
+                options,
+                &mut edge_weights,
+                &mut parsed_chunk.weight_report,
+            )?;
+            if edge.tail != edge.head {
+                parsed_chunk.edges.push(edge);
+                push_weights(&mut parsed_chunk.additional_weights, &edge_weights);
+            } else {
+                parsed_chunk.number_of_self_edges += 1;
+            }
+        }
+        parsed_chunk.number_of_lines += 1;
+    }
+
+    Ok(parsed_chunk)
+}
+
+/// Decodes a line including its line break like [`BufRead::lines`] does, with
+/// the same error for invalid UTF-8.
+fn decode_line<'a>(line: &'a [u8], path: &Path) -> Result<&'a str> {
+    let line = std::str::from_utf8(line).map_err(|_| {
+        let error = io::Error::new(
+            io::ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        );
+        Error::io(path, error)
+    })?;
+    Ok(match line.strip_suffix('\n') {
+        Some(line) => line.strip_suffix('\r').unwrap_or(line),
+        None => line,
+    })
+}
+
+/// Splits `bytes` after the first line break following every `chunk_length`
+/// bytes.
+fn line_aligned_chunks(bytes: &[u8], chunk_length: usize) -> Vec<&[u8]> {
+    let mut chunks = Vec::new();
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let end = rest
+            .get(chunk_length..)
+            .and_then(|tail| tail.iter().position(|&byte| byte == b'\n'))
+            .map_or(rest.len(), |offset| chunk_length + offset + 1);
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+/// Number of lines of `chunk` as counted by [`str::lines`].
+fn count_lines(chunk: &[u8]) -> usize {
+    let line_breaks = chunk.iter().filter(|&&byte| byte == b'\n').count();
+    match chunk.last() {
+        Some(b'\n') | None => line_breaks,
+        Some(_) => line_breaks + 1,
+    }
+}
+
+fn parse_vertex_attribute(fields: &mut LineFields) -> Result<VertexAttribute> {
+    // nodeID osmID lat lon elevation
+    fields.next_integer::<u64>("node id")?;
+    Ok(VertexAttribute {
+        osm_id: Some(fields.next_integer("osm id")?),
+        latitude: fields.next_number("latitude")?,
+        longitude: fields.next_number("longitude")?,
+        elevation: Some(fields.next_number("elevation")?),
+    })
+}
+
+/// Parses an edge line. The weights of `options.additional_metrics` are
+/// written to `additional_weights`.
+fn parse_edge(
+    fields: &mut LineFields,
+    number_of_vertices: usize,
+    options: &FmiOptions,
+    additional_weights: &mut Vec<Distance>,
+    weight_report: &mut WeightReport,
+) -> Result<WeightedEdge> {
+    // srcIDX trgIDX cost type maxspeed
+    let tail = fields.next_vertex("tail", number_of_vertices)?;
+    let head = fields.next_vertex("head", number_of_vertices)?;
+
+    let (cost_column, cost) = fields.next_field("weight")?;
+    let is_travel_time = |metric: &FmiMetric| matches!(metric, FmiMetric::TravelTime(_));
+    // The columns after the cost are only required for travel times.
+    let road = if is_travel_time(&options.metric)
+        || options.additional_metrics.iter().any(is_travel_time)
+    {
+        let length = parse_length(fields, cost_column, cost)?;
+        let road_type: u32 = fields.next_integer("type")?;
+        let maxspeed = fields.next_number("maxspeed")?;
+        Some((length, road_type, maxspeed))
+    } else {
+        None
+    };
+
+    let mut metric_weight = |metric: &FmiMetric| -> Result<Distance> {
+        match (metric, road) {
+            (FmiMetric::TravelTime(travel_time_options), Some((length, road_type, maxspeed))) => {
+                let travel_time = travel_time_options.travel_time(length, road_type, maxspeed);
+                options
+                    .scaling
+                    .convert(travel_time, weight_report)
+                    .ok_or_else(|| {
+                        let travel_time = travel_time.to_string();
+                        let kind = invalid_weight("travel time", &travel_time);
+                        fields.error(cost_column, kind).into()
+                    })
+            }
+            _ => options
+                .scaling
+                .convert_str("weight", cost, weight_report)
+                .map_err(|kind| fields.error(cost_column, kind).into()),
+        }
+    };
+
+    let weight = metric_weight(&options.metric)?;
+    additional_weights.clear();
+    for metric in options.additional_metrics.iter() {
+        additional_weights.push(metric_weight(metric)?);
+    }
+
+    Ok(WeightedEdge { tail, head, weight })
+}
+
+fn parse_length(fields: &LineFields, column: usize, cost: &str) -> Result<f64> {
+    cost.parse()
+        .map_err(|_| invalid_cost(fields, column, cost).into())
+}
+
+fn invalid_cost(fields: &LineFields, column: usize, cost: &str) -> ParseError {
+    fields.error(
+        column,
+        ParseErrorKind::InvalidNumber {
+            field: "weight",
+            value: cost.to_string(),
+        },
+    )
+}
+
+/// Appends the weights of an edge to their columns.
+fn push_weights(columns: &mut [Vec<Distance>], weights: &[Distance]) {
+    for (column, &weight) in columns.iter_mut().zip(weights) {
+        column.push(weight);
+    }
+}
+
+/// Returns the first value after the comment lines of the header.
+fn next_header_value(
+    lines: &mut impl Iterator<Item = Result<(usize, String)>>,
+    line_number: &mut usize,
+    path: &Path,
+    field: &'static str,
+) -> Result<usize> {
+    for line in lines {
+        let (header_line_number, line) = line?;
+        *line_number = header_line_number;
+
+        // skip comment and empty lines
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+
+        return Ok(LineFields::new(path, *line_number, &line).next_integer(field)?);
+    }
+
+    Err(eof_error(path, *line_number, ParseErrorKind::MissingField(field)).into())
+}
+
+/// Error for a file that ended after line `line_number`.
+fn eof_error(path: &Path, line_number: usize, kind: ParseErrorKind) -> ParseError {
+    ParseError {
+        path: path.to_path_buf(),
+        line: line_number + 1,
+        column: 1,
+        kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{read_fmi, read_fmi_in_chunks, FmiFile, FmiMetric, FmiOptions, TravelTimeOptions};
+    use crate::{
+        error::{Error, ParseErrorKind},
+        graphs::{
+            vertex_attributes::VertexAttribute,
+            weight_scaling::{Rounding, WeightReport, WeightScaling},
+            WeightedEdge,
+        },
+    };
+
+    fn read(content: &str) -> Result<FmiFile, Error> {
+        read_fmi(
+            content.as_bytes(),
+            Path::new("test.fmi"),
+            &FmiOptions::default(),
+        )
+    }
+
+    fn parse_error(content: &str) -> (usize, usize, ParseErrorKind) {
+        match read(content) {
+            Err(Error::Parse(error)) => (error.line, error.column, error.kind),
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reads_attributes_and_edges() {
+        let content = "# comment\n\n3\n3\n0 10 48.1 9.1 250\n1 11 48.2 9.2 251\n2 12 48.3 9.3 \
+                       252\n0 1 2.4 1 50\n1 2 3.6 1 50\n2 2 1 1 50\n";
+        let fmi = read(content).unwrap();
+
+        assert_eq!(
+            fmi.edges,
+            vec![WeightedEdge::new(0, 1, 2), WeightedEdge::new(1, 2, 4)]
+        );
+
+        assert_eq!(fmi.attributes.number_of_vertices(), 3);
+        assert_eq!(
+            fmi.attributes.get(1),
+            Some(&VertexAttribute {
+                osm_id: Some(11),
+                latitude: 48.2,
+                longitude: 9.2,
+                elevation: Some(251.0),
+            })
+        );
+    }
+
+    #[test]
+    fn derives_travel_times() {
+        // 100m with maxspeed 36 km/h, 100m on a residential road (30 km/h)
+        let content = "3\n2\n0 10 48.1 9.1 250\n1 11 48.2 9.2 251\n2 12 48.3 9.3 252\n0 1 100 1 \
+                       36\n1 2 100 12 0\n";
+        let options = FmiOptions {
+            metric: FmiMetric::TravelTime(TravelTimeOptions::default()),
+            with_attributes: false,
+            ..FmiOptions::default()
+        };
+        let fmi = read_fmi(content.as_bytes(), Path::new("test.fmi"), &options).unwrap();
+
+        assert_eq!(
+            fmi.edges,
+            vec![
+                WeightedEdge::new(0, 1, 10_000),
+                WeightedEdge::new(1, 2, 12_000)
+            ]
+        );
+        assert!(fmi.attributes.is_empty());
+    }
+
+    #[test]
+    fn scales_costs() {
+        let content = "3\n2\n0 10 48.1 9.1 250\n1 11 48.2 9.2 251\n2 12 48.3 9.3 252\n0 1 0.04 1 \
+                       50\n1 2 1.25 1 50\n";
+        let options = FmiOptions {
+            scaling: WeightScaling::new(10.0, Rounding::Floor).unwrap(),
+            ..FmiOptions::default()
+        };
+        let fmi = read_fmi(content.as_bytes(), Path::new("test.fmi"), &options).unwrap();
+
+        assert_eq!(
+            fmi.edges,
+            vec![WeightedEdge::new(0, 1, 0), WeightedEdge::new(1, 2, 12)]
+        );
+        assert_eq!(
+            fmi.weight_report,
+            WeightReport {
+                number_of_weights: 2,
+                rounded_to_zero: 1,
+                lost_precision: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn reports_location_of_bad_fields() {
+        let header = "2\n1\n0 10 48.1 9.1 250\n1 11 48.2 9.2 251\n";
+
+        let (line, column, kind) = parse_error(&format!("{}0 x 1 1 50\n", header));
+        assert_eq!((line, column), (5, 3));
+        assert!(matches!(
+            kind,
+            ParseErrorKind::InvalidInteger { field: "head", .. }
+        ));
+
+        let (line, column, kind) = parse_error(&format!("{}0 1\n", header));
+        assert_eq!((line, column), (5, 4));
+        assert_eq!(kind, ParseErrorKind::MissingField("weight"));
+
+        let (line, column, kind) = parse_error(&format!("{}0 7 1 1 50\n", header));
+        assert_eq!((line, column), (5, 3));
+        assert_eq!(
+            kind,
+            ParseErrorKind::VertexOutOfRange {
+                vertex: 7,
+                number_of_vertices: 2
+            }
+        );
+
+        let (line, _column, kind) = parse_error(header);
+        assert_eq!(line, 5);
+        assert_eq!(
+            kind,
+            ParseErrorKind::EdgeCountMismatch {
+                expected: 1,
+                found: 0
+            }
+        );
+    }
+
+    #[test]
+    fn parallel_parser_matches_sequential_parser() {
+        let header = "# comment\n\n3\n3\n0 10 48.1 9.1 250\n1 11 48.2 9.2 251\n2 12 48.3 9.3 252\n";
+        let contents = [
+            format!("{}0 1 2.4 1 50\n1 2 3.6 1 50\n2 2 1 1 50\n", header).into_bytes(),
+            format!(
+                "{}0 1 2.4 1 50\r\n1 2 3.6 1 50\n2 0 1 1 50\ntrailing",
+                header
+            )
+            .into_bytes(),
+            format!("{}0 1 2.4 1 50\n1 x 3.6 1 50\n2 9 1 1 50\n", header).into_bytes(),
+            format!("{}0 1 2.4 1 50\n", header).into_bytes(),
+            b"2\n1\n0 10 48.1 9.1 250\n".to_vec(),
+            // Invalid UTF-8 after the edges is never read, within them it is an
+            // error.
+            [
+                header.as_bytes(),
+                b"0 1 2.4 1 50\n1 2 3.6 1 50\n2 0 1 1 50\n\xff\n",
+            ]
+            .concat(),
+            [
+                header.as_bytes(),
+                b"0 1 2.4 1 50\n1 2 \xff 1 50\n2 0 1 1 50\n",
+            ]
+            .concat(),
+        ];
+
+        for content in contents {
+            let expected = read_fmi(
+                content.as_slice(),
+                Path::new("test.fmi"),
+                &FmiOptions::default(),
+            );
+            for chunk_length in [1, 7, 1 << 20] {
+                let found = read_fmi_in_chunks(
+                    &content,
+                    Path::new("test.fmi"),
+                    &FmiOptions::default(),
+                    chunk_length,
+                );
+                match (&expected, &found) {
+                    (Ok(expected), Ok(found)) => {
+                        assert_eq!(expected.edges, found.edges);
+                        assert_eq!(expected.attributes, found.attributes);
+                        assert_eq!(expected.weight_report, found.weight_report);
+                    }
+                    (Err(expected), Err(found)) => {
+                        assert_eq!(expected.to_string(), found.to_string())
+                    }
+                    _ => panic!("expected {:?}, found {:?}", expected, found),
+                }
+            }
+        }
+    }
+}
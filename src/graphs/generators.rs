@@ -0,0 +1,538 @@
+//! Synthetic graphs of any size, e.g. for tests and benchmarks that should
+//! not depend on fixture files.
+//!
+//! Every generator places its vertices in the unit square, stored as latitude
+//! and longitude of the returned [`AttributedGraph`], and is deterministic for
+//! a given [`GeneratorOptions::seed`]. Invalid options, e.g. a probability
+//! outside of `0..=1`, are rejected before anything is generated.
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use super::{
+    reversible_graph::ReversibleGraph,
+    vertex_attributes::{AttributedGraph, VertexAttribute, VertexAttributes},
+    Distance, GraphFromEdges, Vertex, WeightedEdge,
+};
+#[cfg(test)]
+use super::{vec_vec_graph::VecVecGraph, Graph};
+use crate::error::{Error, Result};
+#[cfg(test)]
+use crate::search::{path::ShortestPathTestCase, PathFinding};
+
+/// Maximal displacement of the vertices of [`road_like`] in grid spacings.
+const ROAD_DISPLACEMENT: f64 = 0.25;
+/// Share of the non tree edges of [`road_like`] that are kept.
+const ROAD_EDGE_PROBABILITY: f64 = 0.6;
+/// Probability of a diagonal road through a cell of [`road_like`].
+const ROAD_DIAGONAL_PROBABILITY: f64 = 0.1;
+/// Every this many rows and columns of [`road_like`] are arterial roads.
+const ARTERIAL_SPACING: usize = 8;
+/// Arterial roads are this many times cheaper than other roads.
+const ARTERIAL_SPEEDUP: Distance = 3;
+
+/// How the weight of a generated edge is chosen.
+#[derive(Clone, Debug)]
+pub enum WeightDistribution {
+    Constant(Distance),
+    /// Uniformly distributed in `min..=max`.
+    Uniform {
+        min: Distance,
+        max: Distance,
+    },
+    /// The euclidean distance of the endpoints times `factor`, rounded, at
+    /// least 1 and below `Distance::MAX`.
+    Euclidean {
+        factor: f64,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct GeneratorOptions {
+    /// If true, every generated connection becomes an edge in a random
+    /// direction. Otherwise it becomes a pair of opposite edges with the same
+    /// weight.
+    pub directed: bool,
+    pub weights: WeightDistribution,
+    pub seed: u64,
+}
+
+impl GeneratorOptions {
+    /// Checks that every weight of [`GeneratorOptions::weights`] can be drawn
+    /// and is below `Distance::MAX`, which searches use as infinity.
+    pub fn validate(&self) -> Result<()> {
+        match self.weights {
+            WeightDistribution::Constant(weight) if weight == Distance::MAX => Err(
+                Error::invalid_option("weights", "the weight must be below Distance::MAX"),
+            ),
+            WeightDistribution::Uniform { min, max } if min > max => Err(Error::invalid_option(
+                "weights",
+                format!("min {} is larger than max {}", min, max),
+            )),
+            WeightDistribution::Uniform { max, .. } if max == Distance::MAX => Err(
+                Error::invalid_option("weights", "max must be below Distance::MAX"),
+            ),
+            WeightDistribution::Euclidean { factor } if !(factor.is_finite() && factor > 0.0) => {
+                Err(Error::invalid_option(
+                    "weights",
+                    format!("factor {} is not positive and finite", factor),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        GeneratorOptions {
+            directed: false,
+            weights: WeightDistribution::Uniform { min: 1, max: 100 },
+            seed: 0,
+        }
+    }
+}
+
+/// A grid of `rows` times `columns` vertices, each connected to its up to four
+/// neighbors. Vertex `row * columns + column` is at (`row`, `column`).
+pub fn grid<G: GraphFromEdges>(
+    rows: usize,
+    columns: usize,
+    options: &GeneratorOptions,
+) -> Result<AttributedGraph<G>> {
+    let mut generator = Generator::new(options, rows.checked_mul(columns))?;
+    generator.coordinates = grid_coordinates(rows, columns);
+    for (tail, head) in grid_connections(rows, columns) {
+        generator.connect(tail, head);
+    }
+    Ok(generator.finish())
+}
+
+/// `number_of_vertices` random points, where all points at most `radius`
+/// apart are connected. Fails if `radius` is negative or NaN.
+pub fn random_geometric<G: GraphFromEdges>(
+    number_of_vertices: usize,
+    radius: f64,
+    options: &GeneratorOptions,
+) -> Result<AttributedGraph<G>> {
+    if radius.is_nan() || radius < 0.0 {
+        return Err(Error::invalid_option(
+            "radius",
+            format!("{} is negative or NaN", radius),
+        ));
+    }
+    let mut generator = Generator::new(options, Some(number_of_vertices))?;
+    generator.coordinates = (0..number_of_vertices)
+        .map(|_| (generator.rng.gen(), generator.rng.gen()))
+        .collect();
+
+    // Only points in the same or in neighboring cells can be close enough.
+    let cells_per_side = ((1.0 / radius).floor() as usize).clamp(1, 1 << 12);
+    let cell =
+        |coordinate: f64| ((coordinate * cells_per_side as f64) as usize).min(cells_per_side - 1);
+    let mut cells = vec![Vec::new(); cells_per_side * cells_per_side];
+    for (vertex, &(x, y)) in generator.coordinates.iter().enumerate() {
+        cells[cell(x) * cells_per_side + cell(y)].push(vertex as Vertex);
+    }
+
+    let mut connections = Vec::new();
+    for (tail, &(x, y)) in generator.coordinates.iter().enumerate() {
+        let (row, column) = (cell(x), cell(y));
+        for neighbor_row in row.saturating_sub(1)..=(row + 1).min(cells_per_side - 1) {
+            for neighbor_column in column.saturating_sub(1)..=(column + 1).min(cells_per_side - 1) {
+                for &head in cells[neighbor_row * cells_per_side + neighbor_column].iter() {
+                    if (tail as Vertex) < head
+                        && generator.euclidean_distance(tail as Vertex, head) <= radius
+                    {
+                        connections.push((tail as Vertex, head));
+                    }
+                }
+            }
+        }
+    }
+    // The cells are visited in a different order than the vertices, sort to
+    // draw the weights in a fixed order.
+    connections.sort_unstable();
+    for (tail, head) in connections {
+        generator.connect(tail, head);
+    }
+    Ok(generator.finish())
+}
+
+/// The Erdős–Rényi graph G(n, p), where every pair of vertices is connected
+/// with probability `edge_probability`. If `options.directed` is set, every
+/// ordered pair is connected independently. Takes quadratic time. The
+/// vertices are placed randomly. Fails if `edge_probability` is not in
+/// `0..=1`.
+pub fn erdos_renyi<G: GraphFromEdges>(
+    number_of_vertices: usize,
+    edge_probability: f64,
+    options: &GeneratorOptions,
+) -> Result<AttributedGraph<G>> {
+    if !(0.0..=1.0).contains(&edge_probability) {
+        return Err(Error::invalid_option(
+            "edge probability",
+            format!("{} is not in 0..=1", edge_probability),
+        ));
+    }
+    let mut generator = Generator::new(options, Some(number_of_vertices))?;
+    generator.coordinates = (0..number_of_vertices)
+        .map(|_| (generator.rng.gen(), generator.rng.gen()))
+        .collect();
+
+    for tail in 0..number_of_vertices as Vertex {
+        for head in 0..number_of_vertices as Vertex {
+            let is_candidate = if options.directed {
+                tail != head
+            } else {
+                tail < head
+            };
+            if is_candidate && generator.rng.gen_bool(edge_probability) {
+                generator.add_connection(tail, head);
+            }
+        }
+    }
+    Ok(generator.finish())
+}
+
+/// A planar graph that resembles a road network: a grid of `rows` times
+/// `columns` slightly displaced vertices that is thinned out but stays
+/// connected, with some diagonal roads and faster arterial roads along every
+/// eighth row and column.
+pub fn road_like<G: GraphFromEdges>(
+    rows: usize,
+    columns: usize,
+    options: &GeneratorOptions,
+) -> Result<AttributedGraph<G>> {
+    let mut generator = Generator::new(options, rows.checked_mul(columns))?;
+    // Shrink the grid to leave room for the displacement.
+    let shift = ROAD_DISPLACEMENT * grid_spacing(rows, columns);
+    let scale = 1.0 - 2.0 * shift;
+    generator.coordinates = grid_coordinates(rows, columns)
+        .into_iter()
+        .map(|(x, y)| {
+            (
+                shift + scale * x + generator.rng.gen_range(-shift..=shift),
+                shift + scale * y + generator.rng.gen_range(-shift..=shift),
+            )
+        })
+        .collect();
+
+    // A random spanning tree keeps the graph connected.
+    let mut connections = grid_connections(rows, columns);
+    connections.shuffle(&mut generator.rng);
+    let mut components = (0..(rows * columns) as Vertex).collect::<Vec<_>>();
+    let mut roads = Vec::new();
+    for (tail, head) in connections {
+        let tail_root = find_root(&mut components, tail);
+        let head_root = find_root(&mut components, head);
+        if tail_root != head_root {
+            components[tail_root as usize] = head_root;
+            roads.push((tail, head));
+        } else if generator.rng.gen_bool(ROAD_EDGE_PROBABILITY) {
+            roads.push((tail, head));
+        }
+    }
+
+    // At most one diagonal per cell keeps the graph planar.
+    for row in 0..rows.saturating_sub(1) {
+        for column in 0..columns.saturating_sub(1) {
+            if generator.rng.gen_bool(ROAD_DIAGONAL_PROBABILITY) {
+                let vertex = (row * columns + column) as Vertex;
+                roads.push(if generator.rng.gen() {
+                    (vertex, vertex + columns as Vertex + 1)
+                } else {
+                    (vertex + 1, vertex + columns as Vertex)
+                });
+            }
+        }
+    }
+
+    roads.sort_unstable();
+    let position = |vertex: Vertex| (vertex as usize / columns, vertex as usize % columns);
+    for (tail, head) in roads {
+        let ((tail_row, tail_column), (head_row, head_column)) = (position(tail), position(head));
+        let is_arterial = (tail_row == head_row && tail_row % ARTERIAL_SPACING == 0)
+            || (tail_column == head_column && tail_column % ARTERIAL_SPACING == 0);
+        let speedup = if is_arterial { ARTERIAL_SPEEDUP } else { 1 };
+        let weight = generator.weight(tail, head).div_ceil(speedup);
+        generator.add_edges(tail, head, weight);
+    }
+    Ok(generator.finish())
+}
+
+/// Collects the vertices and edges of a generated graph.
+struct Generator<'a> {
+    options: &'a GeneratorOptions,
+    rng: StdRng,
+    /// The coordinates of every vertex in the unit square.
+    coordinates: Vec<(f64, f64)>,
+    edges: Vec<WeightedEdge>,
+}
+
+impl<'a> Generator<'a> {
+    /// Fails if the options are invalid or if there are more vertices than
+    /// [`Vertex`] can number, where `None` means that their number overflows.
+    fn new(options: &'a GeneratorOptions, number_of_vertices: Option<usize>) -> Result<Self> {
+        options.validate()?;
+        if number_of_vertices
+            .is_none_or(|number_of_vertices| number_of_vertices > Vertex::MAX as usize)
+        {
+            return Err(Error::invalid_option(
+                "number of vertices",
+                "there are more vertices than vertex ids",
+            ));
+        }
+
+        Ok(Generator {
+            options,
+            rng: StdRng::seed_from_u64(options.seed),
+            coordinates: Vec::new(),
+            edges: Vec::new(),
+        })
+    }
+
+    fn euclidean_distance(&self, tail: Vertex, head: Vertex) -> f64 {
+        let (tail_x, tail_y) = self.coordinates[tail as usize];
+        let (head_x, head_y) = self.coordinates[head as usize];
+        (tail_x - head_x).hypot(tail_y - head_y)
+    }
+
+    fn weight(&mut self, tail: Vertex, head: Vertex) -> Distance {
+        match self.options.weights {
+            WeightDistribution::Constant(weight) => weight,
+            WeightDistribution::Uniform { min, max } => self.rng.gen_range(min..=max),
+            WeightDistribution::Euclidean { factor } => {
+                // The cast saturates, so huge factors would give `Distance::MAX`.
+                ((self.euclidean_distance(tail, head) * factor).round() as Distance)
+                    .clamp(1, Distance::MAX - 1)
+            }
+        }
+    }
+
+    /// Connects `tail` and `head` as set by [`GeneratorOptions::directed`].
+    fn connect(&mut self, tail: Vertex, head: Vertex) {
+        let weight = self.weight(tail, head);
+        self.add_edges(tail, head, weight);
+    }
+
+    /// Like [`Generator::connect`], but keeps the direction of directed
+    /// connections.
+    fn add_connection(&mut self, tail: Vertex, head: Vertex) {
+        let weight = self.weight(tail, head);
+        if self.options.directed {
+            self.edges.push(WeightedEdge::new(tail, head, weight));
+        } else {
+            self.add_edges(tail, head, weight);
+        }
+    }
+
+    fn add_edges(&mut self, tail: Vertex, head: Vertex, weight: Distance) {
+        if !self.options.directed {
+            self.edges.push(WeightedEdge::new(tail, head, weight));
+            self.edges.push(WeightedEdge::new(head, tail, weight));
+        } else if self.rng.gen() {
+            self.edges.push(WeightedEdge::new(tail, head, weight));
+        } else {
+            self.edges.push(WeightedEdge::new(head, tail, weight));
+        }
+    }
+
+    fn finish<G: GraphFromEdges>(self) -> AttributedGraph<G> {
+        let attributes = self
+            .coordinates
+            .iter()
+            .map(|&(x, y)| VertexAttribute::new(x, y))
+            .collect();
+
+        AttributedGraph {
+            graph: ReversibleGraph::with_edges(self.coordinates.len() as Vertex, &self.edges),
+            attributes: VertexAttributes::new(attributes),
+        }
+    }
+}
+
+/// The distance of neighboring vertices of a grid that spans the unit square.
+fn grid_spacing(rows: usize, columns: usize) -> f64 {
+    1.0 / (rows.max(columns).max(2) - 1) as f64
+}
+
+/// The coordinates of a grid that spans the unit square.
+fn grid_coordinates(rows: usize, columns: usize) -> Vec<(f64, f64)> {
+    let spacing = grid_spacing(rows, columns);
+    (0..rows * columns)
+        .map(|vertex| {
+            (
+                (vertex / columns) as f64 * spacing,
+                (vertex % columns) as f64 * spacing,
+            )
+        })
+        .collect()
+}
+
+/// The pairs of horizontally and vertically neighboring grid vertices.
+fn grid_connections(rows: usize, columns: usize) -> Vec<(Vertex, Vertex)> {
+    let mut connections = Vec::new();
+    for row in 0..rows {
+        for column in 0..columns {
+            let vertex = (row * columns + column) as Vertex;
+            if column + 1 < columns {
+                connections.push((vertex, vertex + 1));
+            }
+            if row + 1 < rows {
+                connections.push((vertex, vertex + columns as Vertex));
+            }
+        }
+    }
+    connections
+}
+
+/// Union find lookup with path halving.
+fn find_root(parents: &mut [Vertex], mut vertex: Vertex) -> Vertex {
+    while parents[vertex as usize] != vertex {
+        parents[vertex as usize] = parents[parents[vertex as usize] as usize];
+        vertex = parents[vertex as usize];
+    }
+    vertex
+}
+
+/// Returns generated graphs of every kind, directed and undirected, with
+/// random test cases whose distances come from Dijkstra. The fixture free
+/// counterpart of [`super::large_test_graph`].
+#[cfg(test)]
+pub(crate) fn generated_test_graphs(
+) -> Vec<(ReversibleGraph<VecVecGraph>, Vec<ShortestPathTestCase>)> {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let options = GeneratorOptions::default();
+    let directed = GeneratorOptions {
+        directed: true,
+        weights: WeightDistribution::Euclidean { factor: 1000.0 },
+        seed: 1,
+    };
+    let graphs: [AttributedGraph<VecVecGraph>; 5] = [
+        grid(6, 7, &options).unwrap(),
+        random_geometric(60, 0.25, &options).unwrap(),
+        erdos_renyi(40, 0.1, &directed).unwrap(),
+        road_like(9, 9, &options).unwrap(),
+        road_like(9, 9, &directed).unwrap(),
+    ];
+
+    let mut rng = StdRng::seed_from_u64(0);
+    graphs
+        .into_iter()
+        .map(|graph| {
+            let graph = graph.graph;
+            let number_of_vertices = Graph::number_of_vertices(graph.out_graph());
+            let test_cases = (0..50)
+                .map(|_| {
+                    let source = rng.gen_range(0..number_of_vertices);
+                    let target = rng.gen_range(0..number_of_vertices);
+                    ShortestPathTestCase {
+                        source,
+                        target,
+                        distance: graph.out_graph().shortest_path_distance(source, target),
+                    }
+                })
+                .collect();
+            (graph, test_cases)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        erdos_renyi, grid, random_geometric, road_like, GeneratorOptions, WeightDistribution,
+    };
+    use crate::graphs::{
+        csr_graph::CsrGraph, vec_vec_graph::VecVecGraph, vertex_attributes::AttributedGraph,
+        Distance, Graph, WeightedEdge,
+    };
+
+    fn edges(graph: &AttributedGraph<VecVecGraph>) -> Vec<WeightedEdge> {
+        let graph = graph.graph.out_graph();
+        graph
+            .vertices()
+            .flat_map(|vertex| graph.edges(vertex))
+            .collect()
+    }
+
+    #[test]
+    fn generators_are_deterministic() {
+        let options = GeneratorOptions::default();
+        let directed = GeneratorOptions {
+            directed: true,
+            weights: WeightDistribution::Euclidean { factor: 1000.0 },
+            seed: 1,
+        };
+
+        let grid_graph = grid(6, 7, &options).unwrap();
+        assert_eq!(edges(&grid_graph).len(), 2 * (6 * 6 + 5 * 7));
+        assert_eq!(edges(&grid(6, 7, &options).unwrap()), edges(&grid_graph));
+        assert_eq!(
+            edges(&road_like(9, 9, &directed).unwrap()),
+            edges(&road_like(9, 9, &directed).unwrap())
+        );
+        assert_eq!(
+            edges(&random_geometric(60, 0.25, &options).unwrap()),
+            edges(&random_geometric(60, 0.25, &options).unwrap())
+        );
+        assert!(grid_graph
+            .attributes
+            .iter()
+            .all(|attribute| (0.0..=1.0).contains(&attribute.latitude)));
+    }
+
+    #[test]
+    fn isolated_vertices_are_kept() {
+        let options = GeneratorOptions::default();
+        let graph: AttributedGraph<CsrGraph> = erdos_renyi(10, 0.0, &options).unwrap();
+        assert_eq!(Graph::number_of_vertices(graph.graph.out_graph()), 10);
+        assert_eq!(Graph::number_of_vertices(graph.graph.in_graph()), 10);
+        assert_eq!(graph.attributes.number_of_vertices(), 10);
+
+        let graph: AttributedGraph<VecVecGraph> = random_geometric(30, 0.05, &options).unwrap();
+        assert_eq!(
+            Graph::number_of_vertices(graph.graph.out_graph()),
+            graph.attributes.number_of_vertices()
+        );
+    }
+
+    #[test]
+    fn invalid_options_are_rejected() {
+        let options = GeneratorOptions::default();
+        for edge_probability in [-0.1, 1.5, f64::NAN] {
+            assert!(erdos_renyi::<VecVecGraph>(10, edge_probability, &options).is_err());
+        }
+        for radius in [-0.1, f64::NAN] {
+            assert!(random_geometric::<VecVecGraph>(10, radius, &options).is_err());
+        }
+        assert!(grid::<VecVecGraph>(usize::MAX, 2, &options).is_err());
+
+        for weights in [
+            WeightDistribution::Uniform { min: 5, max: 4 },
+            WeightDistribution::Constant(Distance::MAX),
+            WeightDistribution::Euclidean { factor: -1.0 },
+            WeightDistribution::Euclidean { factor: f64::NAN },
+        ] {
+            let options = GeneratorOptions {
+                weights,
+                ..GeneratorOptions::default()
+            };
+            assert!(options.validate().is_err());
+            assert!(road_like::<VecVecGraph>(3, 3, &options).is_err());
+        }
+    }
+
+    #[test]
+    fn euclidean_weights_stay_below_max() {
+        let options = GeneratorOptions {
+            weights: WeightDistribution::Euclidean { factor: f64::MAX },
+            ..GeneratorOptions::default()
+        };
+        let graph = grid::<VecVecGraph>(3, 3, &options).unwrap();
+        assert!(edges(&graph)
+            .iter()
+            .all(|edge| edge.weight == Distance::MAX - 1));
+    }
+}
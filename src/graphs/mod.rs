@@ -12,13 +12,30 @@ use reversible_graph::ReversibleGraph;
 use serde::{Deserialize, Serialize};
 use vec_vec_graph::VecVecGraph;
 
-use crate::{search::path::ShortestPathTestCase, utility::get_progressbar};
-
+use crate::search::path::ShortestPathTestCase;
+
+pub mod binary_graph;
+pub mod csr_graph;
+pub mod dimacs;
+pub mod edge_based_graph;
+pub mod edge_list;
+pub mod fmi;
+pub mod generators;
+pub mod multi_metric_graph;
+mod parsing;
 pub mod pathfinding;
+pub mod permutation;
+pub mod reordering;
 pub mod reversible_graph;
+pub mod strongly_connected_components;
+pub mod subgraph;
+pub mod turn_restrictions;
+pub mod validation;
 pub mod vec_graph;
 pub mod vec_hash_graph;
 pub mod vec_vec_graph;
+pub mod vertex_attributes;
+pub mod weight_scaling;
 
 pub type Vertex = u32;
 pub type EdgeId = u32;
@@ -46,7 +63,7 @@ impl Edge {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct WeightedEdge {
     pub tail: Vertex,
     pub head: Vertex,
@@ -97,6 +114,106 @@ impl TaillessEdge {
     }
 }
 
+/// Graphs that store the out edges of a vertex next to each other. Searches
+/// that are generic over this trait iterate over plain slices instead of a
+/// boxed iterator per expanded vertex.
+pub trait EdgeSlices: Send + Sync {
+    /// Returns the out edges of `tail`, sorted by head. The slice is empty if
+    /// `tail` is out of range.
+    fn edge_slice(&self, tail: Vertex) -> &[TaillessEdge];
+}
+
+/// Out edge access with a concrete iterator type. Searches that are generic
+/// over this trait are monomorphized per graph type, so neither the iterator
+/// nor the call to `out_edges` goes through a vtable.
+///
+/// Graphs with [`EdgeSlices`] get it for free. `dyn Graph` implements it by
+/// boxing the iterator of [`Graph::edges`], so `&dyn Graph` can still be passed
+/// wherever a `StaticGraph` is expected.
+pub trait StaticGraph: Send + Sync {
+    type Edges<'a>: Iterator<Item = WeightedEdge>
+    where
+        Self: 'a;
+
+    /// Returns the out edges of `tail`. There are none if `tail` is out of
+    /// range.
+    fn out_edges(&self, tail: Vertex) -> Self::Edges<'_>;
+}
+
+impl<G: EdgeSlices> StaticGraph for G {
+    type Edges<'a>
+        = SliceEdges<'a>
+    where
+        G: 'a;
+
+    fn out_edges(&self, tail: Vertex) -> SliceEdges<'_> {
+        SliceEdges {
+            edges: self.edge_slice(tail).iter(),
+            tail,
+        }
+    }
+}
+
+impl StaticGraph for dyn Graph + '_ {
+    type Edges<'a>
+        = Box<dyn ExactSizeIterator<Item = WeightedEdge> + Send + 'a>
+    where
+        Self: 'a;
+
+    fn out_edges(&self, tail: Vertex) -> Self::Edges<'_> {
+        if tail < self.number_of_vertices() {
+            self.edges(tail)
+        } else {
+            Box::new(std::iter::empty())
+        }
+    }
+}
+
+/// Out edges of a vertex of a graph with [`EdgeSlices`].
+pub struct SliceEdges<'a> {
+    edges: std::slice::Iter<'a, TaillessEdge>,
+    tail: Vertex,
+}
+
+impl Iterator for SliceEdges<'_> {
+    type Item = WeightedEdge;
+
+    fn next(&mut self) -> Option<WeightedEdge> {
+        self.edges.next().map(|edge| edge.set_tail(self.tail))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edges.size_hint()
+    }
+}
+
+impl ExactSizeIterator for SliceEdges<'_> {}
+
+/// Graphs that can be built from all their edges at once, e.g. when loading
+/// them from a file. Unlike inserting the edges with [`Graph::set_weight`],
+/// this also works for read only graphs.
+pub trait GraphFromEdges: Graph + Sized {
+    /// Builds a graph with exactly `number_of_vertices` vertices, including
+    /// isolated ones. Of parallel edges, the one with the smallest weight is
+    /// kept. Edges of weight `Distance::MAX` are dropped, like
+    /// [`ReversibleGraph::from_edges`] does. Panics if an edge points to a
+    /// vertex out of range.
+    fn with_edges(number_of_vertices: u32, edges: &[WeightedEdge]) -> Self;
+}
+
+/// Returns one more than the largest vertex of an edge with a weight below
+/// `Distance::MAX`, the number of vertices [`ReversibleGraph::from_edges`]
+/// builds. All loaders of edge files size their graphs this way, so graphs read
+/// from the same file have the same fingerprint.
+pub fn number_of_vertices_of(edges: &[WeightedEdge]) -> u32 {
+    edges
+        .iter()
+        .filter(|edge| edge.weight < Distance::MAX)
+        .map(|edge| std::cmp::max(edge.tail, edge.head) + 1)
+        .max()
+        .unwrap_or(0)
+}
+
 pub trait Graph: Send + Sync {
     fn number_of_vertices(&self) -> u32;
 
@@ -153,8 +270,9 @@ pub trait Graph: Send + Sync {
     fn get_path_distance(&self, path: &Vec<Vertex>) -> Option<Distance> {
         path.iter()
             .tuple_windows()
-            .map(|(&tail, &head)| self.get_weight(&Edge { tail, head }))
-            .sum()
+            .try_fold(0, |distance: Distance, (&tail, &head)| {
+                Some(distance.saturating_add(self.get_weight(&Edge { tail, head })?))
+            })
     }
 
     fn build_graph(&mut self, edges: &Vec<WeightedEdge>) {
@@ -201,58 +319,21 @@ pub trait Graph: Send + Sync {
     }
 }
 
+/// Reads the edges of a fmi file. Panics if the file can not be read, see
+/// [`fmi::try_read_edges_from_fmi_file`] for a fallible version.
 pub fn read_edges_from_fmi_file(file: &Path) -> Vec<WeightedEdge> {
-    let file = File::open(file).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut lines = reader.lines();
-
-    // skip comment lines
-    while let Some(next_line) = lines.next() {
-        if !next_line.unwrap().starts_with('#') {
-            break;
-        }
-    }
-
-    let number_of_vertices: usize = lines.next().unwrap().unwrap().parse().unwrap();
-    let number_of_edges: usize = lines.next().unwrap().unwrap().parse().unwrap();
-
-    lines
-        .progress_with(get_progressbar(
-            "Reading fmi file",
-            (number_of_vertices + number_of_edges) as u64,
-        ))
-        .skip(number_of_vertices)
-        .take(number_of_edges)
-        .filter_map(|edge_line| {
-            // srcIDX trgIDX cost type maxspeed
-            let line = edge_line.unwrap();
-            let mut values = line.split_whitespace();
-            let tail: Vertex = values
-                .next()
-                .unwrap_or_else(|| panic!("no tail found in line {}", line))
-                .parse()
-                .unwrap_or_else(|_| panic!("unable to parse tail in line {}", line));
-            let head: Vertex = values
-                .next()
-                .unwrap_or_else(|| panic!("no head found in line {}", line))
-                .parse()
-                .unwrap_or_else(|_| panic!("unable to parse head in line {}", line));
-            let weight: Distance = values
-    .next()
-    .unwrap_or_else(|| panic!("no weight found in line {}", line))
-    .parse::<f64>()                                       // parse as float
-    .unwrap_or_else(|_| panic!("unable to parse weight in line {}", line))
-    .round()                                              // round to nearest
-    as Distance;
-            if tail == head {
-                return None;
-            }
-            Some(WeightedEdge { tail, head, weight })
-        })
-        .collect()
+    fmi::try_read_edges_from_fmi_file(file).unwrap_or_else(|error| panic!("{}", error))
 }
 
+/// Reads the arcs of a DIMACS `.gr` file. Panics if the file can not be read.
+///
+/// The 1-based DIMACS ids are returned unchanged, so vertex `v` of the file is
+/// vertex `v` of the edges and vertex 0 has no edges. The header and all lines
+/// other than arcs are ignored. [`dimacs::try_read_gr_file`] checks the file
+/// and converts the ids to 0-based vertices, like the rest of the crate.
+#[deprecated(
+    note = "use `dimacs::try_read_gr_file`, which checks the file and returns 0-based vertices"
+)]
 pub fn read_edges_from_gr_file(path: &Path) -> Vec<WeightedEdge> {
     let file = File::open(path).unwrap();
     let reader = BufReader::new(file);
@@ -260,7 +341,6 @@ pub fn read_edges_from_gr_file(path: &Path) -> Vec<WeightedEdge> {
     reader
         .lines()
         .filter_map(|edge_line| {
-            // srcIDX trgIDX cost type maxspeed
             let line = edge_line.unwrap();
             let mut values = line.split_whitespace();
             let line_type = values.next().unwrap();
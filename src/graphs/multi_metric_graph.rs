@@ -0,0 +1,385 @@
+//! Several metrics, e.g. distance, travel time and a custom cost, on one
+//! topology.
+//!
+//! The edges are stored once, with one weight column per metric. A metric is
+//! picked by its index with [`MultiMetricGraph::metric`], which is a
+//! [`Graph`] of its own, so Dijkstra and everything else that runs on a graph
+//! can be used on it directly. [`MultiMetricGraph::reversible_metric`] adds the
+//! in edges, which share the weight columns as well, so hub labels and
+//! contraction hierarchies can be built per metric without copying the graph,
+//! e.g. with [`crate::search::ch::contracted_graph::ContractedGraph::by_brute_force`].
+//! Contraction with witness searches inserts shortcuts and works on one
+//! modifiable copy, see
+//! [`crate::search::ch::contracted_graph::ContractedGraph::with_dijkstra_witness_search_read_only`].
+//!
+//! The fmi and the edge list readers fill several metrics in one pass, see
+//! [`super::fmi::FmiOptions::additional_metrics`] and
+//! [`super::edge_list::EdgeListOptions::additional_weight_columns`]. The
+//! `ch_metric` binary contracts the metric picked by its name.
+//!
+//! This is a graph type of its own instead of weight columns in
+//! [`super::vec_vec_graph::VecVecGraph`] and [`super::vec_graph::VecGraph`]:
+//! their edges are [`super::TaillessEdge`]s with a single weight, which every
+//! search and the contraction rely on. A [`Metric`] provides such edges for one
+//! metric, so the existing algorithms run on it unchanged.
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    reversible_graph::ReversibleGraph, Distance, Edge, Graph, StaticGraph, Vertex, WeightedEdge,
+};
+
+/// A read only graph with one weight per edge and metric.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MultiMetricGraph {
+    metric_names: Vec<String>,
+    /// The out edges of `v` are `offsets[v]..offsets[v + 1]`.
+    offsets: Vec<usize>,
+    /// Sorted per tail.
+    heads: Vec<Vertex>,
+    /// The in edges of `v` are `in_offsets[v]..in_offsets[v + 1]`.
+    in_offsets: Vec<usize>,
+    /// Sorted per head.
+    tails: Vec<Vertex>,
+    /// The index of each in edge among the out edges, where its weights are.
+    in_edge_indices: Vec<usize>,
+    /// `weights[metric][edge]`, in the order of the out edges.
+    weights: Vec<Vec<Distance>>,
+}
+
+impl MultiMetricGraph {
+    /// Builds a graph with the metrics `metric_names`. The weights of the
+    /// first metric are the weights of `edges`, the weights of the other
+    /// metrics are given column wise in `additional_weights`, as returned by
+    /// the readers. Of parallel edges, the smallest weight of each metric is
+    /// kept.
+    ///
+    /// Panics if the number of names or the length of a column does not match.
+    pub fn new(
+        metric_names: Vec<String>,
+        edges: &[WeightedEdge],
+        additional_weights: &[Vec<Distance>],
+    ) -> Self {
+        assert_eq!(
+            metric_names.len(),
+            additional_weights.len() + 1,
+            "every metric needs a name"
+        );
+        assert!(
+            additional_weights
+                .iter()
+                .all(|column| column.len() == edges.len()),
+            "every metric needs a weight per edge"
+        );
+
+        let mut order = (0..edges.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&index| (edges[index].tail, edges[index].head));
+
+        let number_of_vertices = edges
+            .iter()
+            .map(|edge| edge.tail.max(edge.head) as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let column = |metric: usize, index: usize| match metric {
+            0 => edges[index].weight,
+            _ => additional_weights[metric - 1][index],
+        };
+
+        let mut offsets = vec![0; number_of_vertices + 1];
+        let mut out_edges = Vec::with_capacity(edges.len());
+        let mut weights: Vec<Vec<Distance>> =
+            vec![Vec::with_capacity(edges.len()); metric_names.len()];
+        let mut last_edge = None;
+        for index in order {
+            let edge = &edges[index];
+            if last_edge == Some((edge.tail, edge.head)) {
+                for (metric, metric_weights) in weights.iter_mut().enumerate() {
+                    let weight = metric_weights.last_mut().unwrap();
+                    *weight = (*weight).min(column(metric, index));
+                }
+                continue;
+            }
+            last_edge = Some((edge.tail, edge.head));
+
+            offsets[edge.tail as usize + 1] += 1;
+            out_edges.push((edge.tail, edge.head));
+            for (metric, metric_weights) in weights.iter_mut().enumerate() {
+                metric_weights.push(column(metric, index));
+            }
+        }
+        accumulate(&mut offsets);
+
+        let mut in_edge_indices = (0..out_edges.len()).collect::<Vec<_>>();
+        in_edge_indices.sort_unstable_by_key(|&index| {
+            let (tail, head) = out_edges[index];
+            (head, tail)
+        });
+        let mut in_offsets = vec![0; number_of_vertices + 1];
+        for &(_, head) in out_edges.iter() {
+            in_offsets[head as usize + 1] += 1;
+        }
+        accumulate(&mut in_offsets);
+
+        MultiMetricGraph {
+            metric_names,
+            offsets,
+            heads: out_edges.iter().map(|&(_, head)| head).collect(),
+            in_offsets,
+            tails: in_edge_indices
+                .iter()
+                .map(|&index| out_edges[index].0)
+                .collect(),
+            in_edge_indices,
+            weights,
+        }
+    }
+
+    pub fn number_of_metrics(&self) -> usize {
+        self.metric_names.len()
+    }
+
+    pub fn metric_names(&self) -> &[String] {
+        &self.metric_names
+    }
+
+    /// Returns the index of the metric called `name`.
+    pub fn metric_index(&self, name: &str) -> Option<usize> {
+        self.metric_names
+            .iter()
+            .position(|metric_name| metric_name == name)
+    }
+
+    pub fn number_of_vertices(&self) -> u32 {
+        self.offsets.len().saturating_sub(1) as u32
+    }
+
+    pub fn number_of_edges(&self) -> usize {
+        self.heads.len()
+    }
+
+    /// Returns the graph with the weights of metric `index`.
+    ///
+    /// Panics if there is no such metric.
+    pub fn metric(&self, index: usize) -> Metric<'_> {
+        Metric {
+            offsets: &self.offsets,
+            heads: &self.heads,
+            weight_indices: None,
+            weights: &self.weights[index],
+        }
+    }
+
+    /// Returns the graph with the weights of metric `index` together with its
+    /// in edges, e.g. to build a contraction hierarchy for this metric. Nothing
+    /// is copied.
+    ///
+    /// Panics if there is no such metric.
+    pub fn reversible_metric(&self, index: usize) -> ReversibleGraph<Metric<'_>> {
+        let in_graph = Metric {
+            offsets: &self.in_offsets,
+            heads: &self.tails,
+            weight_indices: Some(&self.in_edge_indices),
+            weights: &self.weights[index],
+        };
+        ReversibleGraph::from_out_and_in_graph(self.metric(index), in_graph)
+    }
+}
+
+/// Turns the number of edges per vertex into offsets.
+fn accumulate(offsets: &mut [usize]) {
+    for vertex in 1..offsets.len() {
+        offsets[vertex] += offsets[vertex - 1];
+    }
+}
+
+/// The out or in edges of one metric of a [`MultiMetricGraph`].
+#[derive(Clone, Copy)]
+pub struct Metric<'a> {
+    offsets: &'a [usize],
+    heads: &'a [Vertex],
+    /// The index of the weight of each edge, `None` if the weights are in the
+    /// order of the edges.
+    weight_indices: Option<&'a [usize]>,
+    weights: &'a [Distance],
+}
+
+impl<'a> Metric<'a> {
+    fn edge_range(&self, tail: Vertex) -> std::ops::Range<usize> {
+        match (
+            self.offsets.get(tail as usize),
+            self.offsets.get(tail as usize + 1),
+        ) {
+            (Some(&start), Some(&stop)) => start..stop,
+            _ => 0..0,
+        }
+    }
+
+    fn weight(&self, edge_index: usize) -> Distance {
+        match self.weight_indices {
+            Some(weight_indices) => self.weights[weight_indices[edge_index]],
+            None => self.weights[edge_index],
+        }
+    }
+}
+
+impl Graph for Metric<'_> {
+    fn number_of_vertices(&self) -> u32 {
+        self.offsets.len().saturating_sub(1) as u32
+    }
+
+    fn edges(&self, tail: Vertex) -> Box<dyn ExactSizeIterator<Item = WeightedEdge> + Send + '_> {
+        Box::new(self.out_edges(tail))
+    }
+
+    fn get_weight(&self, edge: &Edge) -> Option<Distance> {
+        let range = self.edge_range(edge.tail);
+        let start = range.start;
+        self.heads[range]
+            .binary_search(&edge.head)
+            .ok()
+            .map(|index| self.weight(start + index))
+    }
+
+    fn set_weight(&mut self, _edge: &Edge, _weight: Option<Distance>) {
+        unimplemented!("This is a read only graph");
+    }
+}
+
+impl StaticGraph for Metric<'_> {
+    type Edges<'b>
+        = MetricEdges<'b>
+    where
+        Self: 'b;
+
+    fn out_edges(&self, tail: Vertex) -> MetricEdges<'_> {
+        MetricEdges {
+            metric: self,
+            edge_indices: self.edge_range(tail),
+            tail,
+        }
+    }
+}
+
+/// Out edges of a vertex of a [`Metric`].
+pub struct MetricEdges<'a> {
+    metric: &'a Metric<'a>,
+    edge_indices: std::ops::Range<usize>,
+    tail: Vertex,
+}
+
+impl Iterator for MetricEdges<'_> {
+    type Item = WeightedEdge;
+
+    fn next(&mut self) -> Option<WeightedEdge> {
+        let index = self.edge_indices.next()?;
+        Some(WeightedEdge::new(
+            self.tail,
+            self.metric.heads[index],
+            self.metric.weight(index),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edge_indices.size_hint()
+    }
+}
+
+impl ExactSizeIterator for MetricEdges<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::MultiMetricGraph;
+    use crate::{
+        graphs::{
+            edge_list::{read_edge_list, EdgeListOptions},
+            fmi::{read_fmi, FmiMetric, FmiOptions, TravelTimeOptions},
+            Edge, Graph,
+        },
+        search::{
+            ch::contracted_graph::ContractedGraph, dijkstra::dijkstra_one_to_one_distance_wrapped,
+            hl::hub_graph::HubGraph, PathFinding,
+        },
+    };
+
+    #[test]
+    fn metrics_share_the_topology() {
+        // 0 -> 1 -> 2 is short but slow, 0 -> 2 is long but fast.
+        let content = "3\n3\n0 10 48.1 9.1 250\n1 11 48.2 9.2 251\n2 12 48.3 9.3 252\n0 1 100 \
+                       12 36\n1 2 100 12 36\n0 2 500 1 120\n";
+        let options = FmiOptions {
+            additional_metrics: vec![FmiMetric::TravelTime(TravelTimeOptions::default())],
+            with_attributes: false,
+            ..FmiOptions::default()
+        };
+        let fmi = read_fmi(content.as_bytes(), Path::new("test.fmi"), &options).unwrap();
+        assert_eq!(fmi.additional_weights, vec![vec![10_000, 10_000, 15_000]]);
+
+        let graph = MultiMetricGraph::new(
+            vec!["distance".to_string(), "travel time".to_string()],
+            &fmi.edges,
+            &fmi.additional_weights,
+        );
+        assert_eq!(graph.number_of_edges(), 3);
+        let travel_time = graph.metric_index("travel time").unwrap();
+
+        assert_eq!(graph.metric(0).shortest_path_distance(0, 2), Some(200));
+        assert_eq!(
+            dijkstra_one_to_one_distance_wrapped(&graph.metric(travel_time), 0, 2),
+            Some(15_000)
+        );
+        assert_eq!(
+            graph
+                .metric(travel_time)
+                .shortest_path(0, 2)
+                .unwrap()
+                .vertices,
+            vec![0, 2]
+        );
+
+        let reversible_metric = graph.reversible_metric(travel_time);
+        assert_eq!(
+            reversible_metric
+                .in_graph()
+                .get_weight(&Edge { tail: 2, head: 0 }),
+            Some(15_000)
+        );
+        let contracted_graph =
+            ContractedGraph::with_dijkstra_witness_search_read_only(&reversible_metric, 10);
+        let level_to_vertex = contracted_graph.level_to_vertex().clone();
+        let pathfinders: [Box<dyn PathFinding>; 3] = [
+            Box::new(contracted_graph),
+            Box::new(ContractedGraph::by_brute_force(
+                &reversible_metric,
+                &level_to_vertex,
+            )),
+            Box::new(HubGraph::by_brute_force(
+                &reversible_metric,
+                &level_to_vertex,
+            )),
+        ];
+        for pathfinder in pathfinders.iter() {
+            assert_eq!(pathfinder.shortest_path_distance(0, 2), Some(15_000));
+            assert_eq!(pathfinder.shortest_path_distance(2, 0), None);
+        }
+
+        // Parallel edges keep the smallest weight of each metric.
+        let content = "tail,head,distance,cost\n0,1,5,9\n1,0,5,9\n0,1,7,2\n";
+        let options = EdgeListOptions {
+            additional_weight_columns: vec![3],
+            ..EdgeListOptions::default()
+        };
+        let edge_list =
+            read_edge_list(content.as_bytes(), Path::new("test.csv"), &options).unwrap();
+        let graph = MultiMetricGraph::new(
+            vec!["distance".to_string(), "cost".to_string()],
+            &edge_list.edges,
+            &edge_list.additional_weights,
+        );
+        let edge = Edge { tail: 0, head: 1 };
+        assert_eq!(graph.metric(0).get_weight(&edge), Some(5));
+        assert_eq!(graph.metric(1).get_weight(&edge), Some(2));
+        assert_eq!(graph.metric(1).number_of_edges(), 2);
+    }
+}
@@ -0,0 +1,142 @@
+use std::{io::BufRead, path::Path, str::FromStr};
+
+use super::{
+    weight_scaling::{WeightReport, WeightScaling},
+    Distance, Vertex,
+};
+use crate::error::{Error, ParseError, ParseErrorKind};
+
+/// Splits a line of a text file into whitespace separated fields while
+/// remembering where each field starts, so that errors can point at it.
+pub(crate) struct LineFields<'a> {
+    path: &'a Path,
+    line_number: usize,
+    line: &'a str,
+    position: usize,
+}
+
+impl<'a> LineFields<'a> {
+    /// `line_number` is 1-based.
+    pub(crate) fn new(path: &'a Path, line_number: usize, line: &'a str) -> Self {
+        LineFields {
+            path,
+            line_number,
+            line,
+            position: 0,
+        }
+    }
+
+    /// Returns the next field and its 1-based column.
+    pub(crate) fn next_field(
+        &mut self,
+        field: &'static str,
+    ) -> Result<(usize, &'a str), ParseError> {
+        let rest = &self.line[self.position..];
+        let start = match rest.find(|c: char| !c.is_whitespace()) {
+            Some(offset) => self.position + offset,
+            None => {
+                return Err(self.error(self.line.len() + 1, ParseErrorKind::MissingField(field)))
+            }
+        };
+        let end = self.line[start..]
+            .find(char::is_whitespace)
+            .map_or(self.line.len(), |offset| start + offset);
+
+        self.position = end;
+        Ok((start + 1, &self.line[start..end]))
+    }
+
+    pub(crate) fn next_integer<T: FromStr>(
+        &mut self,
+        field: &'static str,
+    ) -> Result<T, ParseError> {
+        let (column, value) = self.next_field(field)?;
+        value.parse().map_err(|_| {
+            self.error(
+                column,
+                ParseErrorKind::InvalidInteger {
+                    field,
+                    value: value.to_string(),
+                },
+            )
+        })
+    }
+
+    pub(crate) fn next_number(&mut self, field: &'static str) -> Result<f64, ParseError> {
+        let (column, value) = self.next_field(field)?;
+        value.parse().map_err(|_| {
+            self.error(
+                column,
+                ParseErrorKind::InvalidNumber {
+                    field,
+                    value: value.to_string(),
+                },
+            )
+        })
+    }
+
+    /// Parses the next field as a weight, see [`WeightScaling::convert_str`].
+    pub(crate) fn next_weight(
+        &mut self,
+        field: &'static str,
+        scaling: &WeightScaling,
+        report: &mut WeightReport,
+    ) -> Result<Distance, ParseError> {
+        let (column, value) = self.next_field(field)?;
+        scaling
+            .convert_str(field, value, report)
+            .map_err(|kind| self.error(column, kind))
+    }
+
+    /// Parses the next field as a vertex and checks that it is smaller than
+    /// `number_of_vertices`.
+    pub(crate) fn next_vertex(
+        &mut self,
+        field: &'static str,
+        number_of_vertices: usize,
+    ) -> Result<Vertex, ParseError> {
+        let (column, value) = self.next_field(field)?;
+        let vertex: Vertex = value.parse().map_err(|_| {
+            self.error(
+                column,
+                ParseErrorKind::InvalidInteger {
+                    field,
+                    value: value.to_string(),
+                },
+            )
+        })?;
+
+        if vertex as usize >= number_of_vertices {
+            return Err(self.error(
+                column,
+                ParseErrorKind::VertexOutOfRange {
+                    vertex,
+                    number_of_vertices,
+                },
+            ));
+        }
+
+        Ok(vertex)
+    }
+
+    pub(crate) fn error(&self, column: usize, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            path: self.path.to_path_buf(),
+            line: self.line_number,
+            column,
+            kind,
+        }
+    }
+}
+
+/// Iterates over the lines of `reader` together with their 1-based line
+/// number.
+pub(crate) fn numbered_lines<'a, R: BufRead + 'a>(
+    reader: R,
+    path: &'a Path,
+) -> impl Iterator<Item = Result<(usize, String), Error>> + 'a {
+    reader.lines().enumerate().map(move |(index, line)| {
+        line.map(|line| (index + 1, line))
+            .map_err(|error| Error::io(path, error))
+    })
+}
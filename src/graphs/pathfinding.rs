@@ -1,11 +1,11 @@
-use super::{reversible_graph::ReversibleGraph, Distance, Graph, Vertex};
+use super::{reversible_graph::ReversibleGraph, Distance, Graph, StaticGraph, Vertex};
 use crate::search::{
     collections::dijkstra_data::Path,
     dijkstra::{dijkstra_one_to_one_distance_wrapped, dijkstra_one_to_one_path_wrapped},
     PathFinding,
 };
 
-impl<G: Graph> PathFinding for G {
+impl<G: Graph + StaticGraph> PathFinding for G {
     fn shortest_path(&self, source: Vertex, target: Vertex) -> Option<Path> {
         dijkstra_one_to_one_path_wrapped(self, source, target)
     }
@@ -19,7 +19,7 @@ impl<G: Graph> PathFinding for G {
     }
 }
 
-impl<G: Graph> PathFinding for ReversibleGraph<G> {
+impl<G: Graph + StaticGraph> PathFinding for ReversibleGraph<G> {
     fn shortest_path(&self, source: Vertex, target: Vertex) -> Option<Path> {
         self.out_graph().shortest_path(source, target)
     }
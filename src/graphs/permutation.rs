@@ -0,0 +1,255 @@
+//! Renumbering of vertices.
+//!
+//! A [`Permutation`] maps the vertex ids of an input graph ("old") to the ids
+//! used by the crate ("new"). Everything built on the renumbered graph uses new
+//! ids, so the permutation should be stored next to it, see
+//! [`crate::container`], to map results back to the original ids.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{reversible_graph::ReversibleGraph, Graph, Vertex, WeightedEdge};
+use crate::search::{collections::dijkstra_data::Path, path::ShortestPathTestCase};
+
+/// A bijection between old and new vertex ids.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Permutation {
+    new_to_old: Vec<Vertex>,
+    old_to_new: Vec<Vertex>,
+}
+
+impl Permutation {
+    pub fn identity(number_of_vertices: u32) -> Self {
+        Permutation {
+            new_to_old: (0..number_of_vertices).collect(),
+            old_to_new: (0..number_of_vertices).collect(),
+        }
+    }
+
+    /// Returns the permutation that renumbers `new_to_old[v]` to `v`, or
+    /// `None` if `new_to_old` is not a permutation of `0..new_to_old.len()`.
+    pub fn from_new_to_old(new_to_old: Vec<Vertex>) -> Option<Self> {
+        let old_to_new = invert(&new_to_old)?;
+        Some(Permutation {
+            new_to_old,
+            old_to_new,
+        })
+    }
+
+    /// Returns the permutation that renumbers `v` to `old_to_new[v]`, or `None`
+    /// if `old_to_new` is not a permutation of `0..old_to_new.len()`.
+    pub fn from_old_to_new(old_to_new: Vec<Vertex>) -> Option<Self> {
+        let new_to_old = invert(&old_to_new)?;
+        Some(Permutation {
+            new_to_old,
+            old_to_new,
+        })
+    }
+
+    /// Numbers the vertices that have in or out edges continuously from zero,
+    /// keeping their relative order. Vertices without edges are numbered
+    /// after them. Returns the permutation together with the number of
+    /// vertices with edges, to drop the others with
+    /// [`ReversibleGraph::restricted_to`].
+    pub fn compacting<G: Graph>(graph: &ReversibleGraph<G>) -> (Self, u32) {
+        let has_edges = |vertex: Vertex| {
+            graph.out_graph().edges(vertex).len() != 0 || graph.in_graph().edges(vertex).len() != 0
+        };
+        let number_of_vertices = graph
+            .out_graph()
+            .number_of_vertices()
+            .max(graph.in_graph().number_of_vertices());
+
+        let (mut new_to_old, trivial_vertices): (Vec<_>, Vec<_>) =
+            (0..number_of_vertices).partition(|&vertex| has_edges(vertex));
+        let number_of_vertices_with_edges = new_to_old.len() as u32;
+        new_to_old.extend(trivial_vertices);
+
+        (
+            Permutation::from_new_to_old(new_to_old).unwrap(),
+            number_of_vertices_with_edges,
+        )
+    }
+
+    pub fn number_of_vertices(&self) -> u32 {
+        self.new_to_old.len() as u32
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.new_to_old
+            .iter()
+            .enumerate()
+            .all(|(new, &old)| new as Vertex == old)
+    }
+
+    pub fn new_to_old(&self) -> &[Vertex] {
+        &self.new_to_old
+    }
+
+    pub fn old_to_new(&self) -> &[Vertex] {
+        &self.old_to_new
+    }
+
+    /// Panics if `old` is out of range.
+    pub fn to_new(&self, old: Vertex) -> Vertex {
+        self.old_to_new[old as usize]
+    }
+
+    /// Panics if `new` is out of range.
+    pub fn to_old(&self, new: Vertex) -> Vertex {
+        self.new_to_old[new as usize]
+    }
+
+    pub fn inverse(&self) -> Permutation {
+        Permutation {
+            new_to_old: self.old_to_new.clone(),
+            old_to_new: self.new_to_old.clone(),
+        }
+    }
+
+    pub fn apply_to_edges(&self, edges: &[WeightedEdge]) -> Vec<WeightedEdge> {
+        edges
+            .iter()
+            .map(|edge| {
+                WeightedEdge::new(self.to_new(edge.tail), self.to_new(edge.head), edge.weight)
+            })
+            .collect()
+    }
+
+    pub fn apply_to_test_cases(
+        &self,
+        test_cases: &[ShortestPathTestCase],
+    ) -> Vec<ShortestPathTestCase> {
+        test_cases
+            .iter()
+            .map(|test_case| ShortestPathTestCase {
+                source: self.to_new(test_case.source),
+                target: self.to_new(test_case.target),
+                distance: test_case.distance,
+            })
+            .collect()
+    }
+
+    /// Renumbers the vertices of a vertex ordering, e.g. a `level_to_vertex`.
+    pub fn apply_to_level_to_vertex(&self, level_to_vertex: &[Vertex]) -> Vec<Vertex> {
+        level_to_vertex
+            .iter()
+            .map(|&vertex| self.to_new(vertex))
+            .collect()
+    }
+
+    /// Renumbers the keys and the skipped vertices of shortcuts.
+    pub fn apply_to_shortcuts(
+        &self,
+        shortcuts: &HashMap<(Vertex, Vertex), Vertex>,
+    ) -> HashMap<(Vertex, Vertex), Vertex> {
+        shortcuts
+            .iter()
+            .map(|(&(tail, head), &skipped_vertex)| {
+                (
+                    (self.to_new(tail), self.to_new(head)),
+                    self.to_new(skipped_vertex),
+                )
+            })
+            .collect()
+    }
+
+    /// Maps a path found on the renumbered graph back to the old ids.
+    pub fn restore_path(&self, path: &Path) -> Path {
+        Path {
+            vertices: path
+                .vertices
+                .iter()
+                .map(|&vertex| self.to_old(vertex))
+                .collect(),
+            distance: path.distance,
+        }
+    }
+}
+
+/// Structures that can be rebuilt for renumbered vertices. This is the only
+/// way to renumber graphs and the structures built on them.
+pub trait Renumber: Sized {
+    /// Returns the structure with vertex `v` renamed to
+    /// `permutation.to_new(v)`.
+    fn renumbered(&self, permutation: &Permutation) -> Self;
+}
+
+/// Returns the inverse of `permutation`, or `None` if it is none.
+fn invert(permutation: &[Vertex]) -> Option<Vec<Vertex>> {
+    let mut inverse = vec![Vertex::MAX; permutation.len()];
+    for (index, &value) in permutation.iter().enumerate() {
+        let entry = inverse.get_mut(value as usize)?;
+        if *entry != Vertex::MAX {
+            return None;
+        }
+        *entry = index as Vertex;
+    }
+    Some(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Permutation, Renumber};
+    use crate::{
+        graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, WeightedEdge},
+        search::{ch::contracted_graph::ContractedGraph, hl::hub_graph::HubGraph, PathFinding},
+    };
+
+    #[test]
+    fn renumbered_structures_answer_the_same_queries() {
+        // Vertices 0 and 5 have no edges.
+        let mut edges = Vec::new();
+        for vertex in 1..4 {
+            edges.push(WeightedEdge::new(vertex, vertex + 1, vertex as u64));
+            edges.push(WeightedEdge::new(vertex + 1, vertex, 2));
+        }
+        edges.push(WeightedEdge::new(6, 1, 1));
+        edges.push(WeightedEdge::new(4, 6, 7));
+        let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+
+        assert_eq!(Permutation::from_new_to_old(vec![0, 2, 2]), None);
+        let (permutation, number_of_vertices_with_edges) = Permutation::compacting(&graph);
+        assert_eq!(permutation.new_to_old(), &[1, 2, 3, 4, 6, 0, 5]);
+        assert_eq!(number_of_vertices_with_edges, 5);
+
+        let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&graph, u32::MAX);
+        let hub_graph = HubGraph::by_merging(&contracted_graph);
+
+        let renumbered_graph = graph.renumbered(&permutation);
+        assert_eq!(PathFinding::number_of_vertices(&renumbered_graph), 7);
+        let renumbered_graph = renumbered_graph.restricted_to(number_of_vertices_with_edges);
+        let renumbered_contracted_graph = contracted_graph.renumbered(&permutation);
+        let renumbered_hub_graph = hub_graph.renumbered(&permutation);
+        assert_eq!(
+            PathFinding::number_of_vertices(&renumbered_graph),
+            5,
+            "vertices without edges are dropped"
+        );
+
+        for &source in &permutation.new_to_old()[..5] {
+            for &target in &permutation.new_to_old()[..5] {
+                let expected = graph.shortest_path(source, target);
+                let (source, target) = (permutation.to_new(source), permutation.to_new(target));
+                let pathfinders: [&dyn PathFinding; 3] = [
+                    &renumbered_graph,
+                    &renumbered_contracted_graph,
+                    &renumbered_hub_graph,
+                ];
+
+                for pathfinder in pathfinders {
+                    let path = pathfinder
+                        .shortest_path(source, target)
+                        .map(|path| permutation.restore_path(&path));
+                    assert_eq!(
+                        path.as_ref().map(|path| (&path.vertices, path.distance)),
+                        expected
+                            .as_ref()
+                            .map(|path| (&path.vertices, path.distance))
+                    );
+                }
+            }
+        }
+    }
+}
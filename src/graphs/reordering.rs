@@ -0,0 +1,261 @@
+//! Vertex orders that improve the memory locality of queries.
+//!
+//! Each function returns a [`Permutation`] that can be handed to
+//! [`crate::search::reordered::Reordered`], which renumbers a contracted graph
+//! or a hub graph internally and keeps answering queries in the original ids.
+//!
+//! - [`breadth_first`] and [`depth_first`] number vertices that are close in
+//!   the graph closely.
+//! - [`by_level`] numbers the vertices of a contraction hierarchy from the top,
+//!   so the vertices that almost every query settles share a few cache lines.
+//! - [`hilbert_curve`] numbers vertices along a space-filling curve over their
+//!   coordinates, which needs no graph traversal at all.
+
+use std::collections::VecDeque;
+
+use super::{
+    permutation::Permutation, reversible_graph::ReversibleGraph,
+    vertex_attributes::VertexAttributes, Graph, Vertex,
+};
+
+/// Side length of the grid the Hilbert curve is laid over is `2^HILBERT_ORDER`.
+const HILBERT_ORDER: u32 = 16;
+
+/// Numbers the vertices in the order a breadth first search visits them,
+/// ignoring the direction of edges. Every unvisited vertex starts a new
+/// search, in order of the old ids.
+pub fn breadth_first<G: Graph>(graph: &ReversibleGraph<G>) -> Permutation {
+    let number_of_vertices = number_of_vertices(graph);
+    let mut visited = vec![false; number_of_vertices as usize];
+    let mut new_to_old = Vec::with_capacity(number_of_vertices as usize);
+    let mut queue = VecDeque::new();
+
+    for root in 0..number_of_vertices {
+        if visited[root as usize] {
+            continue;
+        }
+        visited[root as usize] = true;
+        queue.push_back(root);
+
+        while let Some(vertex) = queue.pop_front() {
+            new_to_old.push(vertex);
+            for neighbor in undirected_neighbors(graph, vertex) {
+                if !visited[neighbor as usize] {
+                    visited[neighbor as usize] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    Permutation::from_new_to_old(new_to_old).unwrap()
+}
+
+/// Numbers the vertices in the preorder of a depth first search, ignoring the
+/// direction of edges. Every unvisited vertex starts a new search, in order of
+/// the old ids.
+pub fn depth_first<G: Graph>(graph: &ReversibleGraph<G>) -> Permutation {
+    let number_of_vertices = number_of_vertices(graph);
+    let mut visited = vec![false; number_of_vertices as usize];
+    let mut new_to_old = Vec::with_capacity(number_of_vertices as usize);
+    let mut stack = Vec::new();
+
+    for root in 0..number_of_vertices {
+        stack.push(root);
+
+        while let Some(vertex) = stack.pop() {
+            if visited[vertex as usize] {
+                continue;
+            }
+            visited[vertex as usize] = true;
+            new_to_old.push(vertex);
+
+            // Reversed, so that the first neighbor is visited first.
+            let mut neighbors = undirected_neighbors(graph, vertex);
+            neighbors.retain(|&neighbor| !visited[neighbor as usize]);
+            stack.extend(neighbors.into_iter().rev());
+        }
+    }
+
+    Permutation::from_new_to_old(new_to_old).unwrap()
+}
+
+/// Numbers the vertices by descending level, so the highest vertex becomes
+/// vertex 0. Returns `None` if `level_to_vertex` is not a permutation.
+pub fn by_level(level_to_vertex: &[Vertex]) -> Option<Permutation> {
+    Permutation::from_new_to_old(level_to_vertex.iter().rev().cloned().collect())
+}
+
+/// Numbers the vertices along a Hilbert curve laid over the bounding box of
+/// their coordinates. Vertices without coordinates are numbered last, in order
+/// of the old ids.
+pub fn hilbert_curve(attributes: &VertexAttributes, number_of_vertices: u32) -> Permutation {
+    let coordinates = (0..number_of_vertices)
+        .filter_map(|vertex| Some((vertex, attributes.coordinate(vertex)?)))
+        .collect::<Vec<_>>();
+
+    let (min_latitude, max_latitude, min_longitude, max_longitude) = coordinates.iter().fold(
+        (f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+        |(min_lat, max_lat, min_lon, max_lon), &(_, (lat, lon))| {
+            (
+                min_lat.min(lat),
+                max_lat.max(lat),
+                min_lon.min(lon),
+                max_lon.max(lon),
+            )
+        },
+    );
+    let cell = |value: f64, min: f64, max: f64| {
+        let side = (1u32 << HILBERT_ORDER) - 1;
+        if max > min {
+            ((value - min) / (max - min) * side as f64) as u32
+        } else {
+            0
+        }
+    };
+
+    let mut keyed = coordinates
+        .iter()
+        .map(|&(vertex, (latitude, longitude))| {
+            let x = cell(longitude, min_longitude, max_longitude);
+            let y = cell(latitude, min_latitude, max_latitude);
+            (hilbert_index(x, y), vertex)
+        })
+        .collect::<Vec<_>>();
+    keyed.sort_unstable();
+
+    let mut has_coordinate = vec![false; number_of_vertices as usize];
+    let mut new_to_old = Vec::with_capacity(number_of_vertices as usize);
+    for (_, vertex) in keyed {
+        has_coordinate[vertex as usize] = true;
+        new_to_old.push(vertex);
+    }
+    new_to_old.extend((0..number_of_vertices).filter(|&vertex| !has_coordinate[vertex as usize]));
+
+    Permutation::from_new_to_old(new_to_old).unwrap()
+}
+
+/// Distance of the cell `(x, y)` along the Hilbert curve of order
+/// [`HILBERT_ORDER`].
+fn hilbert_index(mut x: u32, mut y: u32) -> u64 {
+    let side = 1u32 << HILBERT_ORDER;
+    let mut index = 0;
+    let mut s = side / 2;
+
+    while s > 0 {
+        let rx = (x & s > 0) as u32;
+        let ry = (y & s > 0) as u32;
+        index += s as u64 * s as u64 * ((3 * rx) ^ ry) as u64;
+
+        // Rotate the quadrant, so that the curve stays continuous.
+        if ry == 0 {
+            if rx == 1 {
+                x = side - 1 - x;
+                y = side - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+
+    index
+}
+
+fn number_of_vertices<G: Graph>(graph: &ReversibleGraph<G>) -> u32 {
+    graph
+        .out_graph()
+        .number_of_vertices()
+        .max(graph.in_graph().number_of_vertices())
+}
+
+fn undirected_neighbors<G: Graph>(graph: &ReversibleGraph<G>, vertex: Vertex) -> Vec<Vertex> {
+    let mut neighbors = Vec::new();
+    for direction in [graph.out_graph(), graph.in_graph()] {
+        if vertex < direction.number_of_vertices() {
+            neighbors.extend(direction.edges(vertex).map(|edge| edge.head));
+        }
+    }
+    neighbors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{breadth_first, by_level, depth_first, hilbert_curve};
+    use crate::{
+        graphs::{
+            reversible_graph::ReversibleGraph,
+            vec_vec_graph::VecVecGraph,
+            vertex_attributes::{VertexAttribute, VertexAttributes},
+            WeightedEdge,
+        },
+        search::{
+            ch::contracted_graph::ContractedGraph, hl::hub_graph::HubGraph, reordered::Reordered,
+            PathFinding,
+        },
+    };
+
+    #[test]
+    fn reordered_structures_keep_external_ids() {
+        // A 3 x 3 grid with vertex 9 disconnected.
+        let mut edges = Vec::new();
+        for row in 0..3 {
+            for column in 0..3 {
+                let vertex = row * 3 + column;
+                if column < 2 {
+                    edges.push(WeightedEdge::new(vertex, vertex + 1, 1 + vertex as u64 % 4));
+                    edges.push(WeightedEdge::new(vertex + 1, vertex, 2));
+                }
+                if row < 2 {
+                    edges.push(WeightedEdge::new(vertex, vertex + 3, 3));
+                    edges.push(WeightedEdge::new(vertex + 3, vertex, 1 + vertex as u64 % 3));
+                }
+            }
+        }
+        edges.push(WeightedEdge::new(9, 9, 1));
+        let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+        let attributes = VertexAttributes::new(
+            (0..9)
+                .map(|vertex| VertexAttribute::new((vertex / 3) as f64, (vertex % 3) as f64))
+                .collect(),
+        );
+
+        let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&graph, u32::MAX);
+        let hub_graph = HubGraph::by_merging(&contracted_graph);
+
+        assert_eq!(breadth_first(&graph).new_to_old()[..4], [0, 1, 3, 2]);
+        assert_eq!(depth_first(&graph).new_to_old()[..4], [0, 1, 2, 5]);
+        assert_eq!(hilbert_curve(&attributes, 10).new_to_old()[9], 9);
+
+        let permutations = [
+            breadth_first(&graph),
+            depth_first(&graph),
+            by_level(contracted_graph.level_to_vertex()).unwrap(),
+            hilbert_curve(&attributes, 10),
+        ];
+        for permutation in permutations {
+            let reordered_contracted_graph = Reordered::new(&contracted_graph, permutation.clone());
+            let reordered_hub_graph = Reordered::new(&hub_graph, permutation);
+
+            for source in 0..10 {
+                for target in 0..10 {
+                    let expected = graph.out_graph().shortest_path(source, target);
+                    for pathfinder in [
+                        &reordered_contracted_graph as &dyn PathFinding,
+                        &reordered_hub_graph,
+                    ] {
+                        let path = pathfinder.shortest_path(source, target);
+                        assert_eq!(
+                            path.as_ref().map(|path| path.distance),
+                            expected.as_ref().map(|path| path.distance)
+                        );
+                        if let Some(path) = path {
+                            assert_eq!(path.vertices.first(), Some(&source));
+                            assert_eq!(path.vertices.last(), Some(&target));
+                        }
+                    }
+                }
+            }
+            assert_eq!(reordered_hub_graph.shortest_path_distance(0, 10), None);
+        }
+    }
+}
@@ -3,8 +3,24 @@ use std::collections::HashMap;
 use indicatif::ProgressIterator;
 use serde::{Deserialize, Serialize};
 
-use super::{Distance, Edge, Graph, TaillessEdge, Vertex, WeightedEdge};
-use crate::{graphs::read_edges_from_fmi_file, utility::get_progressbar};
+use super::{
+    number_of_vertices_of, Distance, Edge, Graph, GraphFromEdges, TaillessEdge, Vertex,
+    WeightedEdge,
+};
+use crate::{
+    error::Result,
+    graphs::{
+        fmi::{
+            try_read_edges_from_fmi_file, try_read_fmi_file, try_read_fmi_file_in_parallel,
+            FmiOptions,
+        },
+        permutation::{Permutation, Renumber},
+        read_edges_from_fmi_file,
+        vec_vec_graph::VecVecGraph,
+        vertex_attributes::VertexAttributes,
+    },
+    utility::get_progressbar,
+};
 
 pub trait FromEdges {
     fn from_edges(edges: &Vec<WeightedEdge>) -> Self;
@@ -29,6 +45,18 @@ impl<G: Graph + Default> ReversibleGraph<G> {
         Self::from_edges(&edges)
     }
 
+    pub fn try_from_fmi_file(path: &std::path::Path) -> Result<Self> {
+        let edges = try_read_edges_from_fmi_file(path)?;
+        Ok(Self::from_edges(&edges))
+    }
+
+    pub fn try_from_fmi_file_with_attributes(
+        path: &std::path::Path,
+    ) -> Result<(Self, VertexAttributes)> {
+        let fmi = try_read_fmi_file(path)?;
+        Ok((Self::from_edges(&fmi.edges), fmi.attributes))
+    }
+
     pub fn from_edges(edges: &Vec<WeightedEdge>) -> Self {
         let mut graph = Self::new();
 
@@ -52,7 +80,91 @@ impl<G: Graph + Default> ReversibleGraph<G> {
     }
 }
 
+impl<G: GraphFromEdges> ReversibleGraph<G> {
+    /// Builds a graph with exactly `number_of_vertices` vertices from all its
+    /// edges at once, see [`GraphFromEdges::with_edges`].
+    pub fn with_edges(number_of_vertices: u32, edges: &[WeightedEdge]) -> Self {
+        let (out_graph, in_graph) = rayon::join(
+            || G::with_edges(number_of_vertices, edges),
+            || {
+                let reversed_edges: Vec<WeightedEdge> =
+                    edges.iter().map(WeightedEdge::reversed).collect();
+                G::with_edges(number_of_vertices, &reversed_edges)
+            },
+        );
+
+        ReversibleGraph {
+            out_graph: Box::new(out_graph),
+            in_graph: Box::new(in_graph),
+        }
+    }
+
+    /// Returns the subgraph induced by the vertices `0..number_of_vertices`,
+    /// e.g. the selected vertices at the front of a renumbered graph. Edges
+    /// from or to other vertices are dropped.
+    pub fn restricted_to(&self, number_of_vertices: u32) -> Self {
+        let edges: Vec<WeightedEdge> = self
+            .out_graph
+            .all_edges()
+            .into_iter()
+            .filter(|edge| edge.tail < number_of_vertices && edge.head < number_of_vertices)
+            .collect();
+
+        Self::with_edges(number_of_vertices, &edges)
+    }
+}
+
+impl<G: GraphFromEdges> Renumber for ReversibleGraph<G> {
+    /// Returns the graph with one vertex per vertex of `permutation`. Edges
+    /// from or to vertices the permutation does not cover are dropped.
+    fn renumbered(&self, permutation: &Permutation) -> Self {
+        let old_to_new = permutation.old_to_new();
+        let edges: Vec<WeightedEdge> = self
+            .out_graph
+            .all_edges()
+            .into_iter()
+            .filter_map(|edge| {
+                Some(WeightedEdge::new(
+                    *old_to_new.get(edge.tail as usize)?,
+                    *old_to_new.get(edge.head as usize)?,
+                    edge.weight,
+                ))
+            })
+            .collect();
+
+        Self::with_edges(permutation.number_of_vertices(), &edges)
+    }
+}
+
+impl ReversibleGraph<VecVecGraph> {
+    /// Reads a fmi file in parallel and builds the graph in bulk, see
+    /// [`try_read_fmi_file_in_parallel`]. Like [`Self::from_fmi_file`], the
+    /// graph ends at the largest vertex with an edge, see
+    /// [`number_of_vertices_of`].
+    pub fn try_from_fmi_file_in_parallel(path: &std::path::Path) -> Result<Self> {
+        let options = FmiOptions {
+            with_attributes: false,
+            ..FmiOptions::default()
+        };
+        let fmi = try_read_fmi_file_in_parallel(path, &options)?;
+        Ok(Self::with_edges(
+            number_of_vertices_of(&fmi.edges),
+            &fmi.edges,
+        ))
+    }
+}
+
 impl<G: Graph> ReversibleGraph<G> {
+    /// Combines a graph with its reverse, e.g. two read only views of the
+    /// same edges. `in_graph` must have the edge `head -> tail` for every edge
+    /// `tail -> head` of `out_graph`, with the same weight.
+    pub fn from_out_and_in_graph(out_graph: G, in_graph: G) -> Self {
+        ReversibleGraph {
+            out_graph: Box::new(out_graph),
+            in_graph: Box::new(in_graph),
+        }
+    }
+
     pub fn out_graph(&self) -> &G {
         &self.out_graph
     }
@@ -107,3 +219,64 @@ impl<G: Graph> ReversibleGraph<G> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::ReversibleGraph;
+    use crate::{
+        container::GraphFingerprint,
+        graphs::{vec_vec_graph::VecVecGraph, Distance, Graph, WeightedEdge},
+    };
+
+    #[test]
+    fn bulk_build_matches_insertion() {
+        let edges = vec![
+            WeightedEdge::new(3, 1, 5),
+            WeightedEdge::new(0, 1, 4),
+            WeightedEdge::new(0, 1, 2),
+            WeightedEdge::new(1, 0, 7),
+            WeightedEdge::new(0, 1, 3),
+            WeightedEdge::new(2, 0, 1),
+            WeightedEdge::new(5, 0, Distance::MAX),
+        ];
+        let inserted = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+        let bulk = ReversibleGraph::<VecVecGraph>::with_edges(
+            inserted.out_graph().number_of_vertices(),
+            &edges,
+        );
+
+        for (expected, found) in [
+            (inserted.out_graph(), bulk.out_graph()),
+            (inserted.in_graph(), bulk.in_graph()),
+        ] {
+            assert_eq!(expected.number_of_vertices(), found.number_of_vertices());
+            assert_eq!(expected.all_edges(), found.all_edges());
+        }
+    }
+
+    #[test]
+    fn fmi_loaders_agree_on_isolated_vertices() {
+        // Vertices 3 and 4 have no edges.
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(
+            b"# Id : 1\n\n5\n3\n\
+            0 0 48.0 9.0 0\n1 1 48.1 9.1 0\n2 2 48.2 9.2 0\n\
+            3 3 48.3 9.3 0\n4 4 48.4 9.4 0\n\
+            0 1 4 0 0\n1 2 3 0 0\n2 0 5 0 0\n",
+        )
+        .unwrap();
+
+        let sequential = ReversibleGraph::<VecVecGraph>::try_from_fmi_file(file.path()).unwrap();
+        let parallel =
+            ReversibleGraph::<VecVecGraph>::try_from_fmi_file_in_parallel(file.path()).unwrap();
+
+        assert_eq!(sequential.out_graph().number_of_vertices(), 3);
+        assert_eq!(parallel.out_graph().number_of_vertices(), 3);
+        assert_eq!(
+            GraphFingerprint::of(sequential.out_graph()),
+            GraphFingerprint::of(parallel.out_graph())
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! Strongly connected components of a [`ReversibleGraph`].
+//!
+//! Road graphs often contain small islands that cannot be left or entered.
+//! Queries between them have no path, so they show up as `None` test cases.
+//! [`StronglyConnectedComponents`] finds them with Kosaraju's algorithm and
+//! can cut the graph down to its largest component.
+
+use super::{
+    permutation::{Permutation, Renumber},
+    reversible_graph::ReversibleGraph,
+    Graph, GraphFromEdges, StaticGraph, Vertex,
+};
+
+const NO_COMPONENT: u32 = u32::MAX;
+
+/// The strongly connected component of every vertex.
+///
+/// Components are numbered in topological order of the condensation: if there
+/// is a path from `u` to `v`, the component of `u` is at most the component of
+/// `v`.
+#[derive(Clone, Debug)]
+pub struct StronglyConnectedComponents {
+    vertex_to_component: Vec<u32>,
+    component_sizes: Vec<u32>,
+}
+
+impl StronglyConnectedComponents {
+    pub fn new<G: Graph>(graph: &ReversibleGraph<G>) -> Self {
+        let out_graph: &dyn Graph = graph.out_graph();
+        let in_graph: &dyn Graph = graph.in_graph();
+        let number_of_vertices = out_graph
+            .number_of_vertices()
+            .max(in_graph.number_of_vertices());
+
+        // First pass: order the vertices by the time a depth first search on
+        // the out graph finishes them.
+        let mut visited = vec![false; number_of_vertices as usize];
+        let mut finished = Vec::with_capacity(number_of_vertices as usize);
+        for root in 0..number_of_vertices {
+            if visited[root as usize] {
+                continue;
+            }
+            visited[root as usize] = true;
+            let mut stack = vec![(root, out_graph.out_edges(root))];
+
+            while let Some((vertex, edges)) = stack.last_mut() {
+                let vertex = *vertex;
+                match edges.find(|edge| !visited[edge.head as usize]) {
+                    Some(edge) => {
+                        visited[edge.head as usize] = true;
+                        stack.push((edge.head, out_graph.out_edges(edge.head)));
+                    }
+                    None => {
+                        finished.push(vertex);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        // Second pass: in order of decreasing finishing time, every vertex
+        // without a component collects the vertices that reach it.
+        let mut vertex_to_component = vec![NO_COMPONENT; number_of_vertices as usize];
+        let mut component_sizes = Vec::new();
+        let mut stack = Vec::new();
+        for &root in finished.iter().rev() {
+            if vertex_to_component[root as usize] != NO_COMPONENT {
+                continue;
+            }
+            let component = component_sizes.len() as u32;
+            let mut size = 0;
+            vertex_to_component[root as usize] = component;
+            stack.push(root);
+
+            while let Some(vertex) = stack.pop() {
+                size += 1;
+                for edge in in_graph.out_edges(vertex) {
+                    if vertex_to_component[edge.head as usize] == NO_COMPONENT {
+                        vertex_to_component[edge.head as usize] = component;
+                        stack.push(edge.head);
+                    }
+                }
+            }
+            component_sizes.push(size);
+        }
+
+        StronglyConnectedComponents {
+            vertex_to_component,
+            component_sizes,
+        }
+    }
+
+    pub fn number_of_vertices(&self) -> u32 {
+        self.vertex_to_component.len() as u32
+    }
+
+    pub fn number_of_components(&self) -> u32 {
+        self.component_sizes.len() as u32
+    }
+
+    /// Returns the component of `vertex`, or `None` if it is out of range.
+    pub fn component(&self, vertex: Vertex) -> Option<u32> {
+        self.vertex_to_component.get(vertex as usize).cloned()
+    }
+
+    pub fn vertex_to_component(&self) -> &[u32] {
+        &self.vertex_to_component
+    }
+
+    /// The number of vertices of each component.
+    pub fn component_sizes(&self) -> &[u32] {
+        &self.component_sizes
+    }
+
+    /// Returns the component with the most vertices, the first one on ties,
+    /// or `None` if the graph has no vertices.
+    pub fn largest_component(&self) -> Option<u32> {
+        let largest_size = *self.component_sizes.iter().max()?;
+        self.component_sizes
+            .iter()
+            .position(|&size| size == largest_size)
+            .map(|component| component as u32)
+    }
+
+    pub fn are_strongly_connected(&self, u: Vertex, v: Vertex) -> bool {
+        match (self.component(u), self.component(v)) {
+            (Some(u_component), Some(v_component)) => u_component == v_component,
+            _ => false,
+        }
+    }
+
+    /// Returns `false` if there is certainly no path from `source` to
+    /// `target`. `true` only guarantees a path if both are strongly connected.
+    pub fn may_reach(&self, source: Vertex, target: Vertex) -> bool {
+        match (self.component(source), self.component(target)) {
+            (Some(source_component), Some(target_component)) => {
+                source_component <= target_component
+            }
+            _ => false,
+        }
+    }
+
+    /// Numbers the vertices of the largest component first, keeping their
+    /// relative order, followed by all other vertices.
+    pub fn largest_component_permutation(&self) -> Permutation {
+        let largest_component = self.largest_component();
+        let (mut new_to_old, other_vertices): (Vec<_>, Vec<_>) = (0..self.number_of_vertices())
+            .partition(|&vertex| self.component(vertex) == largest_component);
+        new_to_old.extend(other_vertices);
+
+        Permutation::from_new_to_old(new_to_old).unwrap()
+    }
+
+    /// Returns the largest component as a graph of its own, together with the
+    /// permutation whose first vertices are the vertices of the component.
+    /// Use [`Permutation::to_old`] to map its ids back.
+    pub fn extract_largest_component<G: GraphFromEdges>(
+        &self,
+        graph: &ReversibleGraph<G>,
+    ) -> (ReversibleGraph<G>, Permutation) {
+        let permutation = self.largest_component_permutation();
+        let size = self
+            .largest_component()
+            .map_or(0, |component| self.component_sizes[component as usize]);
+        let largest_component = graph.renumbered(&permutation).restricted_to(size);
+
+        (largest_component, permutation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StronglyConnectedComponents;
+    use crate::{
+        graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, WeightedEdge},
+        search::{reachability::ReachabilityChecked, PathFinding},
+    };
+
+    #[test]
+    fn components_are_topologically_ordered() {
+        // The cycle 0 -> 2 -> 4 -> 0 leads into the cycle 1 <-> 3 <-> 5 <-> 1,
+        // which leads into the dead end 6.
+        let edges = vec![
+            WeightedEdge::new(0, 2, 1),
+            WeightedEdge::new(2, 4, 1),
+            WeightedEdge::new(4, 0, 1),
+            WeightedEdge::new(4, 5, 7),
+            WeightedEdge::new(1, 3, 1),
+            WeightedEdge::new(3, 1, 1),
+            WeightedEdge::new(3, 5, 2),
+            WeightedEdge::new(5, 1, 3),
+            WeightedEdge::new(5, 6, 4),
+        ];
+        let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+        let components = StronglyConnectedComponents::new(&graph);
+
+        assert_eq!(components.number_of_components(), 3);
+        assert!(components.are_strongly_connected(0, 4));
+        assert!(components.are_strongly_connected(1, 5));
+        assert!(!components.are_strongly_connected(0, 1));
+        assert!(components.may_reach(2, 3));
+        assert!(!components.may_reach(3, 2));
+        assert!(!components.may_reach(6, 5));
+        assert!(!components.may_reach(0, 7));
+
+        let largest_component = components.largest_component().unwrap();
+        assert_eq!(components.component_sizes()[largest_component as usize], 3);
+        assert_eq!(components.component(0), Some(largest_component));
+
+        let (extracted, permutation) = components.extract_largest_component(&graph);
+        assert_eq!(&permutation.new_to_old()[..3], &[0, 2, 4]);
+        for source in 0..3 {
+            for target in 0..3 {
+                assert_eq!(
+                    extracted.out_graph().shortest_path_distance(source, target),
+                    graph.out_graph().shortest_path_distance(
+                        permutation.to_old(source),
+                        permutation.to_old(target)
+                    )
+                );
+            }
+        }
+
+        let checked = ReachabilityChecked::new(graph.out_graph().clone(), components);
+        assert_eq!(checked.shortest_path_distance(5, 0), None);
+        assert_eq!(checked.shortest_path_distance(0, 3), Some(13));
+    }
+}
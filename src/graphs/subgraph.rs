@@ -0,0 +1,227 @@
+//! Extraction of induced subgraphs, e.g. to cut regression fixtures or
+//! regional graphs out of a large graph.
+//!
+//! Every function returns the subgraph together with a [`Permutation`] whose
+//! first vertices are the selected vertices in the order of their old ids, so
+//! vertex `v` of the subgraph is `permutation.to_old(v)` of the input. Edges
+//! are kept if both of their endpoints are selected. The subgraph has exactly
+//! one vertex per selected vertex, including selected vertices without edges.
+
+use std::collections::VecDeque;
+
+use super::{
+    permutation::{Permutation, Renumber},
+    reversible_graph::ReversibleGraph,
+    vertex_attributes::VertexAttributes,
+    Distance, Graph, GraphFromEdges, StaticGraph, Vertex,
+};
+use crate::search::collections::{
+    dijkstra_data::{DijkstraData, DijkstraDataHashMap},
+    vertex_distance_queue::{VertexDistanceQueue, VertexDistanceQueueBinaryHeap},
+    vertex_expanded_data::{VertexExpandedData, VertexExpandedDataHashSet},
+};
+
+/// A range of coordinates, all bounds are inclusive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min_latitude: f64,
+    pub min_longitude: f64,
+    pub max_latitude: f64,
+    pub max_longitude: f64,
+}
+
+impl BoundingBox {
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        (self.min_latitude..=self.max_latitude).contains(&latitude)
+            && (self.min_longitude..=self.max_longitude).contains(&longitude)
+    }
+}
+
+/// Returns the subgraph induced by `vertices`. Vertices out of range and
+/// duplicates are ignored.
+pub fn induced_subgraph<G: GraphFromEdges>(
+    graph: &ReversibleGraph<G>,
+    vertices: &[Vertex],
+) -> (ReversibleGraph<G>, Permutation) {
+    let number_of_vertices = number_of_vertices(graph);
+    let mut is_selected = vec![false; number_of_vertices as usize];
+    for &vertex in vertices {
+        if let Some(entry) = is_selected.get_mut(vertex as usize) {
+            *entry = true;
+        }
+    }
+
+    let (mut new_to_old, other_vertices): (Vec<_>, Vec<_>) =
+        (0..number_of_vertices).partition(|&vertex| is_selected[vertex as usize]);
+    let number_of_selected_vertices = new_to_old.len() as u32;
+    new_to_old.extend(other_vertices);
+
+    let permutation = Permutation::from_new_to_old(new_to_old).unwrap();
+    let subgraph = graph
+        .renumbered(&permutation)
+        .restricted_to(number_of_selected_vertices);
+    (subgraph, permutation)
+}
+
+/// Returns the subgraph induced by the vertices that are at most `hops` edges
+/// away from `seed`, following edges in both directions.
+pub fn within_hops<G: GraphFromEdges>(
+    graph: &ReversibleGraph<G>,
+    seed: Vertex,
+    hops: u32,
+) -> (ReversibleGraph<G>, Permutation) {
+    let mut vertices = vec![seed];
+    let mut queue = VecDeque::from([(seed, 0)]);
+    let mut seen = vec![false; number_of_vertices(graph) as usize];
+    if let Some(entry) = seen.get_mut(seed as usize) {
+        *entry = true;
+    }
+
+    let out_graph: &dyn Graph = graph.out_graph();
+    let in_graph: &dyn Graph = graph.in_graph();
+    while let Some((vertex, vertex_hops)) = queue.pop_front() {
+        if vertex_hops == hops {
+            continue;
+        }
+        for edge in out_graph
+            .out_edges(vertex)
+            .chain(in_graph.out_edges(vertex))
+        {
+            if !seen[edge.head as usize] {
+                seen[edge.head as usize] = true;
+                vertices.push(edge.head);
+                queue.push_back((edge.head, vertex_hops + 1));
+            }
+        }
+    }
+
+    induced_subgraph(graph, &vertices)
+}
+
+/// Returns the subgraph induced by the vertices whose distance from or to
+/// `seed` is at most `distance`.
+pub fn within_distance<G: GraphFromEdges>(
+    graph: &ReversibleGraph<G>,
+    seed: Vertex,
+    distance: Distance,
+) -> (ReversibleGraph<G>, Permutation) {
+    let mut vertices = vertices_within_distance(graph.out_graph(), seed, distance);
+    vertices.extend(vertices_within_distance(graph.in_graph(), seed, distance));
+
+    induced_subgraph(graph, &vertices)
+}
+
+/// Returns the subgraph induced by the vertices with coordinates inside of
+/// `bounding_box`.
+pub fn within_bounding_box<G: GraphFromEdges>(
+    graph: &ReversibleGraph<G>,
+    attributes: &VertexAttributes,
+    bounding_box: &BoundingBox,
+) -> (ReversibleGraph<G>, Permutation) {
+    let vertices = (0..attributes.number_of_vertices())
+        .filter(|&vertex| {
+            attributes
+                .coordinate(vertex)
+                .is_some_and(|(latitude, longitude)| bounding_box.contains(latitude, longitude))
+        })
+        .collect::<Vec<_>>();
+
+    induced_subgraph(graph, &vertices)
+}
+
+/// Runs a Dijkstra from `seed` that stops at `distance` and returns the
+/// settled vertices.
+fn vertices_within_distance(graph: &dyn Graph, seed: Vertex, distance: Distance) -> Vec<Vertex> {
+    let mut data = DijkstraDataHashMap::new();
+    let mut expanded = VertexExpandedDataHashSet::new();
+    let mut queue = VertexDistanceQueueBinaryHeap::new();
+    data.set_distance(seed, 0);
+    queue.insert(seed, 0);
+
+    while let Some((tail, distance_tail)) = queue.pop() {
+        if expanded.expand(tail) {
+            continue;
+        }
+
+        for edge in graph.out_edges(tail) {
+            let alternative_distance_head = distance_tail.saturating_add(edge.weight);
+            if alternative_distance_head <= distance
+                && alternative_distance_head < data.get_distance(edge.head)
+            {
+                data.set_distance(edge.head, alternative_distance_head);
+                queue.insert(edge.head, alternative_distance_head);
+            }
+        }
+    }
+
+    expanded.expanded_vertices().collect()
+}
+
+fn number_of_vertices<G: Graph>(graph: &ReversibleGraph<G>) -> u32 {
+    graph
+        .out_graph()
+        .number_of_vertices()
+        .max(graph.in_graph().number_of_vertices())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{induced_subgraph, within_bounding_box, within_distance, within_hops, BoundingBox};
+    use crate::graphs::{
+        reversible_graph::ReversibleGraph,
+        vec_vec_graph::VecVecGraph,
+        vertex_attributes::{VertexAttribute, VertexAttributes},
+        Graph, WeightedEdge,
+    };
+
+    #[test]
+    fn subgraphs_keep_the_edges_between_selected_vertices() {
+        // The path 0 - 1 - 2 - 3 - 4 with edges in both directions, vertex v
+        // is at latitude v.
+        let mut edges = Vec::new();
+        for vertex in 0..4 {
+            edges.push(WeightedEdge::new(vertex, vertex + 1, 10));
+            edges.push(WeightedEdge::new(vertex + 1, vertex, 1));
+        }
+        let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+        let attributes = VertexAttributes::new(
+            (0..5)
+                .map(|vertex| VertexAttribute::new(vertex as f64, 0.0))
+                .collect(),
+        );
+
+        let (subgraph, permutation) = induced_subgraph(&graph, &[3, 1, 2, 2, 9]);
+        assert_eq!(&permutation.new_to_old()[..3], &[1, 2, 3]);
+        assert_eq!(
+            subgraph.out_graph().all_edges(),
+            vec![
+                WeightedEdge::new(0, 1, 10),
+                WeightedEdge::new(1, 0, 1),
+                WeightedEdge::new(1, 2, 10),
+                WeightedEdge::new(2, 1, 1),
+            ]
+        );
+        assert_eq!(subgraph.in_graph().all_edges().len(), 4);
+
+        let (_, permutation) = within_hops(&graph, 2, 1);
+        assert_eq!(&permutation.new_to_old()[..3], &[1, 2, 3]);
+
+        // 1 and 2 are at most 2 away to 0, but at least 10 away from 0.
+        let (subgraph, permutation) = within_distance(&graph, 0, 2);
+        assert_eq!(&permutation.new_to_old()[..3], &[0, 1, 2]);
+        assert_eq!(subgraph.out_graph().number_of_vertices(), 3);
+
+        let bounding_box = BoundingBox {
+            min_latitude: 2.5,
+            min_longitude: -1.0,
+            max_latitude: 4.0,
+            max_longitude: 1.0,
+        };
+        let (subgraph, permutation) = within_bounding_box(&graph, &attributes, &bounding_box);
+        assert_eq!(&permutation.new_to_old()[..2], &[3, 4]);
+        assert_eq!(
+            subgraph.out_graph().all_edges(),
+            vec![WeightedEdge::new(0, 1, 10), WeightedEdge::new(1, 0, 1)]
+        );
+    }
+}
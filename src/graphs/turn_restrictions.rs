@@ -0,0 +1,160 @@
+//! Turn restrictions and turn costs, which are applied by the transformation
+//! in [`super::edge_based_graph`].
+//!
+//! A turn is given by three vertices: it enters `via` on the edge `from ->
+//! via` and leaves it on the edge `via -> to`. The text format has one turn
+//! per line, empty lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! # forbidden turn
+//! n from via to
+//! # the only allowed turn after from -> via
+//! o from via to
+//! # turn with an additional cost
+//! c from via to cost
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use super::{
+    parsing::{numbered_lines, LineFields},
+    Distance, Vertex,
+};
+use crate::error::{Error, ParseErrorKind, Result};
+
+/// Entering `via` from `from` and leaving it to `to`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Turn {
+    pub from: Vertex,
+    pub via: Vertex,
+    pub to: Vertex,
+}
+
+impl Turn {
+    pub fn new(from: Vertex, via: Vertex, to: Vertex) -> Self {
+        Turn { from, via, to }
+    }
+
+    /// Returns true if the turn goes back to where it came from.
+    pub fn is_u_turn(&self) -> bool {
+        self.from == self.to
+    }
+}
+
+/// Which turns are allowed and what they cost. Turns without an entry are
+/// allowed and free, except for U-turns, see [`TurnRestrictions::u_turn_cost`].
+#[derive(Clone, Debug, Default)]
+pub struct TurnRestrictions {
+    forbidden: HashSet<Turn>,
+    /// The only allowed `to` after entering `via` from `from`.
+    mandatory: HashMap<(Vertex, Vertex), Vertex>,
+    costs: HashMap<Turn, Distance>,
+    /// The cost of U-turns without an entry of their own. `None` forbids them,
+    /// except at dead ends, which could not be left otherwise.
+    pub u_turn_cost: Option<Distance>,
+}
+
+impl TurnRestrictions {
+    pub fn forbid(&mut self, turn: Turn) {
+        self.forbidden.insert(turn);
+    }
+
+    /// Forbids all other turns after entering `turn.via` from `turn.from`.
+    pub fn require(&mut self, turn: Turn) {
+        self.mandatory.insert((turn.from, turn.via), turn.to);
+    }
+
+    pub fn set_cost(&mut self, turn: Turn, cost: Distance) {
+        self.costs.insert(turn, cost);
+    }
+
+    /// Returns the cost of `turn`, or `None` if it is forbidden. `is_dead_end`
+    /// tells whether `turn.via` can only be left towards `turn.from`, which
+    /// allows U-turns without an entry of their own.
+    pub fn turn_cost(&self, turn: &Turn, is_dead_end: bool) -> Option<Distance> {
+        if self.forbidden.contains(turn) {
+            return None;
+        }
+        if let Some(&to) = self.mandatory.get(&(turn.from, turn.via)) {
+            if to != turn.to {
+                return None;
+            }
+        }
+        if let Some(&cost) = self.costs.get(turn) {
+            return Some(cost);
+        }
+        if turn.is_u_turn() {
+            return self.u_turn_cost.or(is_dead_end.then_some(0));
+        }
+
+        Some(0)
+    }
+
+    /// Returns the number of forbidden, mandatory and costly turns.
+    pub fn number_of_entries(&self) -> usize {
+        self.forbidden.len() + self.mandatory.len() + self.costs.len()
+    }
+}
+
+/// Reads a turn restriction file, see the module documentation. Vertices have
+/// to be smaller than `number_of_vertices`.
+pub fn try_read_turn_restrictions_file(
+    path: &Path,
+    number_of_vertices: usize,
+) -> Result<TurnRestrictions> {
+    let file = File::open(path).map_err(|error| Error::io(path, error))?;
+    read_turn_restrictions(BufReader::new(file), path, number_of_vertices)
+}
+
+/// Reads turn restrictions from `reader`. `path` is only used to give errors
+/// some context.
+pub fn read_turn_restrictions<R: BufRead>(
+    reader: R,
+    path: &Path,
+    number_of_vertices: usize,
+) -> Result<TurnRestrictions> {
+    let mut restrictions = TurnRestrictions::default();
+
+    for line in numbered_lines(reader, path) {
+        let (line_number, line) = line?;
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = LineFields::new(path, line_number, &line);
+        let (column, line_type) = fields.next_field("line type")?;
+        let next_turn = |fields: &mut LineFields| -> Result<Turn> {
+            Ok(Turn::new(
+                fields.next_vertex("from", number_of_vertices)?,
+                fields.next_vertex("via", number_of_vertices)?,
+                fields.next_vertex("to", number_of_vertices)?,
+            ))
+        };
+
+        match line_type {
+            "n" => restrictions.forbid(next_turn(&mut fields)?),
+            "o" => restrictions.require(next_turn(&mut fields)?),
+            "c" => {
+                let turn = next_turn(&mut fields)?;
+                let cost = fields.next_integer("cost")?;
+                restrictions.set_cost(turn, cost);
+            }
+            _ => {
+                return Err(fields
+                    .error(
+                        column,
+                        ParseErrorKind::UnknownLineType(line_type.to_string()),
+                    )
+                    .into())
+            }
+        }
+    }
+
+    Ok(restrictions)
+}
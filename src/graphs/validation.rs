@@ -0,0 +1,256 @@
+//! Checks of the graph requirements listed in the README.
+//!
+//! The readers and graph constructors quietly fix some problems, e.g. self
+//! edges are dropped and of parallel edges only the cheapest is kept. A
+//! [`ValidationReport`] lists them instead, together with properties that are
+//! allowed but often hint at a broken export.
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    fmi::FmiFile, reversible_graph::ReversibleGraph,
+    strongly_connected_components::StronglyConnectedComponents, vec_vec_graph::VecVecGraph,
+    Distance, Vertex, WeightedEdge,
+};
+
+/// Number of examples per problem that are shown by the [`fmt::Display`]
+/// implementation.
+const EXAMPLES: usize = 5;
+
+/// The problems found in a list of edges.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub number_of_vertices: u32,
+    /// Number of edges, including self edges and parallel edges.
+    pub number_of_edges: usize,
+    pub number_of_self_edges: usize,
+    /// Vertices without in and out edges, which break the continuous
+    /// numbering.
+    pub isolated_vertices: Vec<Vertex>,
+    /// Edges with the same tail and head as an earlier edge. Graphs keep only
+    /// the cheapest of them.
+    pub parallel_edges: Vec<WeightedEdge>,
+    pub zero_weight_edges: Vec<WeightedEdge>,
+    /// Vertices outside of the largest strongly connected component.
+    pub unreachable_vertices: Vec<Vertex>,
+    /// Edges without an opposite edge of the same weight.
+    pub asymmetric_edges: Vec<WeightedEdge>,
+    pub max_weight: Distance,
+    /// An upper bound of the weight of a shortest path, or `None` if the
+    /// bound does not fit into a [`Distance`].
+    pub max_path_weight: Option<Distance>,
+}
+
+impl ValidationReport {
+    /// Checks `edges` of a graph with at least `number_of_vertices` vertices.
+    pub fn of(number_of_vertices: u32, edges: &[WeightedEdge]) -> Self {
+        let number_of_vertices = edges
+            .iter()
+            .map(|edge| edge.tail.max(edge.head) + 1)
+            .max()
+            .unwrap_or(0)
+            .max(number_of_vertices);
+
+        let mut has_edges = vec![false; number_of_vertices as usize];
+        let mut number_of_self_edges = 0;
+        let mut parallel_edges = Vec::new();
+        let mut zero_weight_edges = Vec::new();
+        // The cheapest weight of each pair of tail and head.
+        let mut weights = HashMap::with_capacity(edges.len());
+        for edge in edges.iter() {
+            has_edges[edge.tail as usize] = true;
+            has_edges[edge.head as usize] = true;
+            if edge.tail == edge.head {
+                number_of_self_edges += 1;
+                continue;
+            }
+            if edge.weight == 0 {
+                zero_weight_edges.push(edge.clone());
+            }
+
+            match weights.entry((edge.tail, edge.head)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(edge.weight);
+                }
+                Entry::Occupied(mut entry) => {
+                    parallel_edges.push(edge.clone());
+                    let weight = entry.get_mut();
+                    *weight = (*weight).min(edge.weight);
+                }
+            }
+        }
+        let isolated_vertices = (0..number_of_vertices)
+            .filter(|&vertex| !has_edges[vertex as usize])
+            .collect();
+
+        let mut asymmetric_edges = weights
+            .iter()
+            .filter(|&(&(tail, head), weight)| weights.get(&(head, tail)) != Some(weight))
+            .map(|(&(tail, head), &weight)| WeightedEdge::new(tail, head, weight))
+            .collect::<Vec<_>>();
+        asymmetric_edges.sort_unstable_by_key(|edge| (edge.tail, edge.head));
+
+        let graph_edges = weights
+            .iter()
+            .map(|(&(tail, head), &weight)| WeightedEdge::new(tail, head, weight))
+            .collect::<Vec<_>>();
+        let components = StronglyConnectedComponents::new(
+            &ReversibleGraph::<VecVecGraph>::with_edges(number_of_vertices, &graph_edges),
+        );
+        let largest_component = components.largest_component();
+        let unreachable_vertices = (0..number_of_vertices)
+            .filter(|&vertex| components.component(vertex) != largest_component)
+            .collect();
+
+        // A shortest path has at most n - 1 edges and uses every edge at most
+        // once.
+        let max_weight = weights.values().max().cloned().unwrap_or(0);
+        let edge_bound =
+            Distance::from(number_of_vertices.saturating_sub(1)).checked_mul(max_weight);
+        let sum_bound = weights
+            .values()
+            .try_fold(0, |sum: Distance, &weight| sum.checked_add(weight));
+        let max_path_weight = match (edge_bound, sum_bound) {
+            (Some(edge_bound), Some(sum_bound)) => Some(edge_bound.min(sum_bound)),
+            (edge_bound, sum_bound) => edge_bound.or(sum_bound),
+        };
+
+        ValidationReport {
+            number_of_vertices,
+            number_of_edges: edges.len(),
+            number_of_self_edges,
+            isolated_vertices,
+            parallel_edges,
+            zero_weight_edges,
+            unreachable_vertices,
+            asymmetric_edges,
+            max_weight,
+            max_path_weight,
+        }
+    }
+
+    /// Checks a fmi file, including the vertices of its header and the self
+    /// edges the reader dropped.
+    pub fn of_fmi_file(fmi: &FmiFile) -> Self {
+        let mut report = ValidationReport::of(fmi.number_of_vertices as u32, &fmi.edges);
+        report.number_of_edges += fmi.number_of_self_edges;
+        report.number_of_self_edges += fmi.number_of_self_edges;
+        report
+    }
+
+    /// Returns true if a shortest path may reach [`Distance::MAX`], which
+    /// searches use as infinity.
+    pub fn may_overflow(&self) -> bool {
+        self.max_path_weight
+            .is_none_or(|max_path_weight| max_path_weight == Distance::MAX)
+    }
+
+    /// Returns true if the graph meets the requirements of the README: no
+    /// isolated vertices, self edges or parallel edges, and no overflow.
+    /// Zero weights, unreachable vertices and asymmetric edges are allowed.
+    pub fn is_valid(&self) -> bool {
+        self.isolated_vertices.is_empty()
+            && self.number_of_self_edges == 0
+            && self.parallel_edges.is_empty()
+            && !self.may_overflow()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} vertices, {} edges, max weight {}, max path weight {}",
+            self.number_of_vertices,
+            self.number_of_edges,
+            self.max_weight,
+            self.max_path_weight
+                .map_or("overflow".to_string(), |weight| weight.to_string())
+        )?;
+        writeln!(f, "self edges: {}", self.number_of_self_edges)?;
+        write_problem(f, "isolated vertices", &self.isolated_vertices)?;
+        write_problem(f, "parallel edges", &self.parallel_edges)?;
+        write_problem(f, "zero weight edges", &self.zero_weight_edges)?;
+        write_problem(f, "unreachable vertices", &self.unreachable_vertices)?;
+        write_problem(f, "asymmetric edges", &self.asymmetric_edges)?;
+        write!(
+            f,
+            "the graph {} the requirements",
+            if self.is_valid() { "meets" } else { "violates" }
+        )
+    }
+}
+
+fn write_problem<T: fmt::Debug>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    found: &[T],
+) -> fmt::Result {
+    write!(f, "{}: {}", name, found.len())?;
+    if !found.is_empty() {
+        write!(f, ", e.g. {:?}", &found[..found.len().min(EXAMPLES)])?;
+    }
+    writeln!(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::ValidationReport;
+    use crate::graphs::{
+        fmi::{read_fmi, FmiOptions},
+        Distance, WeightedEdge,
+    };
+
+    #[test]
+    fn problems_are_reported() {
+        // 0 <-> 1 <-> 2 with a parallel edge, a zero weight edge and a self
+        // edge, 3 can only be left and 4 is isolated.
+        let content = "5\n7\n0 10 48.1 9.1 250\n1 11 48.2 9.2 251\n2 12 48.3 9.3 252\n3 13 \
+                       48.4 9.4 253\n4 14 48.5 9.5 254\n0 1 3\n1 0 3\n1 2 0\n2 1 0\n2 1 7\n1 \
+                       1 4\n3 0 2\n";
+        let fmi = read_fmi(
+            content.as_bytes(),
+            Path::new("test.fmi"),
+            &FmiOptions::default(),
+        )
+        .unwrap();
+        let report = ValidationReport::of_fmi_file(&fmi);
+
+        assert_eq!(report.number_of_vertices, 5);
+        assert_eq!(report.number_of_edges, 7);
+        assert_eq!(report.number_of_self_edges, 1);
+        assert_eq!(report.isolated_vertices, vec![4]);
+        assert_eq!(report.parallel_edges, vec![WeightedEdge::new(2, 1, 7)]);
+        assert_eq!(
+            report.zero_weight_edges,
+            vec![WeightedEdge::new(1, 2, 0), WeightedEdge::new(2, 1, 0)]
+        );
+        assert_eq!(report.unreachable_vertices, vec![3, 4]);
+        assert_eq!(report.asymmetric_edges, vec![WeightedEdge::new(3, 0, 2)]);
+        assert_eq!(report.max_weight, 3);
+        assert_eq!(report.max_path_weight, Some(8));
+        assert!(!report.is_valid());
+
+        let edges = vec![
+            WeightedEdge::new(0, 1, Distance::MAX / 2),
+            WeightedEdge::new(1, 2, Distance::MAX / 2 + 1),
+            WeightedEdge::new(2, 0, 1),
+        ];
+        let report = ValidationReport::of(0, &edges);
+        assert!(report.may_overflow());
+        assert!(!ValidationReport::of(0, &edges[..1]).may_overflow());
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ValidationReport>(&json).unwrap(),
+            report
+        );
+    }
+}
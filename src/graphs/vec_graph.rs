@@ -1,7 +1,9 @@
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
-use super::{Distance, Edge, Graph, TaillessEdge, Vertex, WeightedEdge};
+use super::{
+    Distance, Edge, EdgeSlices, Graph, GraphFromEdges, TaillessEdge, Vertex, WeightedEdge,
+};
 
 #[derive(Serialize, Deserialize)]
 pub struct VecGraph {
@@ -36,6 +38,40 @@ impl VecGraph {
     }
 }
 
+impl GraphFromEdges for VecGraph {
+    fn with_edges(number_of_vertices: u32, edges: &[WeightedEdge]) -> Self {
+        // `from_edges` never inserts an edge of weight `Distance::MAX`.
+        let mut edges: Vec<WeightedEdge> = edges
+            .iter()
+            .filter(|edge| edge.weight < Distance::MAX)
+            .cloned()
+            .collect();
+        edges.sort_unstable_by_key(|edge| (edge.tail, edge.head, edge.weight));
+        edges.dedup_by_key(|edge| (edge.tail, edge.head));
+        for edge in edges.iter() {
+            assert!(
+                edge.tail < number_of_vertices,
+                "tail {} is out of range",
+                edge.tail
+            );
+            assert!(
+                edge.head < number_of_vertices,
+                "head {} is out of range",
+                edge.head
+            );
+        }
+
+        VecGraph::new(&edges, &(0..number_of_vertices).collect())
+    }
+}
+
+impl EdgeSlices for VecGraph {
+    fn edge_slice(&self, tail: Vertex) -> &[TaillessEdge] {
+        let &(start_index, stop_index) = self.indices.get(tail as usize).unwrap_or(&(0, 0));
+        &self.edges[start_index as usize..stop_index as usize]
+    }
+}
+
 impl Graph for VecGraph {
     fn number_of_vertices(&self) -> u32 {
         self.indices.len() as u32
@@ -78,7 +114,7 @@ impl Graph for VecGraph {
         if let Ok(index) = self.edges[start_index as usize..stop_index as usize]
             .binary_search_by_key(&edge.head, |edge| edge.head)
         {
-            return Some(self.edges[index].weight);
+            return Some(self.edges[start_index as usize + index].weight);
         }
         None
     }
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use super::{Distance, Edge, Graph, Vertex, WeightedEdge};
+use super::{Distance, Edge, Graph, GraphFromEdges, StaticGraph, Vertex, WeightedEdge};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct VecHashGraph {
@@ -33,6 +33,65 @@ impl VecHashGraph {
     }
 }
 
+impl GraphFromEdges for VecHashGraph {
+    fn with_edges(number_of_vertices: u32, edges: &[WeightedEdge]) -> Self {
+        let mut adjacency_maps = vec![HashMap::new(); number_of_vertices as usize];
+        // `from_edges` never inserts an edge of weight `Distance::MAX`.
+        for edge in edges.iter().filter(|edge| edge.weight < Distance::MAX) {
+            assert!(
+                edge.head < number_of_vertices,
+                "head {} is out of range",
+                edge.head
+            );
+            let weight = adjacency_maps
+                .get_mut(edge.tail as usize)
+                .unwrap_or_else(|| panic!("tail {} is out of range", edge.tail))
+                .entry(edge.head)
+                .or_insert(edge.weight);
+            *weight = (*weight).min(edge.weight);
+        }
+
+        VecHashGraph {
+            edges: adjacency_maps,
+        }
+    }
+}
+
+impl StaticGraph for VecHashGraph {
+    type Edges<'a> = HashEdges<'a>;
+
+    fn out_edges(&self, tail: Vertex) -> HashEdges<'_> {
+        HashEdges {
+            edges: self.edges.get(tail as usize).map(|edges| edges.iter()),
+            tail,
+        }
+    }
+}
+
+/// Out edges of a vertex of a [`VecHashGraph`].
+pub struct HashEdges<'a> {
+    /// `None` if the tail is out of range.
+    edges: Option<std::collections::hash_map::Iter<'a, Vertex, Distance>>,
+    tail: Vertex,
+}
+
+impl Iterator for HashEdges<'_> {
+    type Item = WeightedEdge;
+
+    fn next(&mut self) -> Option<WeightedEdge> {
+        let (&head, &weight) = self.edges.as_mut()?.next()?;
+        Some(WeightedEdge::new(self.tail, head, weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edges
+            .as_ref()
+            .map_or((0, Some(0)), |edges| edges.size_hint())
+    }
+}
+
+impl ExactSizeIterator for HashEdges<'_> {}
+
 impl Graph for VecHashGraph {
     fn number_of_vertices(&self) -> u32 {
         self.edges.len() as u32
@@ -1,6 +1,12 @@
+use rayon::{
+    iter::{IntoParallelRefIterator, ParallelIterator},
+    slice::ParallelSliceMut,
+};
 use serde::{Deserialize, Serialize};
 
-use super::{Distance, Edge, Graph, TaillessEdge, Vertex, WeightedEdge};
+use super::{
+    Distance, Edge, EdgeSlices, Graph, GraphFromEdges, TaillessEdge, Vertex, WeightedEdge,
+};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct VecVecGraph {
@@ -29,6 +35,59 @@ impl VecVecGraph {
 
         graph
     }
+
+    /// Builds the same graph as [`VecVecGraph::from_edges`], but sorts the
+    /// edges once in parallel instead of inserting them one by one. Of
+    /// parallel edges, the one with the smallest weight is kept.
+    pub fn from_edges_in_bulk(edges: &[WeightedEdge]) -> VecVecGraph {
+        // `from_edges` never inserts an edge of weight `Distance::MAX`.
+        let mut edges: Vec<WeightedEdge> = edges
+            .par_iter()
+            .filter(|edge| edge.weight < Distance::MAX)
+            .cloned()
+            .collect();
+        edges.par_sort_unstable_by_key(|edge| (edge.tail, edge.head, edge.weight));
+        edges.dedup_by_key(|edge| (edge.tail, edge.head));
+
+        let number_of_vertices = edges
+            .par_iter()
+            .map(|edge| std::cmp::max(edge.tail, edge.head) as usize + 1)
+            .max()
+            .unwrap_or(0);
+
+        let mut adjacency_lists = vec![Vec::new(); number_of_vertices];
+        for edges_sharing_tail in edges.chunk_by(|a, b| a.tail == b.tail) {
+            adjacency_lists[edges_sharing_tail[0].tail as usize] = edges_sharing_tail
+                .iter()
+                .map(|edge| TaillessEdge {
+                    head: edge.head,
+                    weight: edge.weight,
+                })
+                .collect();
+        }
+
+        VecVecGraph {
+            edges: adjacency_lists,
+        }
+    }
+}
+
+impl GraphFromEdges for VecVecGraph {
+    fn with_edges(number_of_vertices: u32, edges: &[WeightedEdge]) -> Self {
+        let mut graph = VecVecGraph::from_edges_in_bulk(edges);
+        assert!(
+            graph.edges.len() <= number_of_vertices as usize,
+            "an edge points to a vertex out of range"
+        );
+        graph.edges.resize(number_of_vertices as usize, Vec::new());
+        graph
+    }
+}
+
+impl EdgeSlices for VecVecGraph {
+    fn edge_slice(&self, tail: Vertex) -> &[TaillessEdge] {
+        self.edges.get(tail as usize).map_or(&[], Vec::as_slice)
+    }
 }
 
 impl Graph for VecVecGraph {
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    permutation::{Permutation, Renumber},
+    reversible_graph::ReversibleGraph,
+    Graph, GraphFromEdges, Vertex,
+};
+use crate::error::Result;
+
+/// Geographic data of a single vertex.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VertexAttribute {
+    pub osm_id: Option<u64>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub elevation: Option<f64>,
+}
+
+impl VertexAttribute {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        VertexAttribute {
+            osm_id: None,
+            latitude,
+            longitude,
+            elevation: None,
+        }
+    }
+}
+
+/// Attributes of all vertices of a graph, indexed by [`Vertex`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VertexAttributes {
+    attributes: Vec<VertexAttribute>,
+}
+
+impl VertexAttributes {
+    pub fn new(attributes: Vec<VertexAttribute>) -> Self {
+        VertexAttributes { attributes }
+    }
+
+    pub fn get(&self, vertex: Vertex) -> Option<&VertexAttribute> {
+        self.attributes.get(vertex as usize)
+    }
+
+    /// Returns `(latitude, longitude)` of a vertex.
+    pub fn coordinate(&self, vertex: Vertex) -> Option<(f64, f64)> {
+        self.get(vertex)
+            .map(|attribute| (attribute.latitude, attribute.longitude))
+    }
+
+    pub fn number_of_vertices(&self) -> u32 {
+        self.attributes.len() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, VertexAttribute> {
+        self.attributes.iter()
+    }
+
+    /// Keeps the attributes of the vertices `0..number_of_vertices`, see
+    /// [`ReversibleGraph::restricted_to`].
+    pub fn restricted_to(&self, number_of_vertices: u32) -> VertexAttributes {
+        let end = self.attributes.len().min(number_of_vertices as usize);
+        VertexAttributes::new(self.attributes[..end].to_vec())
+    }
+}
+
+impl Renumber for VertexAttributes {
+    /// Like edges of a renumbered graph, attributes of vertices out of range
+    /// are ignored. The attributes end before the first vertex whose old
+    /// vertex has none, e.g. they stay empty if the graph was read without
+    /// attributes.
+    fn renumbered(&self, permutation: &Permutation) -> Self {
+        let attributes = permutation
+            .new_to_old()
+            .iter()
+            .map_while(|&old_vertex| self.attributes.get(old_vertex as usize).cloned())
+            .collect();
+
+        VertexAttributes { attributes }
+    }
+}
+
+/// A graph together with the attributes of its vertices.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AttributedGraph<G: Graph> {
+    pub graph: ReversibleGraph<G>,
+    pub attributes: VertexAttributes,
+}
+
+impl<G: Graph + Default> AttributedGraph<G> {
+    pub fn try_from_fmi_file(path: &std::path::Path) -> Result<Self> {
+        let (graph, attributes) = ReversibleGraph::try_from_fmi_file_with_attributes(path)?;
+        Ok(AttributedGraph { graph, attributes })
+    }
+}
+
+impl<G: GraphFromEdges> AttributedGraph<G> {
+    /// Restricts the graph and its attributes in sync, see
+    /// [`ReversibleGraph::restricted_to`].
+    pub fn restricted_to(&self, number_of_vertices: u32) -> Self {
+        AttributedGraph {
+            graph: self.graph.restricted_to(number_of_vertices),
+            attributes: self.attributes.restricted_to(number_of_vertices),
+        }
+    }
+}
+
+impl<G: GraphFromEdges> Renumber for AttributedGraph<G> {
+    /// Renumbers the graph and its attributes in sync.
+    fn renumbered(&self, permutation: &Permutation) -> Self {
+        AttributedGraph {
+            graph: self.graph.renumbered(permutation),
+            attributes: self.attributes.renumbered(permutation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttributedGraph, VertexAttribute, VertexAttributes};
+    use crate::graphs::{
+        permutation::{Permutation, Renumber},
+        reversible_graph::ReversibleGraph,
+        vec_vec_graph::VecVecGraph,
+        Graph, WeightedEdge,
+    };
+
+    #[test]
+    fn renumbering_keeps_graph_and_attributes_in_sync() {
+        // Vertices 0 and 3 have no edges, vertex v is at latitude v.
+        let edges = vec![WeightedEdge::new(1, 2, 3), WeightedEdge::new(2, 1, 4)];
+        let graph = AttributedGraph {
+            graph: ReversibleGraph::<VecVecGraph>::with_edges(4, &edges),
+            attributes: VertexAttributes::new(
+                (0..4)
+                    .map(|vertex| VertexAttribute::new(vertex as f64, 0.0))
+                    .collect(),
+            ),
+        };
+
+        let (permutation, number_of_vertices_with_edges) = Permutation::compacting(&graph.graph);
+        let renumbered = graph.renumbered(&permutation);
+        assert_eq!(renumbered.graph.out_graph().number_of_vertices(), 4);
+        assert_eq!(renumbered.attributes.number_of_vertices(), 4);
+        for vertex in 0..4 {
+            assert_eq!(
+                renumbered.attributes.coordinate(vertex),
+                graph.attributes.coordinate(permutation.to_old(vertex))
+            );
+        }
+
+        let restricted = renumbered.restricted_to(number_of_vertices_with_edges);
+        assert_eq!(restricted.graph.out_graph().number_of_vertices(), 2);
+        assert_eq!(restricted.attributes.number_of_vertices(), 2);
+        assert_eq!(
+            restricted.graph.out_graph().all_edges(),
+            vec![WeightedEdge::new(0, 1, 3), WeightedEdge::new(1, 0, 4)]
+        );
+
+        // Attributes of a graph read without them stay empty.
+        let without_attributes = AttributedGraph {
+            graph: graph.graph.clone(),
+            attributes: VertexAttributes::default(),
+        };
+        assert!(without_attributes
+            .renumbered(&permutation)
+            .attributes
+            .is_empty());
+    }
+}
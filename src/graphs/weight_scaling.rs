@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+use super::Distance;
+use crate::error::ParseErrorKind;
+
+/// How a scaled weight is turned into an integer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Rounding {
+    #[default]
+    Round,
+    Floor,
+    Ceil,
+}
+
+/// Converts weights given as decimal numbers into [`Distance`]s by
+/// multiplying them with `factor` and rounding the result. For example, a
+/// factor of 1000 keeps three decimal places.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WeightScaling {
+    factor: f64,
+    rounding: Rounding,
+}
+
+impl Default for WeightScaling {
+    fn default() -> Self {
+        WeightScaling {
+            factor: 1.0,
+            rounding: Rounding::Round,
+        }
+    }
+}
+
+impl WeightScaling {
+    /// Returns `None` if `factor` is not positive and finite, as every weight
+    /// would then be rejected or become 0.
+    pub fn new(factor: f64, rounding: Rounding) -> Option<Self> {
+        (factor.is_finite() && factor > 0.0).then_some(WeightScaling { factor, rounding })
+    }
+
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.factor == 1.0
+    }
+
+    /// Scales and rounds `value`, recording the loss of precision in `report`.
+    /// Returns `None` for negative or non-finite values and for results that
+    /// reach `Distance::MAX`, which searches use as infinity.
+    pub fn convert(&self, value: f64, report: &mut WeightReport) -> Option<Distance> {
+        let scaled = value * self.factor;
+        let rounded = match self.rounding {
+            Rounding::Round => scaled.round(),
+            Rounding::Floor => scaled.floor(),
+            Rounding::Ceil => scaled.ceil(),
+        };
+        // `Distance::MAX as f64` rounds up to 2^64, so this also rejects every
+        // result that would be clamped to `Distance::MAX`.
+        if !(0.0..Distance::MAX as f64).contains(&rounded) || value < 0.0 {
+            return None;
+        }
+        let weight = rounded as Distance;
+
+        report.number_of_weights += 1;
+        // Allow for the error of the multiplication itself, e.g. 2.4 * 10.
+        if (scaled - rounded).abs() > f64::EPSILON * scaled.abs().max(1.0) * 4.0 {
+            report.lost_precision += 1;
+        }
+        if weight == 0 && value > 0.0 {
+            report.rounded_to_zero += 1;
+        }
+
+        Some(weight)
+    }
+
+    /// Converts the weight field `field`. Integers are taken as they are if the
+    /// scaling is the identity, so that large weights do not pass through
+    /// `f64`.
+    pub fn convert_str(
+        &self,
+        field: &'static str,
+        value: &str,
+        report: &mut WeightReport,
+    ) -> Result<Distance, ParseErrorKind> {
+        if self.is_identity() {
+            if let Ok(weight) = value.parse::<Distance>() {
+                if weight == Distance::MAX {
+                    return Err(invalid_weight(field, value));
+                }
+                report.number_of_weights += 1;
+                return Ok(weight);
+            }
+        }
+
+        let number = value
+            .parse::<f64>()
+            .map_err(|_| ParseErrorKind::InvalidNumber {
+                field,
+                value: value.to_string(),
+            })?;
+        self.convert(number, report)
+            .ok_or_else(|| invalid_weight(field, value))
+    }
+}
+
+pub(crate) fn invalid_weight(field: &'static str, value: &str) -> ParseErrorKind {
+    ParseErrorKind::InvalidWeight {
+        field,
+        value: value.to_string(),
+    }
+}
+
+/// Summary of the precision that was lost while converting weights, used to
+/// choose a [`WeightScaling`] that keeps the graph intact.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeightReport {
+    pub number_of_weights: u64,
+    /// Weights that were positive before and are zero after the conversion.
+    pub rounded_to_zero: u64,
+    /// Weights that had to be rounded.
+    pub lost_precision: u64,
+}
+
+impl WeightReport {
+    pub fn merge(&mut self, other: &WeightReport) {
+        self.number_of_weights += other.number_of_weights;
+        self.rounded_to_zero += other.rounded_to_zero;
+        self.lost_precision += other.lost_precision;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rounding, WeightReport, WeightScaling};
+    use crate::error::ParseErrorKind;
+
+    #[test]
+    fn scales_and_reports() {
+        let mut report = WeightReport::default();
+        let scaling = WeightScaling::new(10.0, Rounding::Floor).unwrap();
+
+        assert_eq!(scaling.convert(2.4, &mut report), Some(24));
+        assert_eq!(scaling.convert(-0.0, &mut report), Some(0));
+        assert_eq!(scaling.convert(0.05, &mut report), Some(0));
+        assert_eq!(scaling.convert(0.0, &mut report), Some(0));
+        assert_eq!(
+            WeightScaling::new(10.0, Rounding::Ceil)
+                .unwrap()
+                .convert(0.05, &mut report),
+            Some(1)
+        );
+        for value in [-1.0, -0.01, f64::NAN, f64::INFINITY, 2e18] {
+            assert_eq!(scaling.convert(value, &mut report), None);
+        }
+
+        assert_eq!(
+            report,
+            WeightReport {
+                number_of_weights: 5,
+                rounded_to_zero: 1,
+                lost_precision: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_factors() {
+        for factor in [0.0, -0.0, -10.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(WeightScaling::new(factor, Rounding::Round), None);
+        }
+    }
+
+    #[test]
+    fn keeps_large_integers() {
+        let mut report = WeightReport::default();
+        let weight = u64::MAX - 1;
+
+        assert_eq!(
+            WeightScaling::default().convert_str("weight", &weight.to_string(), &mut report),
+            Ok(weight)
+        );
+        assert_eq!(report.lost_precision, 0);
+
+        for value in [u64::MAX.to_string().as_str(), "-3", "NaN", "inf"] {
+            assert_eq!(
+                WeightScaling::default().convert_str("weight", value, &mut report),
+                Err(ParseErrorKind::InvalidWeight {
+                    field: "weight",
+                    value: value.to_string(),
+                })
+            );
+        }
+    }
+}
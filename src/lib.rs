@@ -1,41 +1,258 @@
-use std::{fs::File, io::BufReader, path::Path};
+use std::{fs::File, io::Read, path::Path};
 
-use clap::ValueEnum;
-use graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph};
-use search::{ch::contracted_graph::ContractedGraph, hl::hub_graph::HubGraph, PathFinding};
+use bincode::Options;
+use container::{read_container_file, GraphFingerprint, PayloadKind};
+use error::{ContainerErrorKind, Error, Result};
+use graphs::{
+    binary_graph::{self, read_binary_graph_file},
+    dimacs::try_read_edges_from_gr_file,
+    reversible_graph::ReversibleGraph,
+    vec_vec_graph::VecVecGraph,
+};
+use log::{info, warn};
+use search::{
+    ch::{
+        contracted_graph::ContractedGraph,
+        mapped_contracted_graph::{self, MappedContractedGraph},
+    },
+    hl::{
+        hub_graph::HubGraph,
+        mapped_hub_graph::{self, MappedHubGraph},
+    },
+    PathFinding,
+};
+use serde::de::DeserializeOwned;
 use utility::get_progressspinner;
 
+pub mod container;
+pub mod error;
+pub mod geojson;
 pub mod graphs;
+mod mapped_file;
 pub mod search;
+pub mod stats;
 pub mod utility;
 
-#[derive(Debug, ValueEnum, Clone)]
+/// The kinds of files [`reading_pathfinder`] can read, see
+/// [`detect_file_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileType {
+    /// A container holding a contracted graph, see [`container`].
     CH,
+    /// A contracted graph in the memory mapped layout, see
+    /// [`search::ch::mapped_contracted_graph`].
+    MappedCH,
+    /// A container holding a hub graph, see [`container`].
     HL,
+    /// A hub graph in the memory mapped layout, see
+    /// [`search::hl::mapped_hub_graph`].
+    MappedHL,
+    /// A container holding a reversible graph, see [`container`].
+    Graph,
+    /// A graph in the compact binary format, see [`graphs::binary_graph`].
+    BinaryGraph,
+    /// A `.fmi` text file, see [`graphs::fmi`].
     FMI,
+    /// A DIMACS `.gr` file, see [`graphs::dimacs`].
+    DIMACS,
+    /// Plain bincode written before the container format existed. Whether it
+    /// holds a contracted graph, a hub graph or a reversible graph is only
+    /// known after deserializing it.
+    Bincode,
 }
 
-pub fn reading_pathfinder(path: &Path, file_type: &FileType) -> Box<dyn PathFinding> {
+/// Number of bytes at the start of a file that are looked at to detect its
+/// type.
+const SNIFF_LENGTH: usize = 4096;
+
+/// Detects the type of the file at `path` by its magic bytes or, for text
+/// files, by its first line. Files that are neither are assumed to be plain
+/// bincode.
+pub fn detect_file_type(path: &Path) -> Result<FileType> {
+    let mut bytes = Vec::with_capacity(SNIFF_LENGTH);
+    File::open(path)
+        .and_then(|file| file.take(SNIFF_LENGTH as u64).read_to_end(&mut bytes))
+        .map_err(|error| Error::io(path, error))?;
+    Ok(sniff_file_type(&bytes))
+}
+
+fn sniff_file_type(bytes: &[u8]) -> FileType {
+    if container::has_magic(bytes) {
+        let header = container::read_header(&mut &bytes[..], Path::new(""));
+        return match header.map(|header| header.kind) {
+            Ok(PayloadKind::ContractedGraph) => FileType::CH,
+            Ok(PayloadKind::HubGraph) => FileType::HL,
+            // A permutation, an edge based mapping, a newer version or an
+            // unknown kind is reported when reading.
+            Ok(
+                PayloadKind::ReversibleGraph
+                | PayloadKind::Permutation
+                | PayloadKind::EdgeBasedMapping,
+            )
+            | Err(_) => FileType::Graph,
+        };
+    }
+    if bytes.starts_with(&binary_graph::MAGIC) {
+        return FileType::BinaryGraph;
+    }
+    if bytes.starts_with(&mapped_contracted_graph::MAGIC) {
+        return FileType::MappedCH;
+    }
+    if bytes.starts_with(&mapped_hub_graph::MAGIC) {
+        return FileType::MappedHL;
+    }
+
+    // The sniffed bytes may end within a multi byte character.
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(error) if bytes.len() - error.valid_up_to() < 4 && error.error_len().is_none() => {
+            std::str::from_utf8(&bytes[..error.valid_up_to()]).unwrap()
+        }
+        Err(_) => return FileType::Bincode,
+    };
+
+    let first_line = text.lines().map(str::trim).find(|line| !line.is_empty());
+    match first_line.and_then(|line| line.split_whitespace().next()) {
+        Some("c" | "p" | "a") => FileType::DIMACS,
+        Some(token) if token.starts_with('#') || token.parse::<u64>().is_ok() => FileType::FMI,
+        _ => FileType::Bincode,
+    }
+}
+
+/// Reads a pathfinder of any [`FileType`], detected by [`detect_file_type`].
+/// Containers have their magic bytes, version, payload kind and checksum
+/// checked before deserializing. If `expected_fingerprint` is given, the
+/// pathfinder must have been built from that graph, or be that graph.
+pub fn reading_pathfinder(
+    path: &Path,
+    expected_fingerprint: Option<&GraphFingerprint>,
+) -> Result<Box<dyn PathFinding>> {
+    let file_type = detect_file_type(path)?;
+    info!("reading {} as {:?}", path.display(), file_type);
+
+    let pb = get_progressspinner(format!("Reading {}", path.display()).as_str());
+    let check_fingerprint = |fingerprint: GraphFingerprint| match expected_fingerprint {
+        Some(expected) if *expected != fingerprint => {
+            let kind = ContainerErrorKind::FingerprintMismatch {
+                expected: *expected,
+                found: fingerprint,
+            };
+            Err(Error::container(path, kind))
+        }
+        _ => Ok(()),
+    };
+
     let pathfinder: Box<dyn PathFinding> = match file_type {
-        FileType::CH => {
-            let spinner = get_progressspinner("Reading contracted graph");
-            let reader = BufReader::new(File::open(path).unwrap());
-            let contractes_graph: ContractedGraph = bincode::deserialize_from(reader).unwrap();
-            spinner.finish_and_clear();
-            Box::new(contractes_graph)
-        }
-        FileType::HL => {
-            let spinner = get_progressspinner("Reading hub graph");
-            let reader = BufReader::new(File::open(path).unwrap());
-            let hub_graph: HubGraph = bincode::deserialize_from(reader).unwrap();
-            spinner.finish_and_clear();
+        FileType::CH => Box::new(read_container_file::<ContractedGraph>(
+            path,
+            expected_fingerprint,
+        )?),
+        FileType::MappedCH => {
+            let contracted_graph = MappedContractedGraph::open(path)?;
+            check_fingerprint(contracted_graph.fingerprint())?;
+            Box::new(contracted_graph)
+        }
+        FileType::HL => Box::new(read_container_file::<HubGraph>(path, expected_fingerprint)?),
+        FileType::MappedHL => {
+            let hub_graph = MappedHubGraph::open(path)?;
+            check_fingerprint(hub_graph.fingerprint())?;
             Box::new(hub_graph)
         }
+        FileType::Graph => Box::new(read_container_file::<ReversibleGraph<VecVecGraph>>(
+            path,
+            expected_fingerprint,
+        )?),
+        FileType::BinaryGraph => {
+            let graph = read_binary_graph_file::<VecVecGraph>(path)?.graph;
+            check_fingerprint(GraphFingerprint::of(graph.out_graph()))?;
+            Box::new(graph)
+        }
         FileType::FMI => {
-            let graph = ReversibleGraph::<VecVecGraph>::from_fmi_file(path);
+            let graph = ReversibleGraph::<VecVecGraph>::try_from_fmi_file_in_parallel(path)?;
+            check_fingerprint(GraphFingerprint::of(graph.out_graph()))?;
             Box::new(graph)
         }
+        FileType::DIMACS => {
+            let edges = try_read_edges_from_gr_file(path)?;
+            let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+            check_fingerprint(GraphFingerprint::of(graph.out_graph()))?;
+            Box::new(graph)
+        }
+        FileType::Bincode => {
+            warn!(
+                "{} is not a container, reading it as plain bincode without any checks",
+                path.display()
+            );
+            read_plain_bincode(path)?
+        }
+    };
+
+    pb.finish_and_clear();
+    Ok(pathfinder)
+}
+
+/// Deserializes a hub graph, a contracted graph or a reversible graph from
+/// plain bincode. The first type that consumes the whole file is taken.
+fn read_plain_bincode(path: &Path) -> Result<Box<dyn PathFinding>> {
+    let bytes = std::fs::read(path).map_err(|error| Error::io(path, error))?;
+
+    // Same encoding as `bincode::serialize`, but length prefixes of garbage
+    // can not allocate more than the file size.
+    fn deserialize_exactly<T: DeserializeOwned>(bytes: &[u8]) -> Option<T> {
+        let mut reader = bytes;
+        let value = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .with_limit(bytes.len() as u64)
+            .deserialize_from(&mut reader)
+            .ok()?;
+        reader.is_empty().then_some(value)
+    }
+
+    if let Some(hub_graph) = deserialize_exactly::<HubGraph>(&bytes) {
+        return Ok(Box::new(hub_graph));
+    }
+    if let Some(contracted_graph) = deserialize_exactly::<ContractedGraph>(&bytes) {
+        return Ok(Box::new(contracted_graph));
+    }
+    if let Some(graph) = deserialize_exactly::<ReversibleGraph<VecVecGraph>>(&bytes) {
+        return Ok(Box::new(graph));
+    }
+    Err(Error::UnknownFileType {
+        path: path.to_path_buf(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{sniff_file_type, FileType};
+    use crate::{
+        container::{write_container, GraphFingerprint},
+        graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, WeightedEdge},
     };
-    pathfinder
+
+    #[test]
+    fn sniffs_file_types() {
+        let graph = ReversibleGraph::<VecVecGraph>::from_edges(&vec![WeightedEdge::new(0, 1, 2)]);
+        let fingerprint = GraphFingerprint::of(graph.out_graph());
+
+        let mut container = Vec::new();
+        write_container(&mut container, Path::new("test"), &graph, &fingerprint).unwrap();
+        assert_eq!(sniff_file_type(&container), FileType::Graph);
+
+        let plain = bincode::serialize(&graph).unwrap();
+        assert_eq!(sniff_file_type(&plain), FileType::Bincode);
+
+        assert_eq!(
+            sniff_file_type(b"c comment\np sp 2 1\na 1 2 2\n"),
+            FileType::DIMACS
+        );
+        assert_eq!(
+            sniff_file_type(b"# Id : 1\n\n2\n1\n0 0 0 0 0\n"),
+            FileType::FMI
+        );
+        assert_eq!(sniff_file_type(b"2\n1\n"), FileType::FMI);
+    }
 }
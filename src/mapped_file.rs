@@ -0,0 +1,128 @@
+//! Helpers for the memory mapped file layouts, see
+//! [`crate::search::hl::mapped_hub_graph`] and
+//! [`crate::search::ch::mapped_contracted_graph`].
+//!
+//! Such a file starts with a header whose first 12 bytes are magic bytes and
+//! a u32 version. The header and every following section start at a multiple
+//! of 8 bytes, so that they can be cast in place.
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    mem::size_of,
+    ops::Range,
+    path::Path,
+};
+
+use bytemuck::Pod;
+use memmap2::Mmap;
+
+use crate::error::{ContainerErrorKind, Error, Result};
+
+/// Maps the file at `path` and returns the mapping together with its header,
+/// after checking the magic bytes and the version.
+///
+/// The file must not be modified while it is mapped.
+pub(crate) fn map_file<H: Pod>(path: &Path, magic: [u8; 8], version: u32) -> Result<(Mmap, H)> {
+    let file = File::open(path).map_err(|error| Error::io(path, error))?;
+    // Safety: the file is only read, and it is documented that it must not be
+    // modified while it is mapped.
+    let mmap = unsafe { Mmap::map(&file) }.map_err(|error| Error::io(path, error))?;
+
+    let header_bytes = mmap
+        .get(..size_of::<H>())
+        .filter(|bytes| bytes.starts_with(&magic))
+        .ok_or_else(|| Error::container(path, ContainerErrorKind::MissingMagic))?;
+
+    let found = u32::from_ne_bytes(header_bytes[8..12].try_into().unwrap());
+    if found != version {
+        let kind = ContainerErrorKind::UnsupportedVersion {
+            found,
+            supported: version,
+        };
+        return Err(Error::container(path, kind));
+    }
+
+    let header = bytemuck::pod_read_unaligned(header_bytes);
+    Ok((mmap, header))
+}
+
+/// Computes the byte ranges of consecutive sections.
+pub(crate) struct SectionLayout {
+    position: usize,
+}
+
+impl SectionLayout {
+    /// Starts the layout after a header of type `H`.
+    pub(crate) fn after_header<H>() -> Self {
+        SectionLayout {
+            position: size_of::<H>().next_multiple_of(8),
+        }
+    }
+
+    /// Returns the range of the next section of `length` values of type `T`,
+    /// or `None` if the range overflows.
+    pub(crate) fn next<T>(&mut self, length: u64) -> Option<Range<usize>> {
+        let length = usize::try_from(length).ok()?.checked_mul(size_of::<T>())?;
+        let start = self.position;
+        let end = start.checked_add(length)?;
+        self.position = end.checked_next_multiple_of(8)?;
+        Some(start..end)
+    }
+}
+
+/// Casts a section of a mapping into its values.
+pub(crate) fn section<'a, T: Pod>(mmap: &'a Mmap, range: &Range<usize>) -> &'a [T] {
+    // The mapping is page aligned and every section starts at a multiple of 8
+    // bytes, so the cast is always aligned.
+    bytemuck::cast_slice(&mmap[range.clone()])
+}
+
+/// Returns `values[offsets[index]..offsets[index + 1]]`, or an empty slice if
+/// `index` or the offsets are out of range.
+pub(crate) fn row<'a, T>(offsets: &[u64], values: &'a [T], index: usize) -> &'a [T] {
+    match (offsets.get(index), offsets.get(index + 1)) {
+        (Some(&start), Some(&stop)) => values.get(start as usize..stop as usize).unwrap_or(&[]),
+        _ => &[],
+    }
+}
+
+/// Returns the offsets of rows with the given lengths, starting at 0.
+pub(crate) fn offsets(lengths: impl Iterator<Item = usize>) -> Vec<u64> {
+    let mut offsets = vec![0];
+    for length in lengths {
+        offsets.push(offsets.last().unwrap() + length as u64);
+    }
+    offsets
+}
+
+/// Writes the header and the sections of a file, padding each to a multiple
+/// of 8 bytes.
+pub(crate) struct SectionWriter<W: Write> {
+    writer: W,
+    position: usize,
+}
+
+impl<W: Write> SectionWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        SectionWriter {
+            writer,
+            position: 0,
+        }
+    }
+
+    pub(crate) fn write<T: Pod>(&mut self, values: &[T]) -> io::Result<()> {
+        let bytes: &[u8] = bytemuck::cast_slice(values);
+        self.writer.write_all(bytes)?;
+        self.position += bytes.len();
+
+        let padding = self.position.next_multiple_of(8) - self.position;
+        self.writer.write_all(&[0; 8][..padding])?;
+        self.position += padding;
+        Ok(())
+    }
+
+    pub(crate) fn finish(mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
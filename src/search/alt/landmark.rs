@@ -4,7 +4,7 @@ use rand::{seq::IteratorRandom, thread_rng};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{
-    graphs::{reversible_graph::ReversibleGraph, Distance, Graph, Vertex},
+    graphs::{reversible_graph::ReversibleGraph, Distance, Graph, StaticGraph, Vertex},
     search::{
         collections::dijkstra_data::DijkstraData, dijkstra::dijkstra_one_to_all_wraped,
         DistanceHeuristic, PathFinding,
@@ -17,7 +17,10 @@ pub struct Landmarks {
 }
 
 impl Landmarks {
-    pub fn new<G: Graph + Default>(graph: &ReversibleGraph<G>, vertices: &[Vertex]) -> Landmarks {
+    pub fn new<G: Graph + StaticGraph + Default>(
+        graph: &ReversibleGraph<G>,
+        vertices: &[Vertex],
+    ) -> Landmarks {
         let landmarks = vertices
             .par_iter()
             .progress_with(get_progressbar(
@@ -30,7 +33,7 @@ impl Landmarks {
         Landmarks { landmarks }
     }
 
-    pub fn hitting_set<G: Graph + Default>(
+    pub fn hitting_set<G: Graph + StaticGraph + Default>(
         graph: &ReversibleGraph<G>,
         number_of_paths: u32,
         number_of_landmarks: u32,
@@ -54,7 +57,7 @@ impl Landmarks {
         )
     }
 
-    pub fn random<G: Graph + Default>(
+    pub fn random<G: Graph + StaticGraph + Default>(
         graph: &ReversibleGraph<G>,
         number_of_landmarks: u32,
     ) -> Landmarks {
@@ -101,7 +104,10 @@ pub struct Landmark {
 }
 
 impl Landmark {
-    pub fn new<G: Graph + Default>(graph: &ReversibleGraph<G>, vertex: Vertex) -> Self {
+    pub fn new<G: Graph + StaticGraph + Default>(
+        graph: &ReversibleGraph<G>,
+        vertex: Vertex,
+    ) -> Self {
         let out_graph_data = dijkstra_one_to_all_wraped(graph.out_graph(), vertex);
         let distance_to = (0..graph.out_graph().number_of_vertices())
             .map(|vertex| out_graph_data.get_distance(vertex))
@@ -141,8 +147,31 @@ impl DistanceHeuristic for Landmark {
         let distance_from_source = self.distance_from[source as usize];
         let distance_to_target = self.distance_to[target as usize];
 
-        distance_from_source
-            .checked_add(distance_to_target)
-            .unwrap_or(Distance::MAX)
+        distance_from_source.saturating_add(distance_to_target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Landmarks;
+    use crate::{
+        graphs::{generators::generated_test_graphs, Graph},
+        search::DistanceHeuristic,
+    };
+
+    #[test]
+    fn landmarks_bound_distances_of_generated_graphs() {
+        for (graph, tests) in generated_test_graphs() {
+            let number_of_vertices = Graph::number_of_vertices(graph.out_graph());
+            let landmarks = Landmarks::new(&graph, &[0, number_of_vertices - 1]);
+
+            for test in tests {
+                let Some(distance) = test.distance else {
+                    continue;
+                };
+                assert!(landmarks.lower_bound(test.source, test.target) <= distance);
+                assert!(distance <= landmarks.upper_bound(test.source, test.target));
+            }
+        }
     }
 }
@@ -46,7 +46,7 @@ pub fn par_simulate_contraction_heuristic<G: Graph>(
                     continue;
                 }
 
-                let shortcut_distance = in_edge.weight + out_edge.weight;
+                let shortcut_distance = in_edge.weight.saturating_add(out_edge.weight);
                 if heuristic.is_less_or_equal_upper_bound(tail, head, shortcut_distance) {
                     let edge = WeightedEdge {
                         tail,
@@ -97,7 +97,7 @@ pub fn par_new_edges<G: Graph>(
                     continue;
                 }
 
-                let shortcut_distance = in_edge.weight + out_edge.weight;
+                let shortcut_distance = in_edge.weight.saturating_add(out_edge.weight);
 
                 if heuristic.is_less_or_equal_upper_bound(tail, head, shortcut_distance) {
                     new_edges += 1;
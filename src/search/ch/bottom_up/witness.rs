@@ -4,7 +4,10 @@ use rayon::iter::{ParallelBridge, ParallelIterator};
 
 use super::generic::contraction_bottom_up;
 use crate::{
-    graphs::{reversible_graph::ReversibleGraph, Graph, TaillessEdge, Vertex, WeightedEdge},
+    graphs::{
+        reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, Graph, TaillessEdge, Vertex,
+        WeightedEdge,
+    },
     search::{
         ch::contracted_graph::ContractedGraph, collections::dijkstra_data::DijkstraData,
         dijkstra::dijkstra_one_to_many,
@@ -23,6 +26,25 @@ impl ContractedGraph {
 
         ContractedGraph::new(level_to_vertex, edges, shortcuts)
     }
+
+    /// Like [`ContractedGraph::with_dijkstra_witness_search`], but for graphs
+    /// that can not be modified, e.g. one metric of a
+    /// [`crate::graphs::multi_metric_graph::MultiMetricGraph`]. The contraction
+    /// works on a modifiable copy of `graph`.
+    pub fn with_dijkstra_witness_search_read_only<G: Graph>(
+        graph: &ReversibleGraph<G>,
+        hop_limit: u32,
+    ) -> ContractedGraph {
+        let graph = ReversibleGraph::<VecVecGraph>::with_edges(
+            graph.out_graph().number_of_vertices(),
+            &graph.out_graph().all_edges(),
+        );
+        let (level_to_vertex, edges, shortcuts) = contraction_bottom_up(graph, |graph, vertex| {
+            par_simulate_contraction_witness_search(graph, hop_limit, vertex)
+        });
+
+        ContractedGraph::new(level_to_vertex, edges, shortcuts)
+    }
 }
 
 /// Simulates a contraction. Returns vertex -> (new_edges, updated_edges)
@@ -46,7 +68,12 @@ pub fn par_simulate_contraction_witness_search<G: Graph>(
             let mut updated_edges = Vec::new();
 
             // Get all shortest path distances (tail -> neighbor)
-            let data = dijkstra_one_to_many(graph.out_graph(), hop_limit, tail, &out_neighbors);
+            let data = dijkstra_one_to_many(
+                graph.out_graph() as &dyn Graph,
+                hop_limit,
+                tail,
+                &out_neighbors,
+            );
 
             for out_edge in graph.out_graph().edges(vertex) {
                 let head = out_edge.head;
@@ -55,7 +82,7 @@ pub fn par_simulate_contraction_witness_search<G: Graph>(
                     continue;
                 }
 
-                let shortcut_distance = in_edge.weight + out_edge.weight;
+                let shortcut_distance = in_edge.weight.saturating_add(out_edge.weight);
                 let shortest_path_distance = data.get_distance(head);
 
                 if shortcut_distance <= shortest_path_distance {
@@ -7,7 +7,7 @@ use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use super::contracted_graph::{vertex_to_level, ContractedGraph};
 use crate::{
-    graphs::{reversible_graph::ReversibleGraph, Graph, Level, Vertex, WeightedEdge},
+    graphs::{reversible_graph::ReversibleGraph, Graph, Level, StaticGraph, Vertex, WeightedEdge},
     search::collections::{
         dijkstra_data::{DijkstraData, DijkstraDataVec},
         vertex_distance_queue::{VertexDistanceQueue, VertexDistanceQueueBinaryHeap},
@@ -17,7 +17,7 @@ use crate::{
 };
 
 impl ContractedGraph {
-    pub fn by_brute_force<G: Graph + Default>(
+    pub fn by_brute_force<G: Graph + StaticGraph>(
         graph: &ReversibleGraph<G>,
         level_to_vertex: &Vec<Vertex>,
     ) -> ContractedGraph {
@@ -53,8 +53,8 @@ impl ContractedGraph {
     }
 }
 
-pub fn brute_force_contracted_graph_edges(
-    graph: &dyn Graph,
+pub fn brute_force_contracted_graph_edges<G: Graph + StaticGraph + ?Sized>(
+    graph: &G,
     vertex_to_level: &Vec<u32>,
     progress_bar: ProgressBar,
 ) -> (Vec<WeightedEdge>, HashMap<(Vertex, Vertex), Vertex>) {
@@ -128,8 +128,8 @@ pub fn create_shortcuts(
     shortcuts
 }
 
-pub fn get_ch_edges_wrapped(
-    graph: &dyn Graph,
+pub fn get_ch_edges_wrapped<G: Graph + StaticGraph + ?Sized>(
+    graph: &G,
     vertex_to_level: &Vec<u32>,
     source: Vertex,
 ) -> (Vec<WeightedEdge>, Vec<((Vertex, Vertex), Vertex)>) {
@@ -143,14 +143,20 @@ pub fn get_ch_edges_wrapped(
     )
 }
 
-pub fn get_ch_edges(
-    graph: &dyn Graph,
-    data: &mut dyn DijkstraData,
-    expanded: &mut dyn VertexExpandedData,
-    queue: &mut dyn VertexDistanceQueue,
+pub fn get_ch_edges<G, D, E, Q>(
+    graph: &G,
+    data: &mut D,
+    expanded: &mut E,
+    queue: &mut Q,
     vertex_to_level: &Vec<u32>,
     source: Vertex,
-) -> (Vec<WeightedEdge>, Vec<((Vertex, Vertex), Vertex)>) {
+) -> (Vec<WeightedEdge>, Vec<((Vertex, Vertex), Vertex)>)
+where
+    G: StaticGraph + ?Sized,
+    D: DijkstraData + ?Sized,
+    E: VertexExpandedData + ?Sized,
+    Q: VertexDistanceQueue + ?Sized,
+{
     // Maps (vertex -> (max level on path from source to vertex, associated vertex))
     //
     // A vertex is a head of a ch edge if its levels equals the max level on its
@@ -207,9 +213,9 @@ pub fn get_ch_edges(
 
         let tail_is_alive = alive.contains(&tail);
 
-        for edge in graph.edges(tail) {
+        for edge in graph.out_edges(tail) {
             let current_distance_head = data.get_distance(edge.head);
-            let alternative_distance_head = distance_tail + edge.weight;
+            let alternative_distance_head = distance_tail.saturating_add(edge.weight);
             if alternative_distance_head < current_distance_head {
                 data.set_distance(edge.head, alternative_distance_head);
                 data.set_predecessor(edge.head, tail);
@@ -234,11 +240,11 @@ pub fn get_ch_edges(
     (edges, shortcuts)
 }
 
-pub fn get_ch_edges_debug(
-    graph: &dyn Graph,
-    data: &mut dyn DijkstraData,
-    expanded: &mut dyn VertexExpandedData,
-    queue: &mut dyn VertexDistanceQueue,
+pub fn get_ch_edges_debug<G, D, E, Q>(
+    graph: &G,
+    data: &mut D,
+    expanded: &mut E,
+    queue: &mut Q,
     vertex_to_level: &Vec<u32>,
     source: Vertex,
 ) -> (
@@ -247,7 +253,13 @@ pub fn get_ch_edges_debug(
     Vec<(Vertex, Vertex)>,
     Vec<(Vertex, Vertex)>,
     Vec<(Vertex, Vertex)>,
-) {
+)
+where
+    G: StaticGraph + ?Sized,
+    D: DijkstraData + ?Sized,
+    E: VertexExpandedData + ?Sized,
+    Q: VertexDistanceQueue + ?Sized,
+{
     let mut alive_setteled = Vec::new();
     let mut setteled = Vec::new();
     let mut seen = HashSet::new();
@@ -313,9 +325,9 @@ pub fn get_ch_edges_debug(
 
         let tail_is_alive = alive.contains(&tail);
 
-        for edge in graph.edges(tail) {
+        for edge in graph.out_edges(tail) {
             let current_distance_head = data.get_distance(edge.head);
-            let alternative_distance_head = distance_tail + edge.weight;
+            let alternative_distance_head = distance_tail.saturating_add(edge.weight);
             if alternative_distance_head < current_distance_head {
                 data.set_distance(edge.head, alternative_distance_head);
                 data.set_predecessor(edge.head, tail);
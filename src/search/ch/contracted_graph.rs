@@ -3,7 +3,11 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use crate::graphs::{vec_graph::VecGraph, Graph, Level, Vertex, WeightedEdge};
+use crate::graphs::{
+    permutation::{Permutation, Renumber},
+    vec_graph::VecGraph,
+    Graph, Level, Vertex, WeightedEdge,
+};
 
 #[serde_as]
 #[derive(Serialize, Deserialize)]
@@ -43,11 +47,12 @@ impl ContractedGraph {
         }
     }
 
-    pub fn upward_graph(&self) -> &dyn Graph {
+    pub fn upward_graph(&self) -> &VecGraph {
         &self.upward_graph
     }
 
-    pub fn downward_graph(&self) -> &dyn Graph {
+    /// The downward edges, stored reversed.
+    pub fn downward_graph(&self) -> &VecGraph {
         &self.downward_graph
     }
 
@@ -64,6 +69,31 @@ impl ContractedGraph {
     }
 }
 
+impl Renumber for ContractedGraph {
+    /// Returns the contracted graph of the graph renumbered by `permutation`,
+    /// with the same levels and shortcuts.
+    fn renumbered(&self, permutation: &Permutation) -> Self {
+        // Downward edges are stored reversed.
+        let edges = self
+            .upward_graph
+            .all_edges()
+            .into_iter()
+            .chain(
+                self.downward_graph
+                    .all_edges()
+                    .into_iter()
+                    .map(|edge| edge.reversed()),
+            )
+            .collect::<Vec<_>>();
+
+        ContractedGraph::new(
+            permutation.apply_to_level_to_vertex(&self.level_to_vertex),
+            permutation.apply_to_edges(&edges),
+            permutation.apply_to_shortcuts(&self.shortcuts),
+        )
+    }
+}
+
 pub fn vertex_to_level(level_to_vertex: &Vec<Vertex>) -> Vec<Level> {
     let mut vertex_to_level = vec![0; level_to_vertex.len()];
 
@@ -88,10 +118,30 @@ pub fn level_to_vertex(vertex_to_level: &Vec<Level>) -> Vec<Vertex> {
 mod tests {
     use super::ContractedGraph;
     use crate::{
-        graphs::{large_test_graph, Graph},
-        search::ch::pathfinding::one_to_one_wrapped_path,
+        graphs::{generators::generated_test_graphs, large_test_graph, Graph},
+        search::{ch::pathfinding::one_to_one_wrapped_path, PathFinding},
     };
 
+    #[test]
+    fn contraction_of_generated_graphs() {
+        for (graph, tests) in generated_test_graphs() {
+            let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&graph, u32::MAX);
+            let brute_force_graph =
+                ContractedGraph::by_brute_force(&graph, contracted_graph.level_to_vertex());
+
+            for test in tests {
+                assert_eq!(
+                    contracted_graph.shortest_path_distance(test.source, test.target),
+                    test.distance
+                );
+                assert_eq!(
+                    brute_force_graph.shortest_path_distance(test.source, test.target),
+                    test.distance
+                );
+            }
+        }
+    }
+
     #[test]
     fn contration_by_witness_search() {
         let (graph, tests) = large_test_graph();
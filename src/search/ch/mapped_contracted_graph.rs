@@ -0,0 +1,413 @@
+//! An on-disk layout of a [`ContractedGraph`] that is queried in place from a
+//! memory mapped file, so that a worker can answer its first query right
+//! after opening the file.
+//!
+//! The upward and the downward graph are stored as CSR arrays. Like in
+//! [`ContractedGraph`], the downward edges are stored reversed. For every
+//! edge, the vertex it skips is stored in a parallel array, which replaces
+//! the shortcut map. The file consists of a header followed by eight
+//! sections, each starting at a multiple of 8 bytes. Integers use the native
+//! byte order.
+//!
+//! | section           | content                                          |
+//! |-------------------|--------------------------------------------------|
+//! | header            | [`MappedHeader`]                                 |
+//! | upward offsets    | `number_of_vertices + 1` u64, edges of `v` are `[offsets[v], offsets[v + 1])` |
+//! | upward heads      | u32 per edge, sorted per tail                    |
+//! | upward weights    | u64 per edge                                     |
+//! | upward skipped    | u32 per edge, [`u32::MAX`] if it is no shortcut  |
+//! | downward sections | like the upward sections                         |
+//!
+//! Parallel edges of the contracted graph are merged, keeping the lowest
+//! weight.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    ops::Range,
+    path::Path,
+};
+
+use bytemuck::{Pod, Zeroable};
+use memmap2::Mmap;
+
+use super::{contracted_graph::ContractedGraph, pathfinding::one_to_one_wrapped_path};
+use crate::{
+    container::GraphFingerprint,
+    error::{ContainerErrorKind, Error, Result},
+    graphs::{Distance, Edge, Graph, StaticGraph, Vertex, WeightedEdge},
+    mapped_file::{map_file, offsets, section, SectionLayout, SectionWriter},
+    search::{
+        ch::pathfinding::one_to_one_wrapped_distance,
+        collections::dijkstra_data::Path as SearchPath, shortcuts::ShortcutLookup, PathFinding,
+    },
+};
+
+pub const MAGIC: [u8; 8] = *b"FPCHMAP\0";
+
+/// Bump this whenever the layout changes.
+pub const VERSION: u32 = 1;
+
+/// Marks an edge that is no shortcut.
+const NO_SKIPPED_VERTEX: Vertex = Vertex::MAX;
+
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct MappedHeader {
+    pub magic: [u8; 8],
+    pub version: u32,
+    pub reserved: u32,
+    pub number_of_vertices: u64,
+    pub number_of_upward_edges: u64,
+    pub number_of_downward_edges: u64,
+    pub fingerprint_number_of_vertices: u64,
+    pub fingerprint_number_of_edges: u64,
+    pub fingerprint_hash: u64,
+}
+
+/// Byte ranges of the CSR arrays of one direction.
+struct CsrSections {
+    offsets: Range<usize>,
+    heads: Range<usize>,
+    weights: Range<usize>,
+    skipped_vertices: Range<usize>,
+}
+
+impl CsrSections {
+    fn new(
+        layout: &mut SectionLayout,
+        number_of_vertices: u64,
+        number_of_edges: u64,
+    ) -> Option<CsrSections> {
+        Some(CsrSections {
+            offsets: layout.next::<u64>(number_of_vertices.checked_add(1)?)?,
+            heads: layout.next::<Vertex>(number_of_edges)?,
+            weights: layout.next::<Distance>(number_of_edges)?,
+            skipped_vertices: layout.next::<Vertex>(number_of_edges)?,
+        })
+    }
+}
+
+/// One direction of a [`MappedContractedGraph`], borrowed from the mapping.
+pub struct MappedCsrGraph<'a> {
+    offsets: &'a [u64],
+    heads: &'a [Vertex],
+    weights: &'a [Distance],
+    skipped_vertices: &'a [Vertex],
+}
+
+impl<'a> MappedCsrGraph<'a> {
+    fn new(mmap: &'a Mmap, sections: &CsrSections) -> Self {
+        MappedCsrGraph {
+            offsets: section(mmap, &sections.offsets),
+            heads: section(mmap, &sections.heads),
+            weights: section(mmap, &sections.weights),
+            skipped_vertices: section(mmap, &sections.skipped_vertices),
+        }
+    }
+
+    fn edge_range(&self, tail: Vertex) -> Range<usize> {
+        let tail = tail as usize;
+        match (self.offsets.get(tail), self.offsets.get(tail + 1)) {
+            (Some(&start), Some(&stop)) if start <= stop && stop as usize <= self.heads.len() => {
+                start as usize..stop as usize
+            }
+            _ => 0..0,
+        }
+    }
+
+    fn edge_index(&self, tail: Vertex, head: Vertex) -> Option<usize> {
+        let range = self.edge_range(tail);
+        let start = range.start;
+        self.heads[range]
+            .binary_search(&head)
+            .ok()
+            .map(|index| start + index)
+    }
+
+    /// Returns the vertex the edge `(tail, head)` skips, if it is a shortcut.
+    pub fn skipped_vertex(&self, tail: Vertex, head: Vertex) -> Option<Vertex> {
+        self.edge_index(tail, head)
+            .map(|index| self.skipped_vertices[index])
+            .filter(|&vertex| vertex != NO_SKIPPED_VERTEX)
+    }
+}
+
+impl Graph for MappedCsrGraph<'_> {
+    fn number_of_vertices(&self) -> u32 {
+        self.offsets.len().saturating_sub(1) as u32
+    }
+
+    fn edges(&self, tail: Vertex) -> Box<dyn ExactSizeIterator<Item = WeightedEdge> + Send + '_> {
+        let range = self.edge_range(tail);
+        Box::new(
+            self.heads[range.clone()]
+                .iter()
+                .zip(self.weights[range].iter())
+                .map(move |(&head, &weight)| WeightedEdge::new(tail, head, weight)),
+        )
+    }
+
+    fn get_weight(&self, edge: &Edge) -> Option<Distance> {
+        self.edge_index(edge.tail, edge.head)
+            .map(|index| self.weights[index])
+    }
+
+    fn set_weight(&mut self, _edge: &Edge, _weight: Option<Distance>) {
+        unimplemented!("This is a read only graph");
+    }
+}
+
+impl StaticGraph for MappedCsrGraph<'_> {
+    type Edges<'b>
+        = MappedEdges<'b>
+    where
+        Self: 'b;
+
+    fn out_edges(&self, tail: Vertex) -> MappedEdges<'_> {
+        let range = self.edge_range(tail);
+        MappedEdges {
+            heads: self.heads[range.clone()].iter(),
+            weights: self.weights[range].iter(),
+            tail,
+        }
+    }
+}
+
+/// Out edges of a vertex of a [`MappedCsrGraph`].
+pub struct MappedEdges<'a> {
+    heads: std::slice::Iter<'a, Vertex>,
+    weights: std::slice::Iter<'a, Distance>,
+    tail: Vertex,
+}
+
+impl Iterator for MappedEdges<'_> {
+    type Item = WeightedEdge;
+
+    fn next(&mut self) -> Option<WeightedEdge> {
+        Some(WeightedEdge::new(
+            self.tail,
+            *self.heads.next()?,
+            *self.weights.next()?,
+        ))
+    }
+}
+
+/// A contracted graph that is queried straight from a memory mapped file, see
+/// the module documentation.
+pub struct MappedContractedGraph {
+    mmap: Mmap,
+    header: MappedHeader,
+    upward: CsrSections,
+    downward: CsrSections,
+}
+
+impl MappedContractedGraph {
+    /// Maps the file at `path` and checks its header.
+    ///
+    /// The file must not be modified while it is mapped.
+    pub fn open(path: &Path) -> Result<MappedContractedGraph> {
+        let (mmap, header): (_, MappedHeader) = map_file(path, MAGIC, VERSION)?;
+
+        let mut layout = SectionLayout::after_header::<MappedHeader>();
+        let sections = CsrSections::new(
+            &mut layout,
+            header.number_of_vertices,
+            header.number_of_upward_edges,
+        )
+        .zip(CsrSections::new(
+            &mut layout,
+            header.number_of_vertices,
+            header.number_of_downward_edges,
+        ))
+        .filter(|(_, downward)| downward.skipped_vertices.end <= mmap.len());
+        let (upward, downward) =
+            sections.ok_or_else(|| Error::container(path, ContainerErrorKind::Truncated))?;
+
+        Ok(MappedContractedGraph {
+            mmap,
+            header,
+            upward,
+            downward,
+        })
+    }
+
+    /// The graph the contracted graph was built from.
+    pub fn fingerprint(&self) -> GraphFingerprint {
+        GraphFingerprint {
+            number_of_vertices: self.header.fingerprint_number_of_vertices,
+            number_of_edges: self.header.fingerprint_number_of_edges,
+            hash: self.header.fingerprint_hash,
+        }
+    }
+
+    pub fn upward_graph(&self) -> MappedCsrGraph<'_> {
+        MappedCsrGraph::new(&self.mmap, &self.upward)
+    }
+
+    /// The downward edges, stored reversed.
+    pub fn downward_graph(&self) -> MappedCsrGraph<'_> {
+        MappedCsrGraph::new(&self.mmap, &self.downward)
+    }
+}
+
+impl ShortcutLookup for MappedContractedGraph {
+    fn skipped_vertex(&self, tail: Vertex, head: Vertex) -> Option<Vertex> {
+        // An edge is either an upward edge, or a reversed downward edge.
+        self.upward_graph()
+            .skipped_vertex(tail, head)
+            .or_else(|| self.downward_graph().skipped_vertex(head, tail))
+    }
+}
+
+impl PathFinding for MappedContractedGraph {
+    fn shortest_path(&self, source: Vertex, target: Vertex) -> Option<SearchPath> {
+        one_to_one_wrapped_path(
+            &self.upward_graph(),
+            &self.downward_graph(),
+            self,
+            source,
+            target,
+        )
+    }
+
+    fn shortest_path_distance(&self, source: Vertex, target: Vertex) -> Option<Distance> {
+        one_to_one_wrapped_distance(&self.upward_graph(), &self.downward_graph(), source, target)
+    }
+
+    fn number_of_vertices(&self) -> u32 {
+        self.header.number_of_vertices as u32
+    }
+}
+
+/// Writes `contracted_graph` in the layout of [`MappedContractedGraph`] into
+/// the file at `path`. `fingerprint` identifies the graph the contracted graph
+/// was built from.
+pub fn write_mapped_contracted_graph_file(
+    path: &Path,
+    contracted_graph: &ContractedGraph,
+    fingerprint: &GraphFingerprint,
+) -> Result<()> {
+    let file = File::create(path).map_err(|error| Error::io(path, error))?;
+    write_mapped_contracted_graph(BufWriter::new(file), contracted_graph, fingerprint)
+        .map_err(|error| Error::io(path, error))
+}
+
+pub fn write_mapped_contracted_graph<W: Write>(
+    writer: W,
+    contracted_graph: &ContractedGraph,
+    fingerprint: &GraphFingerprint,
+) -> std::io::Result<()> {
+    let shortcuts = contracted_graph.shortcuts();
+    let upward_edges = merged_edges(contracted_graph.upward_graph(), |edge| {
+        shortcuts.get(&(edge.tail, edge.head))
+    });
+    let downward_edges = merged_edges(contracted_graph.downward_graph(), |edge| {
+        shortcuts.get(&(edge.head, edge.tail))
+    });
+
+    let number_of_vertices = Graph::number_of_vertices(contracted_graph.upward_graph());
+    let header = MappedHeader {
+        magic: MAGIC,
+        version: VERSION,
+        reserved: 0,
+        number_of_vertices: number_of_vertices as u64,
+        number_of_upward_edges: upward_edges.iter().map(Vec::len).sum::<usize>() as u64,
+        number_of_downward_edges: downward_edges.iter().map(Vec::len).sum::<usize>() as u64,
+        fingerprint_number_of_vertices: fingerprint.number_of_vertices,
+        fingerprint_number_of_edges: fingerprint.number_of_edges,
+        fingerprint_hash: fingerprint.hash,
+    };
+
+    let mut writer = SectionWriter::new(writer);
+    writer.write(&[header])?;
+
+    for edges in [&upward_edges, &downward_edges] {
+        let all_edges = || edges.iter().flatten();
+        writer.write(&offsets(edges.iter().map(Vec::len)))?;
+        writer.write(&all_edges().map(|(edge, _)| edge.head).collect::<Vec<_>>())?;
+        writer.write(&all_edges().map(|(edge, _)| edge.weight).collect::<Vec<_>>())?;
+        writer.write(
+            &all_edges()
+                .map(|&(_, skipped_vertex)| skipped_vertex)
+                .collect::<Vec<_>>(),
+        )?;
+    }
+
+    writer.finish()
+}
+
+/// Returns the edges of every vertex sorted by head together with the vertex
+/// they skip. Of parallel edges, only the one with the lowest weight is kept.
+fn merged_edges<'a>(
+    graph: &dyn Graph,
+    skipped_vertex: impl Fn(&WeightedEdge) -> Option<&'a Vertex>,
+) -> Vec<Vec<(WeightedEdge, Vertex)>> {
+    graph
+        .vertices()
+        .map(|tail| {
+            let mut edges = graph.edges(tail).collect::<Vec<_>>();
+            edges.sort_unstable_by_key(|edge| (edge.head, edge.weight));
+            edges.dedup_by_key(|edge| edge.head);
+
+            edges
+                .into_iter()
+                .map(|edge| {
+                    let skipped_vertex =
+                        skipped_vertex(&edge).copied().unwrap_or(NO_SKIPPED_VERTEX);
+                    (edge, skipped_vertex)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_mapped_contracted_graph_file, MappedContractedGraph};
+    use crate::{
+        container::GraphFingerprint,
+        graphs::{
+            reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, Graph, WeightedEdge,
+        },
+        search::{
+            ch::contracted_graph::ContractedGraph, dijkstra::dijkstra_one_to_one_path_wrapped,
+            PathFinding,
+        },
+    };
+
+    #[test]
+    fn queries_match_dijkstra() {
+        let mut edges = Vec::new();
+        for vertex in 0..11 {
+            edges.push(WeightedEdge::new(vertex, vertex + 1, 1 + vertex as u64 % 3));
+            edges.push(WeightedEdge::new(vertex + 1, vertex, 2));
+        }
+        edges.push(WeightedEdge::new(0, 6, 4));
+        edges.push(WeightedEdge::new(9, 2, 3));
+        let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+
+        let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&graph, u32::MAX);
+        let fingerprint = GraphFingerprint::of(graph.out_graph());
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write_mapped_contracted_graph_file(file.path(), &contracted_graph, &fingerprint).unwrap();
+        let mapped_contracted_graph = MappedContractedGraph::open(file.path()).unwrap();
+
+        assert_eq!(mapped_contracted_graph.fingerprint(), fingerprint);
+        for source in 0..12 {
+            for target in 0..12 {
+                let expected = dijkstra_one_to_one_path_wrapped(graph.out_graph(), source, target);
+                let path = mapped_contracted_graph.shortest_path(source, target);
+
+                assert_eq!(
+                    path.as_ref().map(|path| path.distance),
+                    expected.as_ref().map(|path| path.distance)
+                );
+                let path_distance =
+                    path.and_then(|path| graph.out_graph().get_path_distance(&path.vertices));
+                assert_eq!(path_distance, expected.map(|path| path.distance));
+            }
+        }
+    }
+}
@@ -5,6 +5,7 @@ use contracted_graph::ContractedGraph;
 pub mod bottom_up;
 pub mod brute_force;
 pub mod contracted_graph;
+pub mod mapped_contracted_graph;
 pub mod pathfinding;
 pub mod top_down;
 
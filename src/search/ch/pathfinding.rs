@@ -2,35 +2,42 @@ use std::collections::HashMap;
 
 use super::contracted_graph::ContractedGraph;
 use crate::{
-    graphs::{Distance, Graph, Vertex, WeightedEdge},
+    graphs::{Distance, Graph, StaticGraph, Vertex, WeightedEdge},
     search::{
         collections::{
             dijkstra_data::{DijkstraData, DijkstraDataHashMap, Path},
             vertex_distance_queue::{VertexDistanceQueue, VertexDistanceQueueBinaryHeap},
             vertex_expanded_data::{VertexExpandedData, VertexExpandedDataHashSet},
         },
-        shortcuts::replace_shortcuts_slowly,
+        dijkstra::{search_space, SearchSpace},
+        shortcuts::{replace_shortcuts_slowly, ShortcutLookup},
         PathFinding,
     },
 };
 
 impl PathFinding for ContractedGraph {
     fn shortest_path(&self, source: Vertex, target: Vertex) -> Option<Path> {
-        one_to_one_wrapped_path(
-            self.upward_graph(),
-            self.downward_graph(),
+        let (vertex, distance, forward_data, backward_data) =
+            one_to_one_wrapped(self.upward_graph(), self.downward_graph(), source, target)?;
+
+        Some(path_through_meeting_vertex(
+            vertex,
+            distance,
+            &forward_data,
+            &backward_data,
             self.shortcuts(),
-            source,
-            target,
-        )
+        ))
     }
 
     fn shortest_path_distance(&self, source: Vertex, target: Vertex) -> Option<Distance> {
-        one_to_one_wrapped_distance(self.upward_graph(), self.downward_graph(), source, target)
+        let (_vertex, distance, _forward_data, _backward_data) =
+            one_to_one_wrapped(self.upward_graph(), self.downward_graph(), source, target)?;
+
+        Some(distance)
     }
 
     fn number_of_vertices(&self) -> u32 {
-        self.upward_graph().number_of_vertices()
+        Graph::number_of_vertices(self.upward_graph())
     }
 }
 
@@ -49,9 +56,9 @@ pub fn get_slow_shortcuts(
 }
 
 /// Wrapper that returns the shortest path distance.
-pub fn one_to_one_wrapped_distance(
-    upward_graph: &dyn Graph,
-    downward_graph: &dyn Graph,
+pub fn one_to_one_wrapped_distance<G: StaticGraph + ?Sized>(
+    upward_graph: &G,
+    downward_graph: &G,
     source: Vertex,
     target: Vertex,
 ) -> Option<Distance> {
@@ -62,16 +69,38 @@ pub fn one_to_one_wrapped_distance(
 }
 
 /// Wrapper that returns the shortest path.
-pub fn one_to_one_wrapped_path(
-    upward_graph: &dyn Graph,
-    downward_graph: &dyn Graph,
-    shortcuts: &HashMap<(Vertex, Vertex), Vertex>,
+pub fn one_to_one_wrapped_path<G, S>(
+    upward_graph: &G,
+    downward_graph: &G,
+    shortcuts: &S,
     source: Vertex,
     target: Vertex,
-) -> Option<Path> {
+) -> Option<Path>
+where
+    G: StaticGraph + ?Sized,
+    S: ShortcutLookup + ?Sized,
+{
     let (vertex, distance, forward_data, backward_data) =
         one_to_one_wrapped(upward_graph, downward_graph, source, target)?;
 
+    Some(path_through_meeting_vertex(
+        vertex,
+        distance,
+        &forward_data,
+        &backward_data,
+        shortcuts,
+    ))
+}
+
+/// Joins the paths of the forward and the backward search at the meeting
+/// vertex and replaces the shortcuts.
+fn path_through_meeting_vertex<S: ShortcutLookup + ?Sized>(
+    vertex: Vertex,
+    distance: Distance,
+    forward_data: &dyn DijkstraData,
+    backward_data: &dyn DijkstraData,
+    shortcuts: &S,
+) -> Path {
     let mut vertices = forward_data.get_path(vertex).unwrap().vertices; // (source -> vertex)
     let mut backward_vertices = backward_data.get_path(vertex).unwrap().vertices; // (target -> vertex)
 
@@ -81,13 +110,48 @@ pub fn one_to_one_wrapped_path(
 
     replace_shortcuts_slowly(&mut vertices, shortcuts); // replace the shortcuts
 
-    Some(Path { vertices, distance })
+    Path { vertices, distance }
+}
+
+/// Returns the vertices settled by the forward and the backward search of a
+/// CH query, see [`crate::search::dijkstra::search_space`].
+pub fn one_to_one_search_space<G: StaticGraph + ?Sized>(
+    upward_graph: &G,
+    downward_graph: &G,
+    source: Vertex,
+    target: Vertex,
+) -> (SearchSpace, SearchSpace) {
+    let mut forward_data = DijkstraDataHashMap::new();
+    let mut forward_expanded = VertexExpandedDataHashSet::new();
+    let mut forward_queue = VertexDistanceQueueBinaryHeap::new();
+
+    let mut backward_data = DijkstraDataHashMap::new();
+    let mut backward_expanded = VertexExpandedDataHashSet::new();
+    let mut backward_queue = VertexDistanceQueueBinaryHeap::new();
+
+    one_to_one(
+        upward_graph,
+        downward_graph,
+        &mut forward_data,
+        &mut forward_expanded,
+        &mut forward_queue,
+        &mut backward_data,
+        &mut backward_expanded,
+        &mut backward_queue,
+        source,
+        target,
+    );
+
+    (
+        search_space(&forward_data, &forward_expanded),
+        search_space(&backward_data, &backward_expanded),
+    )
 }
 
 /// Wrapper that returns everythin needed for CH queries.
-pub fn one_to_one_wrapped(
-    upward_graph: &dyn Graph,
-    downward_graph: &dyn Graph,
+pub fn one_to_one_wrapped<G: StaticGraph + ?Sized>(
+    upward_graph: &G,
+    downward_graph: &G,
     source: Vertex,
     target: Vertex,
 ) -> Option<(Vertex, Distance, DijkstraDataHashMap, DijkstraDataHashMap)> {
@@ -116,18 +180,27 @@ pub fn one_to_one_wrapped(
 }
 
 /// CH search logic
-pub fn one_to_one(
-    upward_graph: &dyn Graph,
-    downward_graph: &dyn Graph,
-    forward_data: &mut dyn DijkstraData,
-    forward_expanded: &mut dyn VertexExpandedData,
-    forward_queue: &mut dyn VertexDistanceQueue,
-    backward_data: &mut dyn DijkstraData,
-    backward_expanded: &mut dyn VertexExpandedData,
-    backward_queue: &mut dyn VertexDistanceQueue,
+///
+/// Generic over the graphs and the data structures, see
+/// [`crate::search::dijkstra::dijktra_one_to_all`].
+pub fn one_to_one<G, D, E, Q>(
+    upward_graph: &G,
+    downward_graph: &G,
+    forward_data: &mut D,
+    forward_expanded: &mut E,
+    forward_queue: &mut Q,
+    backward_data: &mut D,
+    backward_expanded: &mut E,
+    backward_queue: &mut Q,
     source: Vertex,
     target: Vertex,
-) -> Option<(Vertex, Distance)> {
+) -> Option<(Vertex, Distance)>
+where
+    G: StaticGraph + ?Sized,
+    D: DijkstraData + ?Sized,
+    E: VertexExpandedData + ?Sized,
+    Q: VertexDistanceQueue + ?Sized,
+{
     forward_data.set_distance(source, 0);
     forward_queue.insert(source, 0);
 
@@ -171,16 +244,21 @@ pub fn one_to_one(
 }
 
 /// Single search step in one direction.
-fn single_search_step(
-    direction1_graph: &dyn Graph,
-    direction2_graph: &dyn Graph,
-    direction1_data: &mut dyn DijkstraData,
-    direction1_expanded: &mut dyn VertexExpandedData,
-    direction1_queue: &mut dyn VertexDistanceQueue,
-    direction2_data: &mut dyn DijkstraData,
+fn single_search_step<G, D, E, Q>(
+    direction1_graph: &G,
+    direction2_graph: &G,
+    direction1_data: &mut D,
+    direction1_expanded: &mut E,
+    direction1_queue: &mut Q,
+    direction2_data: &mut D,
     meeting_vertex: &mut Vertex,
     meeting_distance: &mut Distance,
-) {
+) where
+    G: StaticGraph + ?Sized,
+    D: DijkstraData + ?Sized,
+    E: VertexExpandedData + ?Sized,
+    Q: VertexDistanceQueue + ?Sized,
+{
     if let Some((tail, distance_tail)) = direction1_queue.pop() {
         // It is not guaranteed that the queue does implement a decrease key operation.
         // Therefor, if a vertex has already been expanded, skip it.
@@ -189,31 +267,25 @@ fn single_search_step(
         }
 
         // Stall on demand logic.
-        for direction2_edge in direction2_graph.edges(tail) {
+        for direction2_edge in direction2_graph.out_edges(tail) {
             let predecessor_weight = direction1_data.get_distance(direction2_edge.head);
-            if predecessor_weight
-                .checked_add(direction2_edge.weight)
-                .unwrap_or(Distance::MAX)
-                < distance_tail
-            {
+            if predecessor_weight.saturating_add(direction2_edge.weight) < distance_tail {
                 return;
             }
         }
 
         // Meeting vertex logic
         let direction2_distance_tail = direction2_data.get_distance(tail);
-        let alternative_meeting_distance = distance_tail
-            .checked_add(direction2_distance_tail)
-            .unwrap_or(Distance::MAX);
+        let alternative_meeting_distance = distance_tail.saturating_add(direction2_distance_tail);
         if alternative_meeting_distance < *meeting_distance {
             *meeting_vertex = tail;
             *meeting_distance = alternative_meeting_distance;
         }
 
         // Search logic
-        for edge in direction1_graph.edges(tail) {
+        for edge in direction1_graph.out_edges(tail) {
             let current_distance_head = direction1_data.get_distance(edge.head);
-            let alternative_distance_head = distance_tail + edge.weight;
+            let alternative_distance_head = distance_tail.saturating_add(edge.weight);
             if alternative_distance_head < current_distance_head {
                 direction1_data.set_distance(edge.head, alternative_distance_head);
                 direction1_data.set_predecessor(edge.head, tail);
@@ -68,7 +68,7 @@ pub struct DijkstraDataVec {
 
 impl DijkstraDataVec {
     /// Constructs a new `DijkstraDataVec` for a given graph.
-    pub fn new(graph: &dyn Graph) -> Self {
+    pub fn new<G: Graph + ?Sized>(graph: &G) -> Self {
         DijkstraDataVec {
             // Initialize the vector with (None, None) tuples for each vertex in the graph.
             predecessors: vec![Vertex::MAX; graph.number_of_vertices() as usize],
@@ -15,7 +15,7 @@ pub struct VertexExpandedDataVec {
 }
 
 impl VertexExpandedDataVec {
-    pub fn new(graph: &dyn Graph) -> Self {
+    pub fn new<G: Graph + ?Sized>(graph: &G) -> Self {
         VertexExpandedDataVec {
             expanded: vec![false; graph.number_of_vertices() as usize],
         }
@@ -39,7 +39,7 @@ pub struct VertexExpandedDataBitSet {
 }
 
 impl VertexExpandedDataBitSet {
-    pub fn new(graph: &dyn Graph) -> Self {
+    pub fn new<G: Graph + ?Sized>(graph: &G) -> Self {
         VertexExpandedDataBitSet {
             expanded: FixedBitSet::with_capacity(graph.number_of_vertices() as usize),
         }
@@ -70,6 +70,11 @@ impl VertexExpandedDataHashSet {
     pub fn dijkstra_rank(&self) -> u32 {
         self.expanded.len() as u32
     }
+
+    /// Returns the expanded vertices in arbitrary order.
+    pub fn expanded_vertices(&self) -> impl Iterator<Item = Vertex> + '_ {
+        self.expanded.iter().copied()
+    }
 }
 
 impl VertexExpandedData for VertexExpandedDataHashSet {
@@ -14,16 +14,25 @@ use super::{
     },
     path::ShortestPathTestCase,
 };
-use crate::graphs::{Distance, Graph, Vertex};
+use crate::graphs::{Distance, Graph, StaticGraph, Vertex, WeightedEdge};
 
 /// requires data, expanded and queue to be cleared before calling.
-pub fn dijktra_one_to_all(
-    graph: &dyn Graph,
-    data: &mut dyn DijkstraData,
-    expanded: &mut dyn VertexExpandedData,
-    queue: &mut dyn VertexDistanceQueue,
+///
+/// Generic over the graph and the data structures, so that a search over a
+/// concrete graph type is statically dispatched. `&dyn Graph` and `&mut dyn
+/// DijkstraData` etc. can be passed as well.
+pub fn dijktra_one_to_all<G, D, E, Q>(
+    graph: &G,
+    data: &mut D,
+    expanded: &mut E,
+    queue: &mut Q,
     source: Vertex,
-) {
+) where
+    G: StaticGraph + ?Sized,
+    D: DijkstraData + ?Sized,
+    E: VertexExpandedData + ?Sized,
+    Q: VertexDistanceQueue + ?Sized,
+{
     data.set_distance(source, 0);
     queue.insert(source, 0);
 
@@ -32,21 +41,16 @@ pub fn dijktra_one_to_all(
             continue;
         }
 
-        for edge in graph.edges(tail) {
-            let current_distance_head = data.get_distance(edge.head);
-            let alternative_distance_head = distance_tail + edge.weight;
-            if alternative_distance_head < current_distance_head {
-                data.set_distance(edge.head, alternative_distance_head);
-                data.set_predecessor(edge.head, tail);
-                queue.insert(edge.head, alternative_distance_head);
-            }
-        }
+        relax_edges(graph.out_edges(tail), tail, distance_tail, data, queue);
     }
 }
 
 /// Wrapper that creates all nesseary data structures each time when called
 /// which can have a performance malus.
-pub fn dijkstra_one_to_all_wraped(graph: &dyn Graph, source: Vertex) -> DijkstraDataVec {
+pub fn dijkstra_one_to_all_wraped<G>(graph: &G, source: Vertex) -> DijkstraDataVec
+where
+    G: Graph + StaticGraph + ?Sized,
+{
     let mut data = DijkstraDataVec::new(graph);
     let mut expanded = VertexExpandedDataBitSet::new(graph);
     let mut queue = VertexDistanceQueueBinaryHeap::new();
@@ -54,14 +58,21 @@ pub fn dijkstra_one_to_all_wraped(graph: &dyn Graph, source: Vertex) -> Dijkstra
     data
 }
 
-pub fn dijkstra_one_to_one(
-    graph: &dyn Graph,
-    data: &mut dyn DijkstraData,
-    expanded: &mut dyn VertexExpandedData,
-    queue: &mut dyn VertexDistanceQueue,
+/// requires data, expanded and queue to be cleared before calling. See
+/// [`dijktra_one_to_all`].
+pub fn dijkstra_one_to_one<G, D, E, Q>(
+    graph: &G,
+    data: &mut D,
+    expanded: &mut E,
+    queue: &mut Q,
     source: Vertex,
     target: Vertex,
-) {
+) where
+    G: StaticGraph + ?Sized,
+    D: DijkstraData + ?Sized,
+    E: VertexExpandedData + ?Sized,
+    Q: VertexDistanceQueue + ?Sized,
+{
     data.set_distance(source, 0);
     queue.insert(source, 0);
 
@@ -73,22 +84,35 @@ pub fn dijkstra_one_to_one(
             break;
         }
 
-        for edge in graph.edges(tail) {
-            let current_distance_head = data.get_distance(edge.head);
-            let alternative_distance_head = distance_tail + edge.weight;
-            if alternative_distance_head < current_distance_head {
-                data.set_distance(edge.head, alternative_distance_head);
-                data.set_predecessor(edge.head, tail);
-                queue.insert(edge.head, alternative_distance_head);
-            }
+        relax_edges(graph.out_edges(tail), tail, distance_tail, data, queue);
+    }
+}
+
+fn relax_edges<D, Q>(
+    edges: impl Iterator<Item = WeightedEdge>,
+    tail: Vertex,
+    distance_tail: Distance,
+    data: &mut D,
+    queue: &mut Q,
+) where
+    D: DijkstraData + ?Sized,
+    Q: VertexDistanceQueue + ?Sized,
+{
+    for edge in edges {
+        let current_distance_head = data.get_distance(edge.head);
+        let alternative_distance_head = distance_tail.saturating_add(edge.weight);
+        if alternative_distance_head < current_distance_head {
+            data.set_distance(edge.head, alternative_distance_head);
+            data.set_predecessor(edge.head, tail);
+            queue.insert(edge.head, alternative_distance_head);
         }
     }
 }
 
 /// Wrapper that creates all nesseary data structures each time when called
 /// which can have a performance malus.
-pub fn dijkstra_one_to_one_path_wrapped(
-    graph: &dyn Graph,
+pub fn dijkstra_one_to_one_path_wrapped<G: Graph + StaticGraph + ?Sized>(
+    graph: &G,
     source: Vertex,
     target: Vertex,
 ) -> Option<Path> {
@@ -101,8 +125,8 @@ pub fn dijkstra_one_to_one_path_wrapped(
 
 /// Wrapper that creates all nesseary data structures each time when called
 /// which can have a performance malus.
-pub fn dijkstra_one_to_one_distance_wrapped(
-    graph: &dyn Graph,
+pub fn dijkstra_one_to_one_distance_wrapped<G: Graph + StaticGraph + ?Sized>(
+    graph: &G,
     source: Vertex,
     target: Vertex,
 ) -> Option<Distance> {
@@ -121,8 +145,37 @@ pub fn dijkstra_one_to_one_distance_wrapped(
     distance
 }
 
-pub fn dijkstra_one_to_many(
-    graph: &dyn Graph,
+/// Settled vertices of a search together with their distance from where the
+/// search started.
+pub type SearchSpace = Vec<(Vertex, Distance)>;
+
+/// Returns the vertices settled by a one to one search together with their
+/// distance from `source`, ordered by distance.
+pub fn dijkstra_one_to_one_search_space<G: Graph + StaticGraph + ?Sized>(
+    graph: &G,
+    source: Vertex,
+    target: Vertex,
+) -> SearchSpace {
+    let mut data = DijkstraDataHashMap::new();
+    let mut expanded = VertexExpandedDataHashSet::new();
+    let mut queue = VertexDistanceQueueBinaryHeap::new();
+    dijkstra_one_to_one(graph, &mut data, &mut expanded, &mut queue, source, target);
+    search_space(&data, &expanded)
+}
+
+/// Returns the expanded vertices of a search together with their distance,
+/// ordered by distance.
+pub fn search_space(data: &dyn DijkstraData, expanded: &VertexExpandedDataHashSet) -> SearchSpace {
+    let mut search_space: Vec<_> = expanded
+        .expanded_vertices()
+        .map(|vertex| (vertex, data.get_distance(vertex)))
+        .collect();
+    search_space.sort_unstable_by_key(|&(vertex, distance)| (distance, vertex));
+    search_space
+}
+
+pub fn dijkstra_one_to_many<G: StaticGraph + ?Sized>(
+    graph: &G,
     hop_limit: u32,
     source: Vertex,
     targets: &Vec<Vertex>,
@@ -142,15 +195,20 @@ pub fn dijkstra_one_to_many(
     data
 }
 
-pub fn dijktra_one_to_many(
-    graph: &dyn Graph,
-    data: &mut dyn DijkstraData,
-    expanded: &mut dyn VertexExpandedData,
-    queue: &mut dyn VertexDistanceQueue,
+pub fn dijktra_one_to_many<G, D, E, Q>(
+    graph: &G,
+    data: &mut D,
+    expanded: &mut E,
+    queue: &mut Q,
     hop_limit: u32,
     source: Vertex,
     targets: &Vec<Vertex>,
-) {
+) where
+    G: StaticGraph + ?Sized,
+    D: DijkstraData + ?Sized,
+    E: VertexExpandedData + ?Sized,
+    Q: VertexDistanceQueue + ?Sized,
+{
     let mut targets: HashSet<Vertex> = targets.iter().cloned().collect();
 
     data.set_distance(source, 0);
@@ -170,9 +228,9 @@ pub fn dijktra_one_to_many(
 
         let tail_hops = *hops.get(&tail).unwrap();
 
-        for edge in graph.edges(tail) {
+        for edge in graph.out_edges(tail) {
             let current_distance_head = data.get_distance(edge.head);
-            let alternative_distance_head = distance_tail + edge.weight;
+            let alternative_distance_head = distance_tail.saturating_add(edge.weight);
             if alternative_distance_head < current_distance_head && tail_hops < hop_limit {
                 data.set_distance(edge.head, alternative_distance_head);
                 data.set_predecessor(edge.head, tail);
@@ -184,7 +242,10 @@ pub fn dijktra_one_to_many(
     }
 }
 
-pub fn create_test_cases(graph: &dyn Graph, number_of_testcases: u32) -> Vec<ShortestPathTestCase> {
+pub fn create_test_cases<G>(graph: &G, number_of_testcases: u32) -> Vec<ShortestPathTestCase>
+where
+    G: Graph + StaticGraph + ?Sized,
+{
     (0..number_of_testcases)
         .into_par_iter()
         .progress()
@@ -0,0 +1,76 @@
+//! Queries on an edge based graph, see [`crate::graphs::edge_based_graph`].
+
+use super::{collections::dijkstra_data::Path, PathFinding};
+use crate::graphs::{edge_based_graph::EdgeBasedMapping, Distance, Vertex};
+
+/// A path finder built on an edge based graph that answers queries between
+/// the vertices of the input graph, so that callers do not notice the turn
+/// restrictions other than in the paths.
+///
+/// Like the searches on the input graph, the path from a vertex to itself is
+/// the vertex alone, with distance 0.
+pub struct EdgeBased<P> {
+    pathfinder: P,
+    mapping: EdgeBasedMapping,
+}
+
+impl<P: PathFinding> EdgeBased<P> {
+    /// `pathfinder` has to be built on the edge based graph `mapping` belongs
+    /// to.
+    pub fn new(pathfinder: P, mapping: EdgeBasedMapping) -> Self {
+        EdgeBased {
+            pathfinder,
+            mapping,
+        }
+    }
+
+    /// The path finder on the edge based graph.
+    pub fn pathfinder(&self) -> &P {
+        &self.pathfinder
+    }
+
+    pub fn mapping(&self) -> &EdgeBasedMapping {
+        &self.mapping
+    }
+
+    /// Returns the source and target vertex of the edge based graph. Input
+    /// vertices without edges may have no vertices in the edge based graph.
+    fn source_and_target(&self, source: Vertex, target: Vertex) -> Option<(Vertex, Vertex)> {
+        let number_of_vertices = self.pathfinder.number_of_vertices();
+        let source = self.mapping.source(source)?;
+        let target = self.mapping.target(target)?;
+        (source < number_of_vertices && target < number_of_vertices).then_some((source, target))
+    }
+
+    /// Returns whether `source` and `target` are the same input vertex. The
+    /// source and the target vertex of the edge based graph differ even then.
+    fn is_trivial(&self, source: Vertex, target: Vertex) -> bool {
+        source == target && source < self.mapping.number_of_vertices()
+    }
+}
+
+impl<P: PathFinding> PathFinding for EdgeBased<P> {
+    fn shortest_path(&self, source: Vertex, target: Vertex) -> Option<Path> {
+        if self.is_trivial(source, target) {
+            return Some(Path {
+                vertices: vec![source],
+                distance: 0,
+            });
+        }
+        let (source, target) = self.source_and_target(source, target)?;
+        let path = self.pathfinder.shortest_path(source, target)?;
+        Some(self.mapping.restore_path(&path))
+    }
+
+    fn shortest_path_distance(&self, source: Vertex, target: Vertex) -> Option<Distance> {
+        if self.is_trivial(source, target) {
+            return Some(0);
+        }
+        let (source, target) = self.source_and_target(source, target)?;
+        self.pathfinder.shortest_path_distance(source, target)
+    }
+
+    fn number_of_vertices(&self) -> u32 {
+        self.mapping.number_of_vertices()
+    }
+}
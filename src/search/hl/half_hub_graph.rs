@@ -7,7 +7,10 @@ use serde::{Deserialize, Serialize};
 
 use super::hub_graph::HubLabelEntry;
 use crate::{
-    graphs::{Distance, Graph, Vertex, WeightedEdge},
+    graphs::{
+        permutation::{Permutation, Renumber},
+        Distance, Graph, StaticGraph, Vertex, WeightedEdge,
+    },
     search::{
         ch::brute_force::create_shortcuts,
         collections::{
@@ -41,8 +44,8 @@ impl HalfHubGraph {
         HalfHubGraph { labels, indices }
     }
 
-    pub fn by_brute_force(
-        graph: &dyn Graph,
+    pub fn by_brute_force<G: Graph + StaticGraph + ?Sized>(
+        graph: &G,
         vertex_to_level: &Vec<u32>,
         progress_bar: ProgressBar,
     ) -> (HalfHubGraph, HashMap<(Vertex, Vertex), Vertex>) {
@@ -77,8 +80,43 @@ impl HalfHubGraph {
     }
 }
 
-pub fn get_hub_label_with_brute_force_wrapped(
-    graph: &dyn Graph,
+impl Renumber for HalfHubGraph {
+    /// Returns the labels of the graph renumbered by `permutation`. Labels are
+    /// sorted by hub again, so predecessor indices are remapped.
+    fn renumbered(&self, permutation: &Permutation) -> Self {
+        let labels = (0..self.number_of_vertices())
+            .map(|vertex| {
+                let label = self.get_label(permutation.to_old(vertex));
+
+                let mut order = (0..label.len()).collect_vec();
+                order.sort_unstable_by_key(|&index| permutation.to_new(label[index].vertex));
+                let mut new_index = vec![0; label.len()];
+                for (index, &old_index) in order.iter().enumerate() {
+                    new_index[old_index] = index as u32;
+                }
+
+                order
+                    .iter()
+                    .map(|&old_index| {
+                        let entry = &label[old_index];
+                        HubLabelEntry {
+                            vertex: permutation.to_new(entry.vertex),
+                            distance: entry.distance,
+                            predecessor_index: entry
+                                .predecessor_index
+                                .map(|predecessor_index| new_index[predecessor_index as usize]),
+                        }
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        HalfHubGraph::new(&labels)
+    }
+}
+
+pub fn get_hub_label_with_brute_force_wrapped<G: Graph + StaticGraph + ?Sized>(
+    graph: &G,
     vertex_to_level: &Vec<u32>,
     source: Vertex,
 ) -> (Vec<HubLabelEntry>, Vec<((Vertex, Vertex), Vertex)>) {
@@ -96,14 +134,20 @@ pub fn get_hub_label_with_brute_force_wrapped(
     )
 }
 
-pub fn get_hub_label_with_brute_force(
-    graph: &dyn Graph,
-    data: &mut dyn DijkstraData,
-    expanded: &mut dyn VertexExpandedData,
-    queue: &mut dyn VertexDistanceQueue,
+pub fn get_hub_label_with_brute_force<G, D, E, Q>(
+    graph: &G,
+    data: &mut D,
+    expanded: &mut E,
+    queue: &mut Q,
     vertex_to_level: &Vec<u32>,
     source: Vertex,
-) -> (Vec<HubLabelEntry>, Vec<((Vertex, Vertex), Vertex)>) {
+) -> (Vec<HubLabelEntry>, Vec<((Vertex, Vertex), Vertex)>)
+where
+    G: StaticGraph + ?Sized,
+    D: DijkstraData + ?Sized,
+    E: VertexExpandedData + ?Sized,
+    Q: VertexDistanceQueue + ?Sized,
+{
     // Maps (vertex -> (max level on path from source to vertex, associated vertex))
     //
     // A vertex is a head of a ch edge if its levels equals the max level on its
@@ -151,9 +195,9 @@ pub fn get_hub_label_with_brute_force(
             }
         }
 
-        for edge in graph.edges(tail) {
+        for edge in graph.out_edges(tail) {
             let current_distance_head = data.get_distance(edge.head);
-            let alternative_distance_head = distance_tail + edge.weight;
+            let alternative_distance_head = distance_tail.saturating_add(edge.weight);
             if alternative_distance_head < current_distance_head {
                 data.set_distance(edge.head, alternative_distance_head);
                 data.set_predecessor(edge.head, tail);
@@ -205,7 +249,7 @@ pub fn get_hub_label_by_merging(
                         .get(&entry.vertex)
                         .map(|(_edge, distance)| distance)
                         .unwrap_or(&Distance::MAX);
-                    let alternative_distance = entry.distance + edge_distance;
+                    let alternative_distance = entry.distance.saturating_add(edge_distance);
                     if alternative_distance < current_distance {
                         let mut predecessor = entry.predecessor_index;
                         if entry.predecessor_index.is_none() && edge.is_some() {
@@ -7,11 +7,15 @@ use serde::{Deserialize, Serialize};
 
 use super::half_hub_graph::{get_hub_label_by_merging, set_predecessor, HalfHubGraph};
 use crate::{
-    graphs::{reversible_graph::ReversibleGraph, Distance, Graph, Level, Vertex},
+    graphs::{
+        permutation::{Permutation, Renumber},
+        reversible_graph::ReversibleGraph,
+        Distance, Graph, Level, StaticGraph, Vertex,
+    },
     search::{
         ch::contracted_graph::{vertex_to_level, ContractedGraph},
         collections::dijkstra_data::Path,
-        shortcuts::replace_shortcuts_slowly,
+        shortcuts::{replace_shortcuts_slowly, ShortcutLookup},
         PathFinding,
     },
     utility::get_progressbar,
@@ -27,7 +31,7 @@ pub struct HubGraph {
 }
 
 impl HubGraph {
-    pub fn by_brute_force<G: Graph + Default>(
+    pub fn by_brute_force<G: Graph + StaticGraph>(
         graph: &ReversibleGraph<G>,
         level_to_vertex: &Vec<Vertex>,
     ) -> HubGraph {
@@ -124,6 +128,23 @@ impl HubGraph {
     }
 }
 
+impl Renumber for HubGraph {
+    /// Returns the hub graph of the graph renumbered by `permutation`, with
+    /// the same levels and shortcuts.
+    fn renumbered(&self, permutation: &Permutation) -> Self {
+        let level_to_vertex = permutation.apply_to_level_to_vertex(&self.level_to_vertex);
+        let vertex_to_level = vertex_to_level(&level_to_vertex);
+
+        HubGraph {
+            forward: self.forward.renumbered(permutation),
+            backward: self.backward.renumbered(permutation),
+            shortcuts: permutation.apply_to_shortcuts(&self.shortcuts),
+            level_to_vertex,
+            vertex_to_level,
+        }
+    }
+}
+
 impl PathFinding for HubGraph {
     fn shortest_path(&self, source: Vertex, target: Vertex) -> Option<Path> {
         let forward_label = self.forward.get_label(source);
@@ -142,18 +163,18 @@ impl PathFinding for HubGraph {
     }
 }
 
-fn create_label(
-    contracted_graph_direction1: &dyn Graph,
+fn create_label<G: StaticGraph + ?Sized>(
+    contracted_graph_direction1: &G,
     vertex: u32,
     labels_direction1: &mut Vec<Vec<HubLabelEntry>>,
     labels_direction2: &Vec<Vec<HubLabelEntry>>,
 ) {
     let mut neighbor_labels = contracted_graph_direction1
-        .edges(vertex)
+        .out_edges(vertex)
         .map(|edge| {
             let neighbor_label = labels_direction1.get(edge.head as usize).unwrap();
 
-            (Some(edge.clone()), neighbor_label)
+            (Some(edge), neighbor_label)
         })
         .collect::<Vec<_>>();
     neighbor_labels.push((None, labels_direction1.get(vertex as usize).unwrap()));
@@ -199,10 +220,33 @@ impl HubLabelEntry {
     }
 }
 
-pub fn get_path_from_overlapp(
-    forward_label: &[HubLabelEntry],
-    backward_label: &[HubLabelEntry],
-    shortcuts: &HashMap<(Vertex, Vertex), Vertex>,
+/// Read access to an entry of a hub label, so that labels can be queried
+/// regardless of their memory layout.
+pub trait LabelEntry {
+    fn vertex(&self) -> Vertex;
+    fn distance(&self) -> Distance;
+    /// index of predecessor. None if no predecessor.
+    fn predecessor_index(&self) -> Option<u32>;
+}
+
+impl LabelEntry for HubLabelEntry {
+    fn vertex(&self) -> Vertex {
+        self.vertex
+    }
+
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn predecessor_index(&self) -> Option<u32> {
+        self.predecessor_index
+    }
+}
+
+pub fn get_path_from_overlapp<E: LabelEntry, S: ShortcutLookup + ?Sized>(
+    forward_label: &[E],
+    backward_label: &[E],
+    shortcuts: &S,
 ) -> Option<Path> {
     let (distance, (forward_index, backward_index)) = overlapp(forward_label, backward_label)?;
 
@@ -221,22 +265,22 @@ pub fn get_path_from_overlapp(
     })
 }
 
-pub fn get_path_from_label(label: &[HubLabelEntry], index: usize) -> Vec<Vertex> {
-    let mut path = vec![label[index].vertex];
+pub fn get_path_from_label<E: LabelEntry>(label: &[E], index: usize) -> Vec<Vertex> {
+    let mut path = vec![label[index].vertex()];
 
     let mut index = index;
-    while let Some(predecessor_index) = label[index].predecessor_index {
+    while let Some(predecessor_index) = label[index].predecessor_index() {
         index = predecessor_index as usize;
-        path.push(label[index].vertex);
+        path.push(label[index].vertex());
     }
 
     path.reverse();
     path
 }
 
-pub fn overlapp(
-    forward_label: &[HubLabelEntry],
-    backward_label: &[HubLabelEntry],
+pub fn overlapp<E: LabelEntry>(
+    forward_label: &[E],
+    backward_label: &[E],
 ) -> Option<(Distance, (usize, usize))> {
     let mut overlapp = None;
 
@@ -244,16 +288,17 @@ pub fn overlapp(
     let mut backward_index = 0;
 
     while forward_index < forward_label.len() && backward_index < backward_label.len() {
-        let forward_vertex = forward_label[forward_index].vertex;
-        let backward_vertex = backward_label[backward_index].vertex;
+        let forward_vertex = forward_label[forward_index].vertex();
+        let backward_vertex = backward_label[backward_index].vertex();
 
         match forward_vertex.cmp(&backward_vertex) {
             Ordering::Less => {
                 forward_index += 1;
             }
             Ordering::Equal => {
-                let alternative_distance = forward_label[forward_index as usize].distance
-                    + backward_label[backward_index as usize].distance;
+                let alternative_distance = forward_label[forward_index]
+                    .distance()
+                    .saturating_add(backward_label[backward_index].distance());
                 if alternative_distance
                     < overlapp
                         .map(|(current_distance, _)| current_distance)
@@ -277,13 +322,38 @@ pub fn overlapp(
 #[cfg(test)]
 mod tests {
     use crate::{
-        graphs::{large_test_graph, Graph},
+        graphs::{
+            generators::generated_test_graphs, large_test_graph, reversible_graph::ReversibleGraph,
+            vec_vec_graph::VecVecGraph, Distance, Graph, WeightedEdge,
+        },
         search::{
             ch::contracted_graph::ContractedGraph,
             hl::hub_graph::{get_path_from_overlapp, HubGraph},
+            PathFinding,
         },
     };
 
+    #[test]
+    fn hub_graphs_of_generated_graphs() {
+        for (graph, tests) in generated_test_graphs() {
+            let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&graph, u32::MAX);
+            let merged_hub_graph = HubGraph::by_merging(&contracted_graph);
+            let brute_force_hub_graph =
+                HubGraph::by_brute_force(&graph, contracted_graph.level_to_vertex());
+
+            for test in tests {
+                assert_eq!(
+                    merged_hub_graph.shortest_path_distance(test.source, test.target),
+                    test.distance
+                );
+                assert_eq!(
+                    brute_force_hub_graph.shortest_path_distance(test.source, test.target),
+                    test.distance
+                );
+            }
+        }
+    }
+
     #[test]
     fn hub_graph_by_merging() {
         let (graph, tests) = large_test_graph();
@@ -323,4 +393,42 @@ mod tests {
             assert_eq!(test.distance, path_distance)
         }
     }
+
+    #[test]
+    fn near_maximum_weights_saturate() {
+        // 0 <-> 1 <-> 2 <-> 3 where 0 -> 2 is just below the maximum distance
+        // and 0 -> 3 would exceed it.
+        let half = Distance::MAX / 2;
+        let mut edges = Vec::new();
+        for (tail, head, weight) in [(0, 1, half), (1, 2, half), (2, 3, 1)] {
+            edges.push(WeightedEdge::new(tail, head, weight));
+            edges.push(WeightedEdge::new(head, tail, weight));
+        }
+        let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+
+        let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&graph, u32::MAX);
+        let level_to_vertex = contracted_graph.level_to_vertex().clone();
+        let pathfinders: [Box<dyn PathFinding>; 5] = [
+            Box::new(graph.out_graph().clone()),
+            Box::new(ContractedGraph::by_brute_force(&graph, &level_to_vertex)),
+            Box::new(HubGraph::by_merging(&contracted_graph)),
+            Box::new(HubGraph::by_brute_force(&graph, &level_to_vertex)),
+            Box::new(contracted_graph),
+        ];
+
+        for pathfinder in pathfinders.iter() {
+            assert_eq!(
+                pathfinder.shortest_path_distance(0, 2),
+                Some(Distance::MAX - 1)
+            );
+            assert_eq!(pathfinder.shortest_path_distance(1, 3), Some(half + 1));
+            assert_eq!(
+                pathfinder.shortest_path(2, 0).map(|path| path.vertices),
+                Some(vec![2, 1, 0])
+            );
+            // Distances saturate at the maximum, which means unreachable.
+            assert_eq!(pathfinder.shortest_path_distance(0, 3), None);
+            assert!(pathfinder.shortest_path(3, 0).is_none());
+        }
+    }
 }
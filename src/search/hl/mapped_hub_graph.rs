@@ -0,0 +1,337 @@
+//! An on-disk layout of a [`HubGraph`] that is queried in place from a memory
+//! mapped file. Opening such a file only reads its header, the labels are
+//! paged in on demand and the page cache is shared between all processes
+//! mapping the same file.
+//!
+//! The file consists of a header followed by five sections, each starting at
+//! a multiple of 8 bytes. Integers use the native byte order, i.e. little
+//! endian on all common platforms.
+//!
+//! | section          | content                                          |
+//! |------------------|--------------------------------------------------|
+//! | header           | [`MappedHeader`]                                 |
+//! | forward offsets  | `number_of_vertices + 1` u64, label `v` is `[offsets[v], offsets[v + 1])` |
+//! | forward entries  | [`MappedLabelEntry`]s of all forward labels      |
+//! | backward offsets | like the forward offsets                         |
+//! | backward entries | like the forward entries                         |
+//! | shortcuts        | [`MappedShortcut`]s sorted by `(tail, head)`     |
+//!
+//! Unlike a [`crate::container`], there is no checksum, as computing it would
+//! read the whole file.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    ops::Range,
+    path::Path,
+};
+
+use bytemuck::{Pod, Zeroable};
+use memmap2::Mmap;
+
+use super::hub_graph::{get_path_from_overlapp, overlapp, HubGraph, LabelEntry};
+use crate::{
+    container::GraphFingerprint,
+    error::{ContainerErrorKind, Error, Result},
+    graphs::{Distance, Vertex},
+    mapped_file::{map_file, offsets, row, section, SectionLayout, SectionWriter},
+    search::{
+        collections::dijkstra_data::Path as SearchPath, shortcuts::ShortcutLookup, PathFinding,
+    },
+};
+
+pub const MAGIC: [u8; 8] = *b"FPHUBMAP";
+
+/// Bump this whenever the layout changes.
+pub const VERSION: u32 = 1;
+
+/// Marks a missing predecessor in [`MappedLabelEntry::predecessor_index`].
+const NO_PREDECESSOR: u32 = u32::MAX;
+
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct MappedHeader {
+    pub magic: [u8; 8],
+    pub version: u32,
+    pub reserved: u32,
+    pub number_of_vertices: u64,
+    pub number_of_forward_entries: u64,
+    pub number_of_backward_entries: u64,
+    pub number_of_shortcuts: u64,
+    pub fingerprint_number_of_vertices: u64,
+    pub fingerprint_number_of_edges: u64,
+    pub fingerprint_hash: u64,
+}
+
+/// A [`super::hub_graph::HubLabelEntry`] without `Option`, so that it can be
+/// read from the file as it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct MappedLabelEntry {
+    pub vertex: Vertex,
+    /// index of predecessor. [`u32::MAX`] if no predecessor.
+    pub predecessor_index: u32,
+    pub distance: Distance,
+}
+
+impl LabelEntry for MappedLabelEntry {
+    fn vertex(&self) -> Vertex {
+        self.vertex
+    }
+
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn predecessor_index(&self) -> Option<u32> {
+        (self.predecessor_index != NO_PREDECESSOR).then_some(self.predecessor_index)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct MappedShortcut {
+    pub tail: Vertex,
+    pub head: Vertex,
+    pub skipped_vertex: Vertex,
+}
+
+impl ShortcutLookup for [MappedShortcut] {
+    fn skipped_vertex(&self, tail: Vertex, head: Vertex) -> Option<Vertex> {
+        self.binary_search_by_key(&(tail, head), |shortcut| (shortcut.tail, shortcut.head))
+            .ok()
+            .map(|index| self[index].skipped_vertex)
+    }
+}
+
+/// Byte ranges of the sections of a mapped hub graph.
+struct Sections {
+    forward_offsets: Range<usize>,
+    forward_entries: Range<usize>,
+    backward_offsets: Range<usize>,
+    backward_entries: Range<usize>,
+    shortcuts: Range<usize>,
+}
+
+impl Sections {
+    /// Returns the sections of a file with the given header, or `None` if
+    /// their size overflows.
+    fn new(header: &MappedHeader) -> Option<Sections> {
+        let mut layout = SectionLayout::after_header::<MappedHeader>();
+        let number_of_offsets = header.number_of_vertices.checked_add(1)?;
+
+        Some(Sections {
+            forward_offsets: layout.next::<u64>(number_of_offsets)?,
+            forward_entries: layout.next::<MappedLabelEntry>(header.number_of_forward_entries)?,
+            backward_offsets: layout.next::<u64>(number_of_offsets)?,
+            backward_entries: layout.next::<MappedLabelEntry>(header.number_of_backward_entries)?,
+            shortcuts: layout.next::<MappedShortcut>(header.number_of_shortcuts)?,
+        })
+    }
+}
+
+/// A hub graph that is queried straight from a memory mapped file, see the
+/// module documentation.
+pub struct MappedHubGraph {
+    mmap: Mmap,
+    header: MappedHeader,
+    sections: Sections,
+}
+
+impl MappedHubGraph {
+    /// Maps the file at `path` and checks its header.
+    ///
+    /// The file must not be modified while it is mapped.
+    pub fn open(path: &Path) -> Result<MappedHubGraph> {
+        let (mmap, header): (_, MappedHeader) = map_file(path, MAGIC, VERSION)?;
+
+        let sections = Sections::new(&header)
+            .filter(|sections| sections.shortcuts.end <= mmap.len())
+            .ok_or_else(|| Error::container(path, ContainerErrorKind::Truncated))?;
+
+        let hub_graph = MappedHubGraph {
+            mmap,
+            header,
+            sections,
+        };
+
+        // Cheap consistency check, the offsets must end at the last entry.
+        let forward_offsets = hub_graph.forward_offsets();
+        let backward_offsets = hub_graph.backward_offsets();
+        if forward_offsets.last() != Some(&header.number_of_forward_entries)
+            || backward_offsets.last() != Some(&header.number_of_backward_entries)
+        {
+            return Err(Error::container(path, ContainerErrorKind::Truncated));
+        }
+
+        Ok(hub_graph)
+    }
+
+    /// The graph the hub graph was built from.
+    pub fn fingerprint(&self) -> GraphFingerprint {
+        GraphFingerprint {
+            number_of_vertices: self.header.fingerprint_number_of_vertices,
+            number_of_edges: self.header.fingerprint_number_of_edges,
+            hash: self.header.fingerprint_hash,
+        }
+    }
+
+    pub fn forward_label(&self, vertex: Vertex) -> &[MappedLabelEntry] {
+        row(
+            self.forward_offsets(),
+            section(&self.mmap, &self.sections.forward_entries),
+            vertex as usize,
+        )
+    }
+
+    pub fn backward_label(&self, vertex: Vertex) -> &[MappedLabelEntry] {
+        row(
+            self.backward_offsets(),
+            section(&self.mmap, &self.sections.backward_entries),
+            vertex as usize,
+        )
+    }
+
+    pub fn shortcuts(&self) -> &[MappedShortcut] {
+        section(&self.mmap, &self.sections.shortcuts)
+    }
+
+    fn forward_offsets(&self) -> &[u64] {
+        section(&self.mmap, &self.sections.forward_offsets)
+    }
+
+    fn backward_offsets(&self) -> &[u64] {
+        section(&self.mmap, &self.sections.backward_offsets)
+    }
+}
+
+impl PathFinding for MappedHubGraph {
+    fn shortest_path(&self, source: Vertex, target: Vertex) -> Option<SearchPath> {
+        let forward_label = self.forward_label(source);
+        let backward_label = self.backward_label(target);
+        get_path_from_overlapp(forward_label, backward_label, self.shortcuts())
+    }
+
+    fn shortest_path_distance(&self, source: Vertex, target: Vertex) -> Option<Distance> {
+        let forward_label = self.forward_label(source);
+        let backward_label = self.backward_label(target);
+        overlapp(forward_label, backward_label).map(|(distance, _)| distance)
+    }
+
+    fn number_of_vertices(&self) -> u32 {
+        self.header.number_of_vertices as u32
+    }
+}
+
+/// Writes `hub_graph` in the layout of [`MappedHubGraph`] into the file at
+/// `path`. `fingerprint` identifies the graph the hub graph was built from.
+pub fn write_mapped_hub_graph_file(
+    path: &Path,
+    hub_graph: &HubGraph,
+    fingerprint: &GraphFingerprint,
+) -> Result<()> {
+    let file = File::create(path).map_err(|error| Error::io(path, error))?;
+    write_mapped_hub_graph(BufWriter::new(file), hub_graph, fingerprint)
+        .map_err(|error| Error::io(path, error))
+}
+
+pub fn write_mapped_hub_graph<W: Write>(
+    writer: W,
+    hub_graph: &HubGraph,
+    fingerprint: &GraphFingerprint,
+) -> std::io::Result<()> {
+    let number_of_vertices = hub_graph.forward.number_of_vertices();
+
+    let mut shortcuts = hub_graph
+        .shortcuts
+        .iter()
+        .map(|(&(tail, head), &skipped_vertex)| MappedShortcut {
+            tail,
+            head,
+            skipped_vertex,
+        })
+        .collect::<Vec<_>>();
+    shortcuts.sort_unstable_by_key(|shortcut| (shortcut.tail, shortcut.head));
+
+    let header = MappedHeader {
+        magic: MAGIC,
+        version: VERSION,
+        reserved: 0,
+        number_of_vertices: number_of_vertices as u64,
+        number_of_forward_entries: hub_graph.forward.number_of_entires(),
+        number_of_backward_entries: hub_graph.backward.number_of_entires(),
+        number_of_shortcuts: shortcuts.len() as u64,
+        fingerprint_number_of_vertices: fingerprint.number_of_vertices,
+        fingerprint_number_of_edges: fingerprint.number_of_edges,
+        fingerprint_hash: fingerprint.hash,
+    };
+
+    let mut writer = SectionWriter::new(writer);
+    writer.write(&[header])?;
+
+    for labels in [&hub_graph.forward, &hub_graph.backward] {
+        let offsets = offsets((0..number_of_vertices).map(|vertex| labels.get_label(vertex).len()));
+        writer.write(&offsets)?;
+
+        let entries = (0..number_of_vertices)
+            .flat_map(|vertex| labels.get_label(vertex))
+            .map(|entry| MappedLabelEntry {
+                vertex: entry.vertex,
+                predecessor_index: entry.predecessor_index.unwrap_or(NO_PREDECESSOR),
+                distance: entry.distance,
+            })
+            .collect::<Vec<_>>();
+        writer.write(&entries)?;
+    }
+
+    writer.write(&shortcuts)?;
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_mapped_hub_graph_file, MappedHubGraph};
+    use crate::{
+        container::GraphFingerprint,
+        graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, WeightedEdge},
+        search::{ch::contracted_graph::ContractedGraph, hl::hub_graph::HubGraph, PathFinding},
+    };
+
+    #[test]
+    fn queries_match_hub_graph() {
+        let mut edges = Vec::new();
+        for vertex in 0..11 {
+            edges.push(WeightedEdge::new(vertex, vertex + 1, 1 + vertex as u64 % 3));
+            edges.push(WeightedEdge::new(vertex + 1, vertex, 2));
+        }
+        edges.push(WeightedEdge::new(0, 6, 4));
+        edges.push(WeightedEdge::new(9, 2, 3));
+        let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+
+        let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&graph, u32::MAX);
+        let hub_graph = HubGraph::by_merging(&contracted_graph);
+        let fingerprint = GraphFingerprint::of(graph.out_graph());
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write_mapped_hub_graph_file(file.path(), &hub_graph, &fingerprint).unwrap();
+        let mapped_hub_graph = MappedHubGraph::open(file.path()).unwrap();
+
+        assert_eq!(mapped_hub_graph.fingerprint(), fingerprint);
+        assert_eq!(
+            PathFinding::number_of_vertices(&mapped_hub_graph),
+            PathFinding::number_of_vertices(&hub_graph)
+        );
+        for source in 0..12 {
+            for target in 0..12 {
+                let expected = hub_graph.shortest_path(source, target);
+                let path = mapped_hub_graph.shortest_path(source, target);
+                assert_eq!(
+                    path.as_ref().map(|path| (&path.vertices, path.distance)),
+                    expected
+                        .as_ref()
+                        .map(|path| (&path.vertices, path.distance))
+                );
+            }
+        }
+    }
+}
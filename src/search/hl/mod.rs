@@ -1,2 +1,3 @@
 pub mod half_hub_graph;
 pub mod hub_graph;
+pub mod mapped_hub_graph;
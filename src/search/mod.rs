@@ -6,8 +6,11 @@ pub mod alt;
 pub mod ch;
 pub mod collections;
 pub mod dijkstra;
+pub mod edge_based;
 pub mod hl;
 pub mod path;
+pub mod reachability;
+pub mod reordered;
 pub mod shortcuts;
 
 pub trait DistanceHeuristic: Send + Sync {
@@ -0,0 +1,52 @@
+//! Skipping queries that cannot have a path, see
+//! [`StronglyConnectedComponents::may_reach`].
+
+use super::{collections::dijkstra_data::Path, PathFinding};
+use crate::graphs::{strongly_connected_components::StronglyConnectedComponents, Distance, Vertex};
+
+/// A path finder that answers queries whose target is not reachable by
+/// comparing component ids, without running a search. This saves exploring
+/// the whole search space of a CH query into a disconnected island.
+pub struct ReachabilityChecked<P> {
+    pathfinder: P,
+    components: StronglyConnectedComponents,
+}
+
+impl<P: PathFinding> ReachabilityChecked<P> {
+    /// `components` have to be computed on the graph `pathfinder` was built
+    /// from.
+    pub fn new(pathfinder: P, components: StronglyConnectedComponents) -> Self {
+        ReachabilityChecked {
+            pathfinder,
+            components,
+        }
+    }
+
+    pub fn pathfinder(&self) -> &P {
+        &self.pathfinder
+    }
+
+    pub fn components(&self) -> &StronglyConnectedComponents {
+        &self.components
+    }
+}
+
+impl<P: PathFinding> PathFinding for ReachabilityChecked<P> {
+    fn shortest_path(&self, source: Vertex, target: Vertex) -> Option<Path> {
+        if !self.components.may_reach(source, target) {
+            return None;
+        }
+        self.pathfinder.shortest_path(source, target)
+    }
+
+    fn shortest_path_distance(&self, source: Vertex, target: Vertex) -> Option<Distance> {
+        if !self.components.may_reach(source, target) {
+            return None;
+        }
+        self.pathfinder.shortest_path_distance(source, target)
+    }
+
+    fn number_of_vertices(&self) -> u32 {
+        self.pathfinder.number_of_vertices()
+    }
+}
@@ -0,0 +1,60 @@
+//! Queries on internally renumbered structures, see
+//! [`crate::graphs::reordering`].
+
+use super::{collections::dijkstra_data::Path, PathFinding};
+use crate::graphs::{
+    permutation::{Permutation, Renumber},
+    Distance, Vertex,
+};
+
+/// A path finder whose vertices are renumbered by a permutation, e.g. for a
+/// better memory layout. Sources and targets are given and paths are returned
+/// in the old ids, so callers do not notice the renumbering.
+pub struct Reordered<P> {
+    pathfinder: P,
+    permutation: Permutation,
+}
+
+impl<P: Renumber> Reordered<P> {
+    /// Renumbers `pathfinder` by `permutation`, which has to cover all of its
+    /// vertices.
+    pub fn new(pathfinder: &P, permutation: Permutation) -> Self {
+        Reordered {
+            pathfinder: pathfinder.renumbered(&permutation),
+            permutation,
+        }
+    }
+}
+
+impl<P> Reordered<P> {
+    /// The renumbered path finder, which uses new ids.
+    pub fn pathfinder(&self) -> &P {
+        &self.pathfinder
+    }
+
+    pub fn permutation(&self) -> &Permutation {
+        &self.permutation
+    }
+
+    fn to_new(&self, vertex: Vertex) -> Option<Vertex> {
+        self.permutation.old_to_new().get(vertex as usize).cloned()
+    }
+}
+
+impl<P: PathFinding> PathFinding for Reordered<P> {
+    fn shortest_path(&self, source: Vertex, target: Vertex) -> Option<Path> {
+        let path = self
+            .pathfinder
+            .shortest_path(self.to_new(source)?, self.to_new(target)?)?;
+        Some(self.permutation.restore_path(&path))
+    }
+
+    fn shortest_path_distance(&self, source: Vertex, target: Vertex) -> Option<Distance> {
+        self.pathfinder
+            .shortest_path_distance(self.to_new(source)?, self.to_new(target)?)
+    }
+
+    fn number_of_vertices(&self) -> u32 {
+        self.pathfinder.number_of_vertices()
+    }
+}
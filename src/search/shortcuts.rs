@@ -13,16 +13,27 @@ pub fn get_fast_shortcuts(
         let mut path = vec![tail, vertex, head];
         path.remove(0); // remove tail
         path.pop(); // remove head
-        replace_shortcuts_slowly(&mut path, &single_step_shortcuts);
+        replace_shortcuts_slowly(&mut path, single_step_shortcuts);
         shortcuts.insert((tail, head), path);
     }
 
     shortcuts
 }
 
-pub fn replace_shortcuts_slowly(
+/// Looks up the vertex a shortcut skips.
+pub trait ShortcutLookup {
+    fn skipped_vertex(&self, tail: Vertex, head: Vertex) -> Option<Vertex>;
+}
+
+impl ShortcutLookup for HashMap<(Vertex, Vertex), Vertex> {
+    fn skipped_vertex(&self, tail: Vertex, head: Vertex) -> Option<Vertex> {
+        self.get(&(tail, head)).copied()
+    }
+}
+
+pub fn replace_shortcuts_slowly<S: ShortcutLookup + ?Sized>(
     path_with_shortcuts: &mut Vec<Vertex>,
-    shortcuts: &HashMap<(Vertex, Vertex), Vertex>,
+    shortcuts: &S,
 ) {
     let mut path_without_shortcuts = Vec::new();
 
@@ -32,9 +43,9 @@ pub fn replace_shortcuts_slowly(
         let head = path_with_shortcuts.pop().unwrap();
         let tail = *path_with_shortcuts.last().unwrap();
 
-        if let Some(vertex) = shortcuts.get(&(tail, head)) {
+        if let Some(vertex) = shortcuts.skipped_vertex(tail, head) {
             // println!("{} -> {} skipped {}", tail, head, vertex);
-            path_with_shortcuts.push(*vertex);
+            path_with_shortcuts.push(vertex);
             path_with_shortcuts.push(head);
         } else {
             // println!("{} -> {} is a normal edge", tail, head);
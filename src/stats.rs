@@ -0,0 +1,317 @@
+//! Statistics of graphs and contracted graphs, e.g. to compare inputs or to
+//! judge a contraction order.
+//!
+//! [`GraphStats`] and [`ContractedGraphStats`] are plain serializable structs,
+//! [`StatsReport`] combines them into one json report.
+
+use std::{fs::File, io::BufWriter, path::Path};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    graphs::{Distance, Graph, Vertex},
+    search::{
+        ch::contracted_graph::ContractedGraph, collections::dijkstra_data::DijkstraData,
+        dijkstra::dijkstra_one_to_all_wraped,
+    },
+};
+
+/// Counts of values in buckets of equal width. Bucket `i` holds the values in
+/// `min + i * bucket_width..min + (i + 1) * bucket_width`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    pub min: u64,
+    pub bucket_width: u64,
+    pub counts: Vec<u64>,
+}
+
+impl Histogram {
+    /// Puts every value into a bucket of its own, e.g. for degrees.
+    pub fn exact(values: &[u64]) -> Self {
+        Histogram::with_bucket_width(values, 0, 1)
+    }
+
+    /// Splits the range of `values` into at most `number_of_buckets` buckets.
+    pub fn with_buckets(values: &[u64], number_of_buckets: u64) -> Self {
+        let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+            return Histogram::default();
+        };
+        let bucket_width = ((max - min) / number_of_buckets.max(1)).saturating_add(1);
+        Histogram::with_bucket_width(values, min, bucket_width)
+    }
+
+    fn with_bucket_width(values: &[u64], min: u64, bucket_width: u64) -> Self {
+        let mut counts = Vec::new();
+        for &value in values {
+            let bucket = ((value - min) / bucket_width) as usize;
+            if bucket >= counts.len() {
+                counts.resize(bucket + 1, 0);
+            }
+            counts[bucket] += 1;
+        }
+
+        Histogram {
+            min,
+            bucket_width,
+            counts,
+        }
+    }
+
+    pub fn number_of_values(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+}
+
+/// Settings of [`GraphStats::of`].
+#[derive(Clone, Debug)]
+pub struct StatsOptions {
+    /// Number of Dijkstras from random sources for the diameter estimate.
+    pub diameter_samples: u32,
+    pub weight_buckets: u64,
+    pub seed: u64,
+}
+
+impl Default for StatsOptions {
+    fn default() -> Self {
+        StatsOptions {
+            diameter_samples: 16,
+            weight_buckets: 32,
+            seed: 0,
+        }
+    }
+}
+
+/// The longest shortest path found by the sampled Dijkstras, a lower bound of
+/// the diameter.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiameterEstimate {
+    pub number_of_samples: u32,
+    pub source: Vertex,
+    pub target: Vertex,
+    pub distance: Distance,
+    /// Number of edges of the path.
+    pub hops: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphStats {
+    pub number_of_vertices: u32,
+    pub number_of_edges: u64,
+    /// Vertices with at least one in or out edge.
+    pub number_of_non_trivial_vertices: u32,
+    pub out_degrees: Histogram,
+    pub in_degrees: Histogram,
+    /// Edges per vertex.
+    pub average_degree: f64,
+    /// Share of edges whose opposite edge exists, regardless of its weight.
+    pub bidirectional_ratio: f64,
+    pub weights: Histogram,
+    /// `None` if the graph has no edges or every sampled shortest path has
+    /// distance 0.
+    pub diameter: Option<DiameterEstimate>,
+}
+
+impl GraphStats {
+    pub fn of(graph: &dyn Graph, options: &StatsOptions) -> Self {
+        let number_of_vertices = graph.number_of_vertices();
+
+        let mut out_degrees = Vec::with_capacity(number_of_vertices as usize);
+        let mut in_degrees = vec![0; number_of_vertices as usize];
+        let mut weights = Vec::new();
+        let mut number_of_bidirectional_edges = 0u64;
+        for vertex in graph.vertices() {
+            let edges = graph.edges(vertex);
+            out_degrees.push(edges.len() as u64);
+            for edge in edges {
+                in_degrees[edge.head as usize] += 1;
+                weights.push(edge.weight);
+                if graph.get_weight(&edge.remove_weight().reversed()).is_some() {
+                    number_of_bidirectional_edges += 1;
+                }
+            }
+        }
+        let number_of_edges = weights.len() as u64;
+        let non_trivial_vertices = graph
+            .vertices()
+            .filter(|&vertex| out_degrees[vertex as usize] + in_degrees[vertex as usize] > 0)
+            .collect::<Vec<_>>();
+
+        GraphStats {
+            number_of_vertices,
+            number_of_edges,
+            number_of_non_trivial_vertices: non_trivial_vertices.len() as u32,
+            out_degrees: Histogram::exact(&out_degrees),
+            in_degrees: Histogram::exact(&in_degrees),
+            average_degree: number_of_edges as f64 / number_of_vertices.max(1) as f64,
+            bidirectional_ratio: number_of_bidirectional_edges as f64
+                / number_of_edges.max(1) as f64,
+            weights: Histogram::with_buckets(&weights, options.weight_buckets),
+            diameter: estimate_diameter(graph, &non_trivial_vertices, options),
+        }
+    }
+}
+
+/// Runs Dijkstras from random vertices of `vertices` and returns the longest
+/// shortest path they found.
+fn estimate_diameter(
+    graph: &dyn Graph,
+    vertices: &[Vertex],
+    options: &StatsOptions,
+) -> Option<DiameterEstimate> {
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let sources = (0..options.diameter_samples)
+        .filter_map(|_| vertices.choose(&mut rng).cloned())
+        .collect::<Vec<_>>();
+
+    let (source, target, distance) = sources
+        .into_par_iter()
+        .filter_map(|source| {
+            let data = dijkstra_one_to_all_wraped(graph, source);
+            let (target, distance) = (0..)
+                .zip(data.distances.iter())
+                .filter(|&(_, &distance)| distance != Distance::MAX)
+                .max_by_key(|&(target, &distance)| (distance, std::cmp::Reverse(target)))?;
+            Some((source, target, *distance))
+        })
+        .max_by_key(|&(source, target, distance)| {
+            (distance, std::cmp::Reverse((source, target)))
+        })?;
+    if distance == 0 {
+        return None;
+    }
+
+    let path = dijkstra_one_to_all_wraped(graph, source).get_path(target)?;
+    Some(DiameterEstimate {
+        number_of_samples: options.diameter_samples,
+        source,
+        target,
+        distance,
+        hops: path.vertices.len() as u32 - 1,
+    })
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContractedGraphStats {
+    pub number_of_vertices: u32,
+    pub number_of_upward_edges: u64,
+    pub number_of_downward_edges: u64,
+    pub number_of_shortcuts: u64,
+    /// The number of shortcuts that skip the vertex of each level.
+    pub shortcuts_per_level: Vec<u32>,
+    pub upward_degrees: Histogram,
+    pub downward_degrees: Histogram,
+    pub average_upward_degree: f64,
+    pub average_downward_degree: f64,
+}
+
+impl ContractedGraphStats {
+    pub fn of(contracted_graph: &ContractedGraph) -> Self {
+        let number_of_vertices = contracted_graph.level_to_vertex().len() as u32;
+        let vertex_to_level = contracted_graph.vertex_to_level();
+
+        let mut shortcuts_per_level = vec![0; number_of_vertices as usize];
+        for &skipped_vertex in contracted_graph.shortcuts().values() {
+            shortcuts_per_level[vertex_to_level[skipped_vertex as usize] as usize] += 1;
+        }
+
+        let degrees = |graph: &dyn Graph| {
+            (0..number_of_vertices)
+                .map(|vertex| graph.edges(vertex).len() as u64)
+                .collect::<Vec<_>>()
+        };
+        let upward_degrees = degrees(contracted_graph.upward_graph());
+        let downward_degrees = degrees(contracted_graph.downward_graph());
+        let number_of_upward_edges = upward_degrees.iter().sum::<u64>();
+        let number_of_downward_edges = downward_degrees.iter().sum::<u64>();
+
+        ContractedGraphStats {
+            number_of_vertices,
+            number_of_upward_edges,
+            number_of_downward_edges,
+            number_of_shortcuts: contracted_graph.shortcuts().len() as u64,
+            shortcuts_per_level,
+            upward_degrees: Histogram::exact(&upward_degrees),
+            downward_degrees: Histogram::exact(&downward_degrees),
+            average_upward_degree: number_of_upward_edges as f64 / number_of_vertices.max(1) as f64,
+            average_downward_degree: number_of_downward_edges as f64
+                / number_of_vertices.max(1) as f64,
+        }
+    }
+}
+
+/// Everything known about a graph, as written by the `graph_stats` binary.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatsReport {
+    pub graph: GraphStats,
+    pub contracted_graph: Option<ContractedGraphStats>,
+}
+
+impl StatsReport {
+    pub fn write_json_file(&self, path: &Path) -> Result<()> {
+        let file = File::create(path).map_err(|error| Error::io(path, error))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .map_err(|error| Error::io(path, error.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContractedGraphStats, GraphStats, Histogram, StatsOptions, StatsReport};
+    use crate::{
+        graphs::{reversible_graph::ReversibleGraph, vec_vec_graph::VecVecGraph, WeightedEdge},
+        search::ch::contracted_graph::ContractedGraph,
+    };
+
+    #[test]
+    fn stats_of_a_path() {
+        // The path 0 <-> 1 <-> 2 <-> 3 and the one way edge 3 -> 4.
+        let mut edges = Vec::new();
+        for vertex in 0..3 {
+            edges.push(WeightedEdge::new(vertex, vertex + 1, 10));
+            edges.push(WeightedEdge::new(vertex + 1, vertex, 10));
+        }
+        edges.push(WeightedEdge::new(3, 4, 45));
+        let graph = ReversibleGraph::<VecVecGraph>::from_edges(&edges);
+
+        let stats = GraphStats::of(graph.out_graph(), &StatsOptions::default());
+        assert_eq!(stats.number_of_vertices, 5);
+        assert_eq!(stats.number_of_edges, 7);
+        assert_eq!(stats.out_degrees.counts, vec![1, 1, 3]);
+        assert_eq!(stats.in_degrees.counts, vec![0, 3, 2]);
+        assert_eq!(stats.average_degree, 7.0 / 5.0);
+        assert_eq!(stats.bidirectional_ratio, 6.0 / 7.0);
+        assert_eq!(stats.weights.number_of_values(), 7);
+        assert_eq!(stats.weights.counts.first(), Some(&6));
+        let diameter = stats.diameter.as_ref().unwrap();
+        assert!(diameter.distance >= 45 && diameter.distance <= 75);
+        assert_eq!(
+            Histogram::with_buckets(&[3, 4, 13], 2),
+            Histogram {
+                min: 3,
+                bucket_width: 6,
+                counts: vec![2, 1],
+            }
+        );
+
+        let contracted_graph = ContractedGraph::with_dijkstra_witness_search(&graph, 10);
+        let contracted_graph_stats = ContractedGraphStats::of(&contracted_graph);
+        assert_eq!(
+            contracted_graph_stats
+                .shortcuts_per_level
+                .iter()
+                .sum::<u32>() as u64,
+            contracted_graph_stats.number_of_shortcuts
+        );
+        assert_eq!(contracted_graph_stats.upward_degrees.number_of_values(), 5);
+
+        let report = StatsReport {
+            graph: stats,
+            contracted_graph: Some(contracted_graph_stats),
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<StatsReport>(&json).unwrap(), report);
+    }
+}
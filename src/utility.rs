@@ -9,11 +9,14 @@ use std::{
 
 use indicatif::*;
 use itertools::Itertools;
+use log::warn;
 use rand::prelude::*;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    container::{read_container_file, write_container_file, GraphFingerprint, Payload},
+    error::{ContainerErrorKind, Error},
     graphs::{Graph, Level, Vertex},
     search::{
         ch::brute_force::get_ch_edges_wrapped, dijkstra::dijkstra_one_to_one_path_wrapped,
@@ -679,6 +682,45 @@ where
     pb.finish_and_clear();
 }
 
+/// Writes `value` as container, see [`crate::container`].
+pub fn write_container_with_spinnner<T: Payload>(
+    name: &str,
+    path: &Path,
+    value: &T,
+    fingerprint: &GraphFingerprint,
+) {
+    let pb = get_progressspinner(format!("Writing {}", name).as_str());
+    write_container_file(path, value, fingerprint).unwrap_or_else(|error| panic!("{}", error));
+    pb.finish_and_clear();
+}
+
+/// Reads a container, see [`crate::container`]. Files written before the
+/// container format existed are read as plain bincode, without any checks.
+pub fn read_container_with_spinnner<T: Payload>(
+    name: &str,
+    path: &Path,
+    expected_fingerprint: Option<&GraphFingerprint>,
+) -> T {
+    let pb = get_progressspinner(format!("Reading {}", name).as_str());
+    let t = match read_container_file(path, expected_fingerprint) {
+        Ok(t) => t,
+        Err(Error::Container {
+            kind: ContainerErrorKind::MissingMagic,
+            ..
+        }) => {
+            warn!(
+                "{} is not a container, reading it as plain bincode without any checks",
+                path.display()
+            );
+            let reader = BufReader::new(File::open(path).unwrap());
+            bincode::deserialize_from(reader).unwrap()
+        }
+        Err(error) => panic!("{}", error),
+    };
+    pb.finish_and_clear();
+    t
+}
+
 pub fn read_bincode_with_spinnner<T>(name: &str, path: &Path) -> T
 where
     T: for<'a> Deserialize<'a>,